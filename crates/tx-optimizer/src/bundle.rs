@@ -1,24 +1,70 @@
-use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::{
+    clock::DEFAULT_MS_PER_SLOT,
+    hash::Hash,
     pubkey::Pubkey,
-    signature::Signature,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    system_instruction::SystemInstruction,
     transaction::Transaction,
 };
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
 
 use crate::config::{Config, JITO_TIP_ACCOUNTS};
+use crate::error::{Result, TxOptimizerError};
+use crate::units::Lamports;
+use crate::util::poll_until;
 
 /// Maximum number of transactions allowed in a single Jito bundle.
-const MAX_BUNDLE_SIZE: usize = 5;
+///
+/// The default for [`JitoBundleBuilder`]; see
+/// [`with_max_bundle_size`](JitoBundleBuilder::with_max_bundle_size).
+pub const MAX_BUNDLE_SIZE: usize = 5;
+
+/// Largest bundle size [`JitoBundleBuilder::with_max_bundle_size`] accepts.
+pub const BUNDLE_SIZE_HARD_CAP: usize = 16;
+
+/// Base58 length of a packet-sized transaction
+/// ([`PACKET_DATA_SIZE`](solana_sdk::packet::PACKET_DATA_SIZE), 1232 bytes).
+///
+/// No bundled transaction may be longer, and a bundle's transactions may add
+/// up to this many characters per allowed transaction; see
+/// [`JitoBundleBuilder::max_bundle_bytes`]. The block engine drops larger
+/// bundles without an error.
+pub const MAX_ENCODED_TX_LEN: usize = 1_683;
 
-/// Default Jito tip in lamports (0.00001 SOL).
-const DEFAULT_TIP_LAMPORTS: u64 = 10_000;
+/// Most base58 characters a bundle of [`MAX_BUNDLE_SIZE`] transactions may
+/// add up to, the default [`JitoBundleBuilder::max_bundle_bytes`].
+pub const MAX_BUNDLE_BYTES: usize = MAX_BUNDLE_SIZE * MAX_ENCODED_TX_LEN;
+
+/// How long after acceptance a bundle missing from `getBundleStatuses` is
+/// still reported as pending; see
+/// [`with_initial_grace`](JitoBundleBuilder::with_initial_grace).
+pub const DEFAULT_INITIAL_GRACE: Duration = Duration::from_secs(5);
+
+/// How long each block engine request may take by default; see
+/// [`with_request_timeout`](JitoBundleBuilder::with_request_timeout).
+pub const DEFAULT_BLOCK_ENGINE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often [`JitoBundleBuilder::submit_and_confirm`] polls the bundle status
+/// by default; see [`with_poll_interval`](JitoBundleBuilder::with_poll_interval).
+pub const DEFAULT_STATUS_POLL_INTERVAL: Duration = Duration::from_millis(500);
 
 /// Bundle submission status returned by the Jito block engine.
+///
+/// Serializes externally tagged (`{"Accepted":{"bundle_id":...}}`); with the
+/// `serialize_v2` feature, internally tagged as
+/// `{"status":"accepted","bundle_id":...}`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "serialize_v2",
+    serde(tag = "status", rename_all = "snake_case")
+)]
 pub enum BundleStatus {
     /// Bundle accepted and pending inclusion
     Accepted { bundle_id: String },
@@ -28,7 +74,13 @@ pub enum BundleStatus {
         slot: u64,
     },
     /// Bundle was rejected by the block engine
-    Rejected { reason: String },
+    Rejected {
+        reason: String,
+        /// JSON-RPC error code from the engine; `None` when the rejection
+        /// was decided client-side, e.g. after running out of retries
+        #[serde(default)]
+        code: Option<i64>,
+    },
     /// Bundle expired without being included
     Expired { bundle_id: String },
 }
@@ -39,6 +91,232 @@ pub struct BundleSubmissionResult {
     pub status: BundleStatus,
     pub attempts: u8,
     pub elapsed_ms: u128,
+    /// Total lamports the bundle's transactions transfer to Jito tip accounts
+    #[serde(default)]
+    pub tip_paid_lamports: u64,
+    /// Client-side bundle id, see [`JitoBundleBuilder::bundle_hash`]
+    #[serde(default)]
+    pub bundle_hash: String,
+    /// Whether the requested tip was clamped into the builder's tip bounds
+    #[serde(default)]
+    pub tip_clamped: bool,
+    /// Which limit ended submission, if it gave up after running out of
+    /// retries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_limit_reached: Option<RetryLimit>,
+}
+
+/// Limit that made [`JitoBundleBuilder::submit`] stop retrying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryLimit {
+    /// Every attempt up to `max_retries` was used
+    Attempts,
+    /// The next attempt would have started after the
+    /// [retry duration](JitoBundleBuilder::with_max_retry_duration)
+    Duration,
+}
+
+impl BundleSubmissionResult {
+    /// Convert a `Rejected` status into [`TxOptimizerError::BundleRejected`]
+    /// for callers that treat rejection as a failure.
+    pub fn into_result(self) -> Result<Self> {
+        match self.status {
+            BundleStatus::Rejected { reason, code } => {
+                Err(TxOptimizerError::BundleRejected { reason, code })
+            }
+            _ => Ok(self),
+        }
+    }
+}
+
+/// Everything `getBundleStatuses` reports about one bundle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleResult {
+    pub bundle_id: String,
+    /// Coarse status, as returned by [`JitoBundleBuilder::check_status`]
+    pub status: BundleStatus,
+    /// Slot the bundle landed in
+    pub slot: Option<u64>,
+    /// `processed`, `confirmed` or `finalized`
+    pub confirmation_status: Option<String>,
+    /// Signatures of the bundle's transactions, in bundle order
+    pub transactions: Vec<String>,
+}
+
+/// How long a bundle has before its blockhash expires, as estimated by
+/// [`JitoBundleBuilder::estimated_expiry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotExpiry {
+    /// Recent blockhash of the bundle's first transaction
+    pub blockhash: Hash,
+    /// Slots left before the blockhash is rejected; 0 once it has expired.
+    /// Counted in blocks, so skipped slots only add time
+    pub remaining_slots: u64,
+    /// `remaining_slots` at the nominal slot time, rounded down
+    pub seconds_remaining: u64,
+    /// Whether `remaining_slots` is exact. It is an upper bound when the
+    /// blockhash is valid but older than the node's latest, since the RPC
+    /// API can't tell how much older
+    pub exact: bool,
+}
+
+impl SlotExpiry {
+    fn new(blockhash: Hash, remaining_slots: u64, exact: bool) -> Self {
+        Self {
+            blockhash,
+            remaining_slots,
+            seconds_remaining: remaining_slots * DEFAULT_MS_PER_SLOT / 1000,
+            exact,
+        }
+    }
+}
+
+/// Hooks called at each stage of bundle submission, for exporting metrics
+/// (attempts, latency, outcome) without parsing logs.
+///
+/// Every method defaults to a no-op, so implementors only override the
+/// events they care about. `elapsed_ms` is measured from the start of the
+/// `submit` call, or of the confirmation wait for `on_landed`/`on_expired`.
+pub trait BundleObserver: Send + Sync {
+    /// A submission attempt is about to be sent (1-based).
+    fn on_attempt(&self, _attempt: u8) {}
+
+    /// The block engine accepted the bundle.
+    fn on_accepted(&self, _bundle_id: &str, _attempts: u8, _elapsed_ms: u128) {}
+
+    /// The bundle landed on-chain.
+    fn on_landed(&self, _bundle_id: &str, _slot: u64, _elapsed_ms: u128) {}
+
+    /// The bundle was rejected, or submission ran out of retries.
+    fn on_rejected(&self, _reason: &str, _attempts: u8) {}
+
+    /// The bundle expired or confirmation timed out.
+    fn on_expired(&self, _bundle_id: &str, _elapsed_ms: u128) {}
+}
+
+/// Observer that ignores every event; the default for new builders.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopObserver;
+
+impl BundleObserver for NoopObserver {}
+
+/// Chooses the account each Jito tip transfer pays.
+///
+/// Implement it to tip a custom or test block engine's accounts, to weight
+/// the choice, or to make it deterministic in tests.
+pub trait TipAccountProvider: Send + Sync {
+    /// The tip account for the next tip transfer.
+    fn select(&self) -> Pubkey;
+}
+
+/// Picks one of Jito's mainnet [`JITO_TIP_ACCOUNTS`] pseudo-randomly; the
+/// default for new builders.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JitoTipAccounts;
+
+impl TipAccountProvider for JitoTipAccounts {
+    fn select(&self) -> Pubkey {
+        Pubkey::from_str(Config::random_tip_account())
+            .expect("JITO_TIP_ACCOUNTS holds valid pubkeys")
+    }
+}
+
+/// Re-signs a bundle transaction against a fresh blockhash; see
+/// [`JitoBundleBuilder::with_resigner`].
+pub type Resigner = dyn Fn(&mut Transaction, Hash) -> Result<()> + Send + Sync;
+
+/// Token-bucket limiter for block engine requests.
+///
+/// Share one limiter via `Arc` between every [`JitoBundleBuilder`] that talks
+/// to the same block engine, so concurrent submissions stay under Jito's
+/// per-IP rate limit together.
+///
+/// # Example
+/// ```no_run
+/// use std::sync::Arc;
+/// use solana_tx_optimizer::bundle::{JitoBundleBuilder, RateLimiter};
+/// use solana_tx_optimizer::config::Config;
+///
+/// let config = Config::from_env();
+/// let limiter = Arc::new(RateLimiter::new(5.0).unwrap());
+/// let a = JitoBundleBuilder::new(&config).unwrap().with_rate_limiter(limiter.clone());
+/// let b = JitoBundleBuilder::new(&config).unwrap().with_rate_limiter(limiter);
+/// ```
+#[derive(Debug)]
+pub struct RateLimiter {
+    requests_per_second: f64,
+    burst: f64,
+    state: Mutex<BucketState>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    /// Allow `requests_per_second` requests, one at a time.
+    ///
+    /// Fails if the rate is not a positive, finite number.
+    pub fn new(requests_per_second: f64) -> Result<Self> {
+        if !(requests_per_second.is_finite() && requests_per_second > 0.0) {
+            return Err(TxOptimizerError::Config(format!(
+                "Invalid rate limit {requests_per_second} (expected requests per second above 0)"
+            )));
+        }
+        Ok(Self {
+            requests_per_second,
+            burst: 1.0,
+            state: Mutex::new(BucketState {
+                tokens: 1.0,
+                refilled_at: Instant::now(),
+            }),
+        })
+    }
+
+    /// Let up to `burst` requests through back to back before spacing
+    /// kicks in. Starts with a full bucket.
+    pub fn with_burst(mut self, burst: u32) -> Result<Self> {
+        if burst == 0 {
+            return Err(TxOptimizerError::Config(
+                "Rate limit burst must be at least 1".to_string(),
+            ));
+        }
+        self.burst = burst as f64;
+        self.state = Mutex::new(BucketState {
+            tokens: self.burst,
+            refilled_at: Instant::now(),
+        });
+        Ok(self)
+    }
+
+    /// Configured rate in requests per second.
+    pub fn requests_per_second(&self) -> f64 {
+        self.requests_per_second
+    }
+
+    /// Wait until a request may be sent, then consume a token.
+    pub async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+                let now = Instant::now();
+                let refill =
+                    now.duration_since(state.refilled_at).as_secs_f64() * self.requests_per_second;
+                state.tokens = (state.tokens + refill).min(self.burst);
+                state.refilled_at = now;
+                if state.tokens >= 1.0 {
+                    state.tokens -= 1.0;
+                    return;
+                }
+                Duration::from_secs_f64((1.0 - state.tokens) / self.requests_per_second)
+            };
+            debug!("Rate limited, waiting {}ms", wait.as_millis());
+            tokio::time::sleep(wait).await;
+        }
+    }
 }
 
 /// Builder for constructing and submitting Jito bundles.
@@ -52,7 +330,7 @@ pub struct BundleSubmissionResult {
 /// use solana_tx_optimizer::config::Config;
 ///
 /// let config = Config::from_env();
-/// let mut builder = JitoBundleBuilder::new(&config);
+/// let mut builder = JitoBundleBuilder::new(&config).unwrap();
 /// // builder.add_transaction(tx1)?;
 /// // builder.set_tip(50_000); // 0.00005 SOL tip
 /// // let result = builder.submit().await?;
@@ -62,20 +340,363 @@ pub struct JitoBundleBuilder {
     transactions: Vec<Vec<u8>>,
     /// Tip amount in lamports paid to Jito validators
     tip_lamports: u64,
+    /// Inclusive bounds [`set_tip`](Self::set_tip) clamps to
+    min_tip_lamports: u64,
+    max_tip_lamports: u64,
+    /// Whether the last tip set was clamped into the bounds
+    tip_clamped: bool,
     /// Jito block engine endpoint URL
     block_engine_url: String,
     /// Maximum retry attempts for submission
     max_retries: u8,
+    /// Wall-clock budget for all submission attempts; unlimited when `None`
+    max_retry_duration: Option<Duration>,
+    /// Maximum transactions per bundle; [`MAX_BUNDLE_SIZE`] by default
+    max_bundle_size: usize,
+    /// HTTP client shared by all block engine requests so connections and
+    /// TLS sessions are pooled across calls
+    http_client: reqwest::Client,
+    /// Receives submission events; [`NoopObserver`] unless one is registered
+    observer: Arc<dyn BundleObserver>,
+    /// Chooses tip accounts; [`JitoTipAccounts`] unless one is registered
+    tip_provider: Arc<dyn TipAccountProvider>,
+    /// Paces block engine requests; unlimited when `None`
+    rate_limiter: Option<Arc<RateLimiter>>,
+    /// Checks blockhashes before submission when set
+    rpc: Option<Arc<RpcClient>>,
+    /// Re-signs transactions whose blockhash expired, together with `rpc`
+    resigner: Option<Arc<Resigner>>,
+    /// Bundle hashes the block engine may already have, either accepted or
+    /// sent without a response
+    submitted: Mutex<HashSet<String>>,
+    /// When each bundle id was accepted, for the status grace window
+    accepted_at: Mutex<HashMap<String, Instant>>,
+    /// Bundle ids replaced by [`resubmit_with_tip`](Self::resubmit_with_tip)
+    superseded: Mutex<HashSet<String>>,
+    /// Window after acceptance in which an unknown bundle counts as pending
+    initial_grace: Duration,
+    /// Timeout for each `sendBundle` and `getBundleStatuses` request
+    request_timeout: Duration,
+    /// Delay between status checks in `submit_and_confirm`
+    poll_interval: Duration,
+    /// Whether `add_transaction` rejects transactions that aren't fully signed
+    verify_signatures: bool,
 }
 
 impl JitoBundleBuilder {
     /// Create a new bundle builder with the given configuration.
-    pub fn new(config: &Config) -> Self {
-        Self {
+    ///
+    /// Block engine requests go through `config.http_proxy` if set. The tip
+    /// starts at `config.jito_tip_lamports`, clamped into
+    /// `config.min_tip_lamports..=config.max_tip_lamports`.
+    ///
+    /// Fails if the block engine HTTP client cannot be built from `config`,
+    /// e.g. for an invalid proxy URL or an unreadable CA bundle.
+    pub fn new(config: &Config) -> Result<Self> {
+        let mut builder = Self {
             transactions: Vec::with_capacity(MAX_BUNDLE_SIZE),
             tip_lamports: config.jito_tip_lamports,
+            min_tip_lamports: config.min_tip_lamports,
+            max_tip_lamports: config.max_tip_lamports.max(config.min_tip_lamports),
+            tip_clamped: false,
             block_engine_url: config.jito_block_engine_url.clone(),
             max_retries: config.max_retries,
+            max_retry_duration: None,
+            max_bundle_size: MAX_BUNDLE_SIZE,
+            http_client: config.block_engine_http_client()?,
+            observer: Arc::new(NoopObserver),
+            tip_provider: Arc::new(JitoTipAccounts),
+            rate_limiter: None,
+            rpc: None,
+            resigner: None,
+            submitted: Mutex::new(HashSet::new()),
+            accepted_at: Mutex::new(HashMap::new()),
+            superseded: Mutex::new(HashSet::new()),
+            initial_grace: DEFAULT_INITIAL_GRACE,
+            request_timeout: DEFAULT_BLOCK_ENGINE_TIMEOUT,
+            poll_interval: DEFAULT_STATUS_POLL_INTERVAL,
+            verify_signatures: true,
+        };
+        builder.clamp_tip(config.jito_tip_lamports);
+        Ok(builder)
+    }
+
+    /// Use a custom HTTP client for block engine requests, e.g. one with
+    /// its own timeouts or a proxy.
+    ///
+    /// The per-request timeout set by
+    /// [`with_request_timeout`](Self::with_request_timeout) still applies.
+    pub fn with_http_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = client;
+        self
+    }
+
+    /// Stop retrying [`submit`](Self::submit) once the next attempt, after
+    /// its backoff, would start more than `duration` after the first one.
+    ///
+    /// Applies on top of `max_retries`, whichever runs out first. The budget
+    /// is checked between attempts, so an attempt already in flight is not
+    /// cut short. Unlimited by default.
+    pub fn with_max_retry_duration(mut self, duration: Duration) -> Self {
+        self.max_retry_duration = Some(duration);
+        self
+    }
+
+    /// Allow up to `size` transactions per bundle, for block engines whose
+    /// limit differs from Jito mainnet's [`MAX_BUNDLE_SIZE`].
+    ///
+    /// Fails if `size` is zero, above [`BUNDLE_SIZE_HARD_CAP`], or smaller
+    /// than the number of transactions already added.
+    pub fn with_max_bundle_size(mut self, size: usize) -> Result<Self> {
+        if size == 0 || size > BUNDLE_SIZE_HARD_CAP {
+            return Err(TxOptimizerError::Config(format!(
+                "Invalid max bundle size {size} (expected 1 to {BUNDLE_SIZE_HARD_CAP})"
+            )));
+        }
+        if size < self.transactions.len() {
+            return Err(TxOptimizerError::Config(format!(
+                "Max bundle size {size} is below the {} transactions already added",
+                self.transactions.len()
+            )));
+        }
+        self.max_bundle_size = size;
+        Ok(self)
+    }
+
+    /// Maximum number of transactions this builder accepts.
+    pub fn max_bundle_size(&self) -> usize {
+        self.max_bundle_size
+    }
+
+    /// Most base58 characters the bundle's transactions may add up to:
+    /// [`MAX_ENCODED_TX_LEN`] for each of the
+    /// [`max_bundle_size`](Self::max_bundle_size) transactions, which is
+    /// [`MAX_BUNDLE_BYTES`] by default.
+    pub fn max_bundle_bytes(&self) -> usize {
+        self.max_bundle_size * MAX_ENCODED_TX_LEN
+    }
+
+    /// Register an observer for submission metrics.
+    pub fn with_observer(mut self, observer: Arc<dyn BundleObserver>) -> Self {
+        self.observer = observer;
+        self
+    }
+
+    /// Choose tip accounts for [`tip_instruction`](Self::tip_instruction)
+    /// with `provider` instead of Jito's mainnet accounts.
+    pub fn with_tip_provider(mut self, provider: Arc<dyn TipAccountProvider>) -> Self {
+        self.tip_provider = provider;
+        self
+    }
+
+    /// Wait for a permit from `limiter` before every block engine request,
+    /// including each submission retry and status check.
+    pub fn with_rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Report bundles accepted less than `grace` ago as still pending when
+    /// `getBundleStatuses` doesn't know them yet.
+    ///
+    /// The block engine can take a moment to index a bundle it has just
+    /// accepted, so an empty status response right after
+    /// [`submit`](Self::submit) doesn't mean the bundle is gone. Within the
+    /// window [`check_status`](Self::check_status) returns
+    /// [`BundleStatus::Accepted`]; after it, or for bundles this builder
+    /// never saw accepted, an unknown bundle is [`BundleStatus::Expired`].
+    /// Defaults to [`DEFAULT_INITIAL_GRACE`].
+    pub fn with_initial_grace(mut self, grace: Duration) -> Self {
+        self.initial_grace = grace;
+        self
+    }
+
+    /// Give up on each block engine request, `sendBundle` or
+    /// `getBundleStatuses`, after `timeout`. A timed-out submission counts as
+    /// a failed attempt and is retried. Defaults to
+    /// [`DEFAULT_BLOCK_ENGINE_TIMEOUT`].
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Check the bundle status every `interval` while
+    /// [`submit_and_confirm`](Self::submit_and_confirm) waits. Defaults to
+    /// [`DEFAULT_STATUS_POLL_INTERVAL`].
+    ///
+    /// Fails if `interval` is zero.
+    pub fn with_poll_interval(mut self, interval: Duration) -> Result<Self> {
+        if interval.is_zero() {
+            return Err(TxOptimizerError::Config(
+                "Bundle status poll interval must be positive".into(),
+            ));
+        }
+        self.poll_interval = interval;
+        Ok(self)
+    }
+
+    /// Turn off the signature check in
+    /// [`add_transaction`](Self::add_transaction).
+    ///
+    /// The block engine rejects bundles with unsigned or badly signed
+    /// transactions, so only disable this when the serialized bundle is
+    /// signed elsewhere before it is sent, e.g. by taking
+    /// [`build`](Self::build)'s payload to an external signer.
+    pub fn with_signature_verification(mut self, verify: bool) -> Self {
+        self.verify_signatures = verify;
+        self
+    }
+
+    /// Check every transaction's blockhash against `client` before
+    /// [`submit`](Self::submit) sends the bundle.
+    pub fn with_rpc(mut self, client: Arc<RpcClient>) -> Self {
+        self.rpc = Some(client);
+        self
+    }
+
+    /// Let [`submit`](Self::submit) recover from an expired blockhash by
+    /// re-signing the bundle against a fresh one from the RPC client
+    /// attached via [`with_rpc`](Self::with_rpc), then retrying once.
+    ///
+    /// `resigner` is called for each transaction with the fresh blockhash
+    /// and must sign it again, typically with
+    /// `tx.try_sign(&[&payer], blockhash)`. This happens when
+    /// [`validate_blockhashes`](Self::validate_blockhashes) finds a stale
+    /// blockhash before sending, or when the block engine rejects the bundle
+    /// with "blockhash not found". The re-signed transactions are only used
+    /// for that submission; the builder keeps the originals. Without an RPC
+    /// client, or without a resigner, stale blockhashes fail as before.
+    ///
+    /// # Security
+    ///
+    /// The resigner usually captures the signing keypairs, so they live as
+    /// long as the builder and anything holding it. It can sign any message
+    /// it is handed, not just these transactions: keep the builder out of
+    /// untrusted code, and prefer a closure over a remote or hardware signer
+    /// when keys shouldn't sit in process memory.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use std::sync::Arc;
+    /// use solana_client::nonblocking::rpc_client::RpcClient;
+    /// use solana_sdk::signature::Keypair;
+    /// use solana_tx_optimizer::bundle::JitoBundleBuilder;
+    /// use solana_tx_optimizer::config::Config;
+    ///
+    /// let config = Config::from_env();
+    /// let payer = Keypair::new();
+    /// let builder = JitoBundleBuilder::new(&config)
+    ///     .unwrap()
+    ///     .with_rpc(Arc::new(RpcClient::new(config.rpc_url.clone())))
+    ///     .with_resigner(move |tx, blockhash| Ok(tx.try_sign(&[&payer], blockhash)?));
+    /// ```
+    pub fn with_resigner(
+        mut self,
+        resigner: impl Fn(&mut Transaction, Hash) -> Result<()> + Send + Sync + 'static,
+    ) -> Self {
+        self.resigner = Some(Arc::new(resigner));
+        self
+    }
+
+    /// Ask the attached RPC node whether each transaction's recent blockhash
+    /// is still valid, at the client's commitment.
+    ///
+    /// Fails with [`TxOptimizerError::StaleBlockhash`] naming the first
+    /// stale transaction. Does nothing if no client is attached via
+    /// [`with_rpc`](Self::with_rpc).
+    pub async fn validate_blockhashes(&self) -> Result<()> {
+        self.check_blockhashes(&self.transactions).await
+    }
+
+    async fn check_blockhashes(&self, transactions: &[Vec<u8>]) -> Result<()> {
+        let Some(client) = &self.rpc else {
+            return Ok(());
+        };
+
+        let mut checked: HashMap<Hash, bool> = HashMap::new();
+        for (index, tx_bytes) in transactions.iter().enumerate() {
+            let tx: Transaction = bincode::deserialize(tx_bytes)?;
+            let blockhash = tx.message.recent_blockhash;
+            let valid = match checked.get(&blockhash) {
+                Some(&valid) => valid,
+                None => {
+                    let valid = client
+                        .is_blockhash_valid(&blockhash, client.commitment())
+                        .await?;
+                    checked.insert(blockhash, valid);
+                    valid
+                }
+            };
+            if !valid {
+                return Err(TxOptimizerError::StaleBlockhash { index, blockhash });
+            }
+        }
+        debug!("All {} bundle blockhashes are valid", transactions.len());
+        Ok(())
+    }
+
+    /// Estimate how long the bundle has before the blockhash of its first
+    /// transaction expires, at `client`'s commitment.
+    ///
+    /// An expired blockhash gives zero remaining slots. Otherwise the
+    /// remaining slots are the latest blockhash's last valid block height
+    /// minus the current block height, which is exact when the bundle was
+    /// signed against that latest blockhash and an upper bound otherwise
+    /// (see [`SlotExpiry::exact`]). Fails with
+    /// [`TxOptimizerError::EmptyBundle`] if there are no transactions.
+    pub async fn estimated_expiry(&self, client: &RpcClient) -> Result<SlotExpiry> {
+        let tx_bytes = self
+            .transactions
+            .first()
+            .ok_or(TxOptimizerError::EmptyBundle)?;
+        let tx: Transaction = bincode::deserialize(tx_bytes)?;
+        let blockhash = tx.message.recent_blockhash;
+        let commitment = client.commitment();
+
+        if !client.is_blockhash_valid(&blockhash, commitment).await? {
+            return Ok(SlotExpiry::new(blockhash, 0, true));
+        }
+        let (latest, last_valid_block_height) = client
+            .get_latest_blockhash_with_commitment(commitment)
+            .await?;
+        let block_height = client.get_block_height_with_commitment(commitment).await?;
+        Ok(SlotExpiry::new(
+            blockhash,
+            last_valid_block_height.saturating_sub(block_height),
+            blockhash == latest,
+        ))
+    }
+
+    /// Re-sign the bundle's transactions against the RPC node's latest
+    /// blockhash, or `None` if no RPC client or resigner is attached.
+    async fn resign(&self) -> Result<Option<Vec<Vec<u8>>>> {
+        let (Some(client), Some(resigner)) = (&self.rpc, &self.resigner) else {
+            return Ok(None);
+        };
+
+        let blockhash = client.get_latest_blockhash().await?;
+        info!("Re-signing bundle against blockhash {blockhash}");
+        self.transactions
+            .iter()
+            .enumerate()
+            .map(|(index, tx_bytes)| {
+                let mut tx: Transaction = bincode::deserialize(tx_bytes)?;
+                resigner(&mut tx, blockhash)?;
+                if self.verify_signatures {
+                    if let Some(reason) = signature_problem(&tx) {
+                        return Err(TxOptimizerError::InvalidSignature { index, reason });
+                    }
+                }
+                Ok(bincode::serialize(&tx)?)
+            })
+            .collect::<Result<_>>()
+            .map(Some)
+    }
+
+    /// Wait for the rate limiter, if one is attached.
+    async fn throttle(&self) {
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire().await;
         }
     }
 
@@ -83,77 +704,339 @@ impl JitoBundleBuilder {
     ///
     /// Transactions execute in the order they are added.
     /// Returns an error if the bundle already contains the maximum number of transactions.
+    ///
+    /// Unless [turned off](Self::with_signature_verification), every required
+    /// signature must be present and valid; otherwise this fails with
+    /// [`TxOptimizerError::InvalidSignature`] naming the index the
+    /// transaction would have had.
     pub fn add_transaction(&mut self, tx: &Transaction) -> Result<&mut Self> {
-        if self.transactions.len() >= MAX_BUNDLE_SIZE {
-            bail!(
-                "Bundle already contains {} transactions (max {})",
-                self.transactions.len(),
-                MAX_BUNDLE_SIZE
-            );
+        if self.transactions.len() >= self.max_bundle_size {
+            return Err(TxOptimizerError::BundleTooLarge {
+                max: self.max_bundle_size,
+            });
+        }
+        if self.verify_signatures {
+            if let Some(reason) = signature_problem(tx) {
+                return Err(TxOptimizerError::InvalidSignature {
+                    index: self.transactions.len(),
+                    reason,
+                });
+            }
         }
 
-        let serialized = bincode::serialize(tx)
-            .context("Failed to serialize transaction")?;
+        let serialized = bincode::serialize(tx)?;
 
         debug!(
             "Added transaction to bundle (size: {}/{})",
             self.transactions.len() + 1,
-            MAX_BUNDLE_SIZE
+            self.max_bundle_size
         );
 
         self.transactions.push(serialized);
         Ok(self)
     }
 
+    /// Only accept tips within `min..=max` lamports, guarding against a zero
+    /// tip that never lands or an extra zero that overpays. The current tip
+    /// is clamped into the new bounds.
+    ///
+    /// Fails if `min` is greater than `max`.
+    pub fn with_tip_bounds(mut self, min: u64, max: u64) -> Result<Self> {
+        if min > max {
+            return Err(TxOptimizerError::Config(format!(
+                "Invalid tip bounds: min {} is above max {}",
+                Lamports(min),
+                Lamports(max)
+            )));
+        }
+        self.min_tip_lamports = min;
+        self.max_tip_lamports = max;
+        self.clamp_tip(self.tip_lamports);
+        Ok(self)
+    }
+
     /// Set the tip amount in lamports paid to Jito validators.
     ///
     /// Higher tips increase the probability of bundle inclusion.
-    /// The tip is paid to a randomly selected Jito tip account.
+    /// The tip is paid to an account chosen by the
+    /// [tip provider](Self::with_tip_provider), see
+    /// [`tip_instruction`](Self::tip_instruction). A tip outside the
+    /// [tip bounds](Self::with_tip_bounds) is clamped with a warning and
+    /// reported as `tip_clamped` in the submission result; use
+    /// [`try_set_tip`](Self::try_set_tip) to reject it instead.
     pub fn set_tip(&mut self, lamports: u64) -> &mut Self {
-        self.tip_lamports = lamports;
-        info!("Bundle tip set to {} lamports ({} SOL)", lamports, lamports as f64 / 1e9);
+        self.clamp_tip(lamports);
+        info!("Bundle tip set to {}", Lamports(self.tip_lamports));
         self
     }
 
-    /// Get a random Jito tip account pubkey for the tip transfer.
-    fn random_tip_account() -> Result<Pubkey> {
-        let account_str = Config::random_tip_account();
-        Pubkey::from_str(account_str)
-            .context("Failed to parse Jito tip account pubkey")
+    /// Strict form of [`set_tip`](Self::set_tip): fails with
+    /// [`TxOptimizerError::Config`] instead of clamping a tip outside the
+    /// tip bounds.
+    pub fn try_set_tip(&mut self, lamports: u64) -> Result<&mut Self> {
+        if !(self.min_tip_lamports..=self.max_tip_lamports).contains(&lamports) {
+            return Err(TxOptimizerError::Config(format!(
+                "Tip {} is outside the allowed range {} to {}",
+                Lamports(lamports),
+                Lamports(self.min_tip_lamports),
+                Lamports(self.max_tip_lamports)
+            )));
+        }
+        Ok(self.set_tip(lamports))
     }
 
-    /// Build the bundle payload for submission to the Jito block engine.
-    ///
-    /// Encodes all transactions as base58 strings in the format expected
-    /// by the `sendBundle` JSON-RPC method.
-    pub fn build(&self) -> Result<serde_json::Value> {
-        if self.transactions.is_empty() {
-            bail!("Cannot build an empty bundle");
+    /// Current tip in lamports, after clamping.
+    pub fn tip_lamports(&self) -> u64 {
+        self.tip_lamports
+    }
+
+    /// A transfer of the current tip from `payer` to an account chosen by
+    /// the tip provider, for the last transaction of the bundle.
+    pub fn tip_instruction(&self, payer: &Pubkey) -> solana_sdk::instruction::Instruction {
+        solana_sdk::system_instruction::transfer(
+            payer,
+            &self.tip_provider.select(),
+            self.tip_lamports,
+        )
+    }
+
+    fn clamp_tip(&mut self, lamports: u64) {
+        let tip = self.clamped_tip(lamports);
+        self.tip_clamped = tip != lamports;
+        if self.tip_clamped {
+            warn!(
+                "Tip {} is outside {} to {}, clamping to {}",
+                Lamports(lamports),
+                Lamports(self.min_tip_lamports),
+                Lamports(self.max_tip_lamports),
+                Lamports(tip)
+            );
         }
+        self.tip_lamports = tip;
+    }
 
-        let encoded_txs: Vec<String> = self
-            .transactions
+    /// `lamports` clamped into the tip bounds.
+    fn clamped_tip(&self, lamports: u64) -> u64 {
+        lamports.clamp(self.min_tip_lamports, self.max_tip_lamports)
+    }
+
+    /// Sum the lamports every transaction in the bundle transfers to a Jito
+    /// tip account.
+    ///
+    /// This is what the bundle actually pays, which may differ from the
+    /// intended tip passed to [`set_tip`](Self::set_tip).
+    pub fn total_tip_lamports(&self) -> Result<u64> {
+        total_tip_lamports(&self.transactions)
+    }
+
+    /// Idempotency key for the bundle's current transactions: the hex
+    /// SHA-256 of their first signatures joined with commas.
+    ///
+    /// This is how Jito's block engine derives bundle ids, so the hash can be
+    /// passed to [`bundle_result`](Self::bundle_result) even when the
+    /// `sendBundle` response was lost. Other engines may assign ids
+    /// differently.
+    pub fn bundle_hash(&self) -> Result<String> {
+        bundle_hash(&self.transactions)
+    }
+
+    /// Record that the engine may already hold the bundle `hash`.
+    fn mark_submitted(&self, hash: &str) {
+        self.submitted
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(hash.to_string());
+    }
+
+    fn was_submitted(&self, hash: &str) -> bool {
+        self.submitted
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(hash)
+    }
+
+    fn mark_accepted(&self, bundle_id: &str) {
+        self.accepted_at
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(bundle_id.to_string(), Instant::now());
+    }
+
+    /// Whether `bundle_id` was accepted within the initial grace window.
+    fn in_initial_grace(&self, bundle_id: &str) -> bool {
+        self.accepted_at
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(bundle_id)
+            .is_some_and(|accepted| accepted.elapsed() < self.initial_grace)
+    }
+
+    /// The most recently accepted bundle id, if any.
+    fn last_accepted(&self) -> Option<String> {
+        self.accepted_at
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
             .iter()
-            .map(|tx_bytes| bs58::encode(tx_bytes).into_string())
-            .collect();
+            .max_by_key(|(_, accepted)| **accepted)
+            .map(|(bundle_id, _)| bundle_id.clone())
+    }
 
-        let payload = serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "sendBundle",
-            "params": [encoded_txs]
-        });
+    /// Whether `bundle_id` was replaced by a later
+    /// [`resubmit_with_tip`](Self::resubmit_with_tip).
+    pub fn is_superseded(&self, bundle_id: &str) -> bool {
+        self.superseded
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(bundle_id)
+    }
+
+    /// Total length of the bundle's transactions once base58-encoded, which
+    /// is what `sendBundle` carries and
+    /// [`max_bundle_bytes`](Self::max_bundle_bytes) limits.
+    pub fn total_serialized_bytes(&self) -> usize {
+        self.transactions
+            .iter()
+            .map(|tx_bytes| bs58::encode(tx_bytes).into_string().len())
+            .sum()
+    }
 
-        debug!("Built bundle with {} transactions", encoded_txs.len());
-        Ok(payload)
+    /// Build the bundle payload for submission to the Jito block engine.
+    ///
+    /// Encodes all transactions as base58 strings in the format expected
+    /// by the `sendBundle` JSON-RPC method. Fails with
+    /// [`TxOptimizerError::TransactionTooLarge`] if one of them is longer than
+    /// [`MAX_ENCODED_TX_LEN`], or [`TxOptimizerError::BundleTooManyBytes`] if
+    /// they add up to more than [`max_bundle_bytes`](Self::max_bundle_bytes).
+    pub fn build(&self) -> Result<serde_json::Value> {
+        build_payload(&self.transactions, self.max_bundle_bytes())
     }
 
     /// Submit the bundle to the Jito block engine with retry logic.
     ///
     /// Uses exponential backoff on failure. Returns the bundle ID on success.
+    /// Retries stop after `max_retries` attempts or the
+    /// [retry duration](Self::with_max_retry_duration), recorded in
+    /// [`BundleSubmissionResult::retry_limit_reached`].
+    ///
+    /// Each request carries the [`bundle_hash`](Self::bundle_hash) in an
+    /// `Idempotency-Key` header. Once a request times out, or the bundle has
+    /// been accepted, later attempts (including later `submit` calls on this
+    /// builder) first look the hash up with `getBundleStatuses` and only
+    /// resend if the engine does not know it. This guards against resending
+    /// after an ambiguous timeout, but engines are not required to honor the
+    /// header, so full idempotency depends on the engine.
+    ///
+    /// With an RPC client attached via [`with_rpc`](Self::with_rpc), stale
+    /// blockhashes are caught by
+    /// [`validate_blockhashes`](Self::validate_blockhashes) before anything
+    /// is sent. With a [resigner](Self::with_resigner) as well, a stale or
+    /// "blockhash not found" bundle is re-signed and retried once instead;
+    /// the result then counts the attempts and time of both submissions.
     pub async fn submit(&self) -> Result<BundleSubmissionResult> {
-        let payload = self.build()?;
-        let client = reqwest::Client::new();
+        self.build()?;
+        match self.validate_blockhashes().await {
+            Err(TxOptimizerError::StaleBlockhash { index, blockhash }) => {
+                let Some(resigned) = self.resign().await? else {
+                    return Err(TxOptimizerError::StaleBlockhash { index, blockhash });
+                };
+                warn!("Transaction {index} uses expired blockhash {blockhash}, re-signed");
+                return self.send_bundle(&resigned).await;
+            }
+            result => result?,
+        }
+
+        let first = self.send_bundle(&self.transactions).await?;
+        if !is_blockhash_not_found(&first.status) {
+            return Ok(first);
+        }
+        let Some(resigned) = self.resign().await? else {
+            return Ok(first);
+        };
+        warn!("Block engine reports blockhash not found, retrying once re-signed");
+        let retry = self.send_bundle(&resigned).await?;
+        Ok(BundleSubmissionResult {
+            attempts: first.attempts.saturating_add(retry.attempts),
+            elapsed_ms: first.elapsed_ms + retry.elapsed_ms,
+            ..retry
+        })
+    }
+
+    /// Replace a pending bundle with one paying `new_tip` lamports instead of
+    /// waiting for it to expire.
+    ///
+    /// Rewrites the tip transfer in the last transaction, re-signs that
+    /// transaction with the [resigner](Self::with_resigner) and
+    /// [submits](Self::submit) the bundle again, which gives it a new bundle
+    /// id. Once the new bundle is accepted, the previously accepted id is
+    /// marked superseded: [`bundle_result`](Self::bundle_result) reports it
+    /// expired unless it already landed, so pollers stop waiting on it. The
+    /// tip is clamped into the [tip bounds](Self::with_tip_bounds) like
+    /// [`set_tip`](Self::set_tip).
+    ///
+    /// Re-signing is required because changing the tip changes the message,
+    /// so this fails with [`TxOptimizerError::Config`] when no resigner is
+    /// attached, and when the last transaction has no tip transfer to
+    /// rewrite. Earlier transactions keep their signatures, so at most one
+    /// of the two bundles can land; a single-transaction bundle has no such
+    /// guard and both versions may land while the old one is still pending.
+    pub async fn resubmit_with_tip(&mut self, new_tip: u64) -> Result<BundleSubmissionResult> {
+        let Some(resigner) = self.resigner.clone() else {
+            return Err(TxOptimizerError::Config(
+                "Replacing a bundle's tip requires a resigner, see with_resigner".into(),
+            ));
+        };
+        let index = self
+            .transactions
+            .len()
+            .checked_sub(1)
+            .ok_or(TxOptimizerError::EmptyBundle)?;
+
+        // Rewrite a copy so a failure leaves the bundle and tip untouched
+        let mut tx: Transaction = bincode::deserialize(&self.transactions[index])?;
+        if !set_tip_lamports(&mut tx, self.clamped_tip(new_tip))? {
+            return Err(TxOptimizerError::Config(format!(
+                "Transaction {index} has no tip transfer to replace"
+            )));
+        }
+        let blockhash = tx.message.recent_blockhash;
+        resigner(&mut tx, blockhash)?;
+        if self.verify_signatures {
+            if let Some(reason) = signature_problem(&tx) {
+                return Err(TxOptimizerError::InvalidSignature { index, reason });
+            }
+        }
+        let tx_bytes = bincode::serialize(&tx)?;
+        self.clamp_tip(new_tip);
+        self.transactions[index] = tx_bytes;
+        info!("Replacing bundle with tip {}", Lamports(self.tip_lamports));
+
+        let previous = self.last_accepted();
+        let result = self.submit().await?;
+        if let (Some(previous), BundleStatus::Accepted { bundle_id }) = (previous, &result.status) {
+            if previous != *bundle_id {
+                info!("Bundle {previous} superseded by {bundle_id}");
+                self.superseded
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(previous);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Submit serialized `transactions` with retries; the body of
+    /// [`submit`](Self::submit).
+    async fn send_bundle(&self, transactions: &[Vec<u8>]) -> Result<BundleSubmissionResult> {
+        let payload = build_payload(transactions, self.max_bundle_bytes())?;
+        let bundle_hash = bundle_hash(transactions)?;
+        let tip_paid_lamports = total_tip_lamports(transactions)?;
+        if tip_paid_lamports != self.tip_lamports {
+            warn!(
+                "Bundle pays {} in tips, but the configured tip is {}",
+                Lamports(tip_paid_lamports),
+                Lamports(self.tip_lamports)
+            );
+        }
         let start = std::time::Instant::now();
 
         let bundle_endpoint = format!("{}/api/v1/bundles", self.block_engine_url);
@@ -163,29 +1046,69 @@ impl JitoBundleBuilder {
                 "Submitting bundle to {} (attempt {}/{})",
                 self.block_engine_url, attempt, self.max_retries
             );
+            self.observer.on_attempt(attempt);
+
+            if self.was_submitted(&bundle_hash) {
+                match self.bundle_result(&bundle_hash).await {
+                    Ok(BundleResult {
+                        status: BundleStatus::Expired { .. },
+                        ..
+                    }) => debug!("Bundle {bundle_hash} unknown to the block engine, resending"),
+                    Ok(known) => {
+                        info!("Bundle {bundle_hash} already submitted, not resending");
+                        let elapsed_ms = start.elapsed().as_millis();
+                        self.observer.on_accepted(&bundle_hash, attempt, elapsed_ms);
+                        return Ok(BundleSubmissionResult {
+                            status: known.status,
+                            attempts: attempt,
+                            elapsed_ms,
+                            tip_paid_lamports,
+                            tip_clamped: self.tip_clamped,
+                            bundle_hash,
+                            retry_limit_reached: None,
+                        });
+                    }
+                    Err(e) => warn!("Could not check status of bundle {bundle_hash}: {e}"),
+                }
+            }
+            self.throttle().await;
 
-            match client
+            match self
+                .http_client
                 .post(&bundle_endpoint)
+                .header("Idempotency-Key", &bundle_hash)
                 .json(&payload)
-                .timeout(Duration::from_secs(10))
+                .timeout(self.request_timeout)
                 .send()
                 .await
             {
                 Ok(response) => {
                     let status_code = response.status();
-                    let body: serde_json::Value = response
-                        .json()
-                        .await
-                        .context("Failed to parse bundle submission response")?;
+                    // A non-JSON body (e.g. a proxy error page) is retryable
+                    let body: serde_json::Value = match response.json().await {
+                        Ok(body) => body,
+                        Err(e) => {
+                            warn!("Unreadable bundle submission response ({status_code}): {e}");
+                            serde_json::Value::Null
+                        }
+                    };
 
                     if status_code.is_success() {
                         if let Some(result) = body.get("result") {
                             let bundle_id = result.as_str().unwrap_or("unknown").to_string();
                             info!("Bundle accepted: {bundle_id}");
+                            let elapsed_ms = start.elapsed().as_millis();
+                            self.observer.on_accepted(&bundle_id, attempt, elapsed_ms);
+                            self.mark_submitted(&bundle_hash);
+                            self.mark_accepted(&bundle_id);
                             return Ok(BundleSubmissionResult {
                                 status: BundleStatus::Accepted { bundle_id },
                                 attempts: attempt,
-                                elapsed_ms: start.elapsed().as_millis(),
+                                elapsed_ms,
+                                tip_paid_lamports,
+                                tip_clamped: self.tip_clamped,
+                                bundle_hash,
+                                retry_limit_reached: None,
                             });
                         }
                     }
@@ -197,51 +1120,99 @@ impl JitoBundleBuilder {
                             .and_then(|m| m.as_str())
                             .unwrap_or("Unknown error")
                             .to_string();
+                        let code = error.get("code").and_then(|c| c.as_i64());
+                        let retryable =
+                            is_retryable_code(code).unwrap_or_else(|| is_retryable(&reason));
 
-                        // Non-retryable errors
-                        if reason.contains("already processed")
-                            || reason.contains("blockhash not found")
-                        {
+                        if !retryable {
+                            self.observer.on_rejected(&reason, attempt);
                             return Ok(BundleSubmissionResult {
-                                status: BundleStatus::Rejected { reason },
+                                status: BundleStatus::Rejected { reason, code },
                                 attempts: attempt,
                                 elapsed_ms: start.elapsed().as_millis(),
+                                tip_paid_lamports,
+                                tip_clamped: self.tip_clamped,
+                                bundle_hash,
+                                retry_limit_reached: None,
                             });
                         }
 
-                        warn!("Bundle submission error (retryable): {reason}");
+                        match code {
+                            Some(code) => {
+                                warn!("Bundle submission error {code} (retryable): {reason}")
+                            }
+                            None => warn!("Bundle submission error (retryable): {reason}"),
+                        }
                     }
                 }
                 Err(e) => {
                     error!("Bundle submission request failed: {e}");
+                    // The engine may have received the bundle before the timeout
+                    if e.is_timeout() {
+                        self.mark_submitted(&bundle_hash);
+                    }
                 }
             }
 
             // Exponential backoff: 100ms, 200ms, 400ms, ...
             if attempt < self.max_retries {
                 let backoff = Duration::from_millis(100 * 2u64.pow((attempt - 1) as u32));
+                if let Some(budget) = self.max_retry_duration {
+                    if start.elapsed() + backoff > budget {
+                        warn!(
+                            "Retry budget of {}ms exhausted after {attempt} attempt(s)",
+                            budget.as_millis()
+                        );
+                        let reason = "Retry duration exceeded".to_string();
+                        self.observer.on_rejected(&reason, attempt);
+                        return Ok(BundleSubmissionResult {
+                            status: BundleStatus::Rejected { reason, code: None },
+                            attempts: attempt,
+                            elapsed_ms: start.elapsed().as_millis(),
+                            tip_paid_lamports,
+                            tip_clamped: self.tip_clamped,
+                            bundle_hash,
+                            retry_limit_reached: Some(RetryLimit::Duration),
+                        });
+                    }
+                }
                 debug!("Retrying in {}ms", backoff.as_millis());
                 tokio::time::sleep(backoff).await;
             }
         }
 
+        let reason = "Max retries exceeded".to_string();
+        self.observer.on_rejected(&reason, self.max_retries);
         Ok(BundleSubmissionResult {
-            status: BundleStatus::Rejected {
-                reason: "Max retries exceeded".to_string(),
-            },
+            status: BundleStatus::Rejected { reason, code: None },
             attempts: self.max_retries,
             elapsed_ms: start.elapsed().as_millis(),
+            tip_paid_lamports,
+            tip_clamped: self.tip_clamped,
+            bundle_hash,
+            retry_limit_reached: Some(RetryLimit::Attempts),
         })
     }
 
     /// Check the status of a previously submitted bundle.
     ///
     /// Polls the Jito block engine to determine if the bundle has landed,
-    /// is still pending, or has expired.
+    /// is still pending, or has expired. Use
+    /// [`bundle_result`](Self::bundle_result) for the slot, confirmation
+    /// level and transaction signatures.
     ///
     /// TODO: implement WebSocket subscription for real-time bundle status updates
     pub async fn check_status(&self, bundle_id: &str) -> Result<BundleStatus> {
-        let client = reqwest::Client::new();
+        Ok(self.bundle_result(bundle_id).await?.status)
+    }
+
+    /// Fetch the full `getBundleStatuses` entry for a submitted bundle.
+    ///
+    /// A bundle the engine doesn't report is pending during the
+    /// [initial grace](Self::with_initial_grace) window and expired after.
+    /// A [superseded](Self::is_superseded) bundle that hasn't landed is
+    /// reported expired.
+    pub async fn bundle_result(&self, bundle_id: &str) -> Result<BundleResult> {
         let status_endpoint = format!("{}/api/v1/bundles", self.block_engine_url);
 
         let payload = serde_json::json!({
@@ -251,54 +1222,35 @@ impl JitoBundleBuilder {
             "params": [[bundle_id]]
         });
 
-        let response: serde_json::Value = client
+        self.throttle().await;
+        let response: serde_json::Value = self
+            .http_client
             .post(&status_endpoint)
             .json(&payload)
-            .timeout(Duration::from_secs(10))
+            .timeout(self.request_timeout)
             .send()
             .await?
             .json()
             .await?;
 
-        // Parse the status response
-        if let Some(result) = response.get("result") {
-            if let Some(statuses) = result.get("value").and_then(|v| v.as_array()) {
-                if let Some(status) = statuses.first() {
-                    let confirmation = status
-                        .get("confirmation_status")
-                        .and_then(|s| s.as_str())
-                        .unwrap_or("unknown");
-
-                    return match confirmation {
-                        "confirmed" | "finalized" => {
-                            let slot = status
-                                .get("slot")
-                                .and_then(|s| s.as_u64())
-                                .unwrap_or(0);
-                            Ok(BundleStatus::Landed {
-                                bundle_id: bundle_id.to_string(),
-                                slot,
-                            })
-                        }
-                        _ => Ok(BundleStatus::Accepted {
-                            bundle_id: bundle_id.to_string(),
-                        }),
-                    };
-                }
-            }
+        let mut result =
+            parse_bundle_status(bundle_id, &response, self.in_initial_grace(bundle_id));
+        if matches!(result.status, BundleStatus::Accepted { .. }) && self.is_superseded(bundle_id) {
+            result.status = BundleStatus::Expired {
+                bundle_id: bundle_id.to_string(),
+            };
         }
-
-        // TODO: differentiate between "not found" (expired) and "pending"
-        Ok(BundleStatus::Expired {
-            bundle_id: bundle_id.to_string(),
-        })
-    }
+        Ok(result)
+    }
 
     /// Submit the bundle and wait for it to land on-chain.
     ///
-    /// Polls bundle status with a timeout. Returns the final status.
+    /// Polls bundle status every [poll interval](Self::with_poll_interval)
+    /// until it lands, expires or `timeout` passes. Returns the final status.
     ///
-    /// TODO: add configurable timeout and polling interval
+    /// With an RPC client attached via [`with_rpc`](Self::with_rpc), the
+    /// [estimated expiry](Self::estimated_expiry) is logged once the bundle
+    /// is accepted, with a warning if `timeout` outlasts the blockhash.
     pub async fn submit_and_confirm(
         &self,
         timeout: Duration,
@@ -310,59 +1262,210 @@ impl JitoBundleBuilder {
             _ => return Ok(result),
         };
 
-        let start = std::time::Instant::now();
-        let poll_interval = Duration::from_millis(500);
-
-        while start.elapsed() < timeout {
-            tokio::time::sleep(poll_interval).await;
-
-            match self.check_status(&bundle_id).await {
-                Ok(BundleStatus::Landed { slot, .. }) => {
-                    info!("Bundle {bundle_id} landed in slot {slot}");
-                    return Ok(BundleSubmissionResult {
-                        status: BundleStatus::Landed {
-                            bundle_id,
-                            slot,
-                        },
-                        attempts: result.attempts,
-                        elapsed_ms: start.elapsed().as_millis(),
-                    });
-                }
-                Ok(BundleStatus::Expired { .. }) => {
-                    warn!("Bundle {bundle_id} expired");
-                    return Ok(BundleSubmissionResult {
-                        status: BundleStatus::Expired { bundle_id },
-                        attempts: result.attempts,
-                        elapsed_ms: start.elapsed().as_millis(),
-                    });
+        if let Some(client) = &self.rpc {
+            match self.estimated_expiry(client).await {
+                Ok(expiry) => {
+                    info!(
+                        "Bundle {bundle_id} blockhash expires in {}{} slots (~{}s)",
+                        if expiry.exact { "" } else { "at most " },
+                        expiry.remaining_slots,
+                        expiry.seconds_remaining
+                    );
+                    if expiry.remaining_slots > 0 && timeout.as_secs() > expiry.seconds_remaining {
+                        warn!(
+                            "Confirmation timeout of {}s outlasts the bundle's blockhash",
+                            timeout.as_secs()
+                        );
+                    }
                 }
+                Err(e) => debug!("Cannot estimate bundle expiry: {e}"),
+            }
+        }
+
+        let start = std::time::Instant::now();
+
+        // `Some(Some(slot))` once landed, `Some(None)` once expired
+        let id = &bundle_id;
+        let outcome = poll_until(self.poll_interval, timeout, move || async move {
+            match self.check_status(id).await {
+                Ok(BundleStatus::Landed { slot, .. }) => Ok(Some(Some(slot))),
+                Ok(BundleStatus::Expired { .. }) => Ok(Some(None)),
                 Ok(_) => {
-                    debug!("Bundle {bundle_id} still pending...");
+                    debug!("Bundle {id} still pending...");
+                    Ok(None)
                 }
                 Err(e) => {
                     warn!("Error checking bundle status: {e}");
+                    Ok(None)
                 }
             }
-        }
+        })
+        .await?;
 
-        warn!("Bundle confirmation timed out after {}ms", timeout.as_millis());
+        let elapsed_ms = start.elapsed().as_millis();
+        let status = match outcome {
+            Some(Some(slot)) => {
+                info!("Bundle {bundle_id} landed in slot {slot}");
+                self.observer.on_landed(&bundle_id, slot, elapsed_ms);
+                BundleStatus::Landed { bundle_id, slot }
+            }
+            Some(None) => {
+                warn!("Bundle {bundle_id} expired");
+                self.observer.on_expired(&bundle_id, elapsed_ms);
+                BundleStatus::Expired { bundle_id }
+            }
+            None => {
+                warn!(
+                    "Bundle confirmation timed out after {}ms",
+                    timeout.as_millis()
+                );
+                self.observer.on_expired(&bundle_id, elapsed_ms);
+                BundleStatus::Expired { bundle_id }
+            }
+        };
         Ok(BundleSubmissionResult {
-            status: BundleStatus::Expired { bundle_id },
+            status,
             attempts: result.attempts,
-            elapsed_ms: start.elapsed().as_millis(),
+            elapsed_ms,
+            tip_paid_lamports: result.tip_paid_lamports,
+            tip_clamped: result.tip_clamped,
+            bundle_hash: result.bundle_hash,
+            retry_limit_reached: result.retry_limit_reached,
+        })
+    }
+}
+
+/// Parse a `getBundleStatuses` response for `bundle_id`.
+///
+/// A bundle the block engine doesn't report is treated as pending
+/// ([`BundleStatus::Accepted`]) while `in_grace`, and as expired otherwise.
+fn parse_bundle_status(
+    bundle_id: &str,
+    response: &serde_json::Value,
+    in_grace: bool,
+) -> BundleResult {
+    let entry = response
+        .get("result")
+        .and_then(|r| r.get("value"))
+        .and_then(|v| v.as_array())
+        .and_then(|statuses| statuses.first());
+
+    let Some(entry) = entry else {
+        let bundle_id = bundle_id.to_string();
+        return BundleResult {
+            bundle_id: bundle_id.clone(),
+            status: if in_grace {
+                BundleStatus::Accepted { bundle_id }
+            } else {
+                BundleStatus::Expired { bundle_id }
+            },
+            slot: None,
+            confirmation_status: None,
+            transactions: Vec::new(),
+        };
+    };
+
+    let slot = entry.get("slot").and_then(|s| s.as_u64());
+    let confirmation_status = entry
+        .get("confirmation_status")
+        .and_then(|s| s.as_str())
+        .map(str::to_string);
+    let transactions = entry
+        .get("transactions")
+        .and_then(|t| t.as_array())
+        .map(|txs| {
+            txs.iter()
+                .filter_map(|tx| tx.as_str().map(str::to_string))
+                .collect()
         })
+        .unwrap_or_default();
+
+    let status = match confirmation_status.as_deref() {
+        Some("confirmed" | "finalized") => BundleStatus::Landed {
+            bundle_id: bundle_id.to_string(),
+            slot: slot.unwrap_or(0),
+        },
+        _ => BundleStatus::Accepted {
+            bundle_id: bundle_id.to_string(),
+        },
+    };
+
+    BundleResult {
+        bundle_id: bundle_id.to_string(),
+        status,
+        slot,
+        confirmation_status,
+        transactions,
     }
 }
 
-/// Create a tip transfer instruction to a random Jito tip account.
+/// How block engine JSON-RPC error codes are classified, as
+/// `(code, retryable)`.
+///
+/// Checked before [`BUNDLE_ERROR_CLASSES`]. Jito answers `-32097` when it is
+/// rate limiting the caller; the standard JSON-RPC request errors mean the
+/// same payload will be refused again, while an internal error may not
+/// recur.
+const BUNDLE_ERROR_CODES: &[(i64, bool)] = &[
+    (-32097, true),
+    (-32603, true),
+    (-32700, false),
+    (-32600, false),
+    (-32601, false),
+    (-32602, false),
+];
+
+/// How block engine error messages are classified, as `(substring, retryable)`.
+///
+/// Used when the error code is missing or not in [`BUNDLE_ERROR_CODES`].
+/// Matched case-insensitively in order; the first hit wins. Messages that
+/// match nothing are retried. Transient conditions (rate limits, timeouts)
+/// may clear on the next attempt, while a bundle that is already landed,
+/// expired, badly signed or conflicting will fail the same way every time.
+const BUNDLE_ERROR_CLASSES: &[(&str, bool)] = &[
+    ("rate limit", true),
+    ("too many requests", true),
+    ("timed out", true),
+    ("timeout", true),
+    ("already processed", false),
+    ("blockhash not found", false),
+    ("invalid transaction", false),
+    ("signature verification", false),
+    ("account in use", false),
+    ("accountinuse", false),
+];
+
+/// Whether a bundle rejected with error `code` is worth resubmitting, or
+/// `None` if the code is unknown. See [`BUNDLE_ERROR_CODES`].
+fn is_retryable_code(code: Option<i64>) -> Option<bool> {
+    let code = code?;
+    BUNDLE_ERROR_CODES
+        .iter()
+        .find(|&&(known, _)| known == code)
+        .map(|&(_, retryable)| retryable)
+}
+
+/// Whether a bundle rejected with `reason` is worth resubmitting.
+/// See [`BUNDLE_ERROR_CLASSES`].
+fn is_retryable(reason: &str) -> bool {
+    let reason = reason.to_lowercase();
+    BUNDLE_ERROR_CLASSES
+        .iter()
+        .find(|(pattern, _)| reason.contains(pattern))
+        .is_none_or(|&(_, retryable)| retryable)
+}
+
+/// Create a tip transfer instruction to an account chosen by `provider`,
+/// or to a random Jito tip account when it is `None`.
 ///
 /// This should be added as the last instruction in the last transaction
 /// of the bundle.
 pub fn create_tip_instruction(
     payer: &Pubkey,
     tip_lamports: u64,
+    provider: Option<&dyn TipAccountProvider>,
 ) -> Result<solana_sdk::instruction::Instruction> {
-    let tip_account = JitoBundleBuilder::random_tip_account()?;
+    let tip_account = provider.unwrap_or(&JitoTipAccounts).select();
 
     Ok(solana_sdk::system_instruction::transfer(
         payer,
@@ -371,20 +1474,1401 @@ pub fn create_tip_instruction(
     ))
 }
 
+/// A signed transaction that only pays a Jito tip, from
+/// [`build_tip_transaction`].
+#[derive(Debug, Clone)]
+pub struct TipTransaction {
+    pub transaction: Transaction,
+    /// The randomly selected tip account being paid
+    pub tip_account: Pubkey,
+}
+
+/// Build and sign a transaction containing just a tip transfer from `payer`
+/// to a random Jito tip account.
+///
+/// Use it as the last transaction of a bundle whose payload transactions
+/// are signed by others and don't pay a tip themselves.
+///
+/// # Example
+/// ```no_run
+/// use solana_client::rpc_client::RpcClient;
+/// use solana_sdk::signature::Keypair;
+/// use solana_tx_optimizer::bundle::{build_tip_transaction, JitoBundleBuilder};
+/// use solana_tx_optimizer::config::Config;
+///
+/// let config = Config::from_env();
+/// let client = RpcClient::new(config.rpc_url.clone());
+/// let payer = Keypair::new();
+///
+/// let tip = build_tip_transaction(&payer, client.get_latest_blockhash()?, 50_000)?;
+/// let mut builder = JitoBundleBuilder::new(&config)?;
+/// // builder.add_transaction(&payload_tx)?;
+/// builder.add_transaction(&tip.transaction)?;
+/// println!("Tipping {}", tip.tip_account);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn build_tip_transaction(
+    payer: &Keypair,
+    blockhash: Hash,
+    tip_lamports: u64,
+) -> Result<TipTransaction> {
+    let tip_ix = create_tip_instruction(&payer.pubkey(), tip_lamports, None)?;
+    let tip_account = tip_ix.accounts[1].pubkey;
+
+    let mut transaction = Transaction::new_with_payer(&[tip_ix], Some(&payer.pubkey()));
+    transaction.try_sign(&[payer], blockhash)?;
+    debug!("Built tip transaction paying {} to {tip_account}", Lamports(tip_lamports));
+
+    Ok(TipTransaction {
+        transaction,
+        tip_account,
+    })
+}
+
+/// The `sendBundle` request for serialized `transactions`, whose base58
+/// encodings may each be up to [`MAX_ENCODED_TX_LEN`] long and add up to
+/// `max_bytes`; see [`JitoBundleBuilder::build`].
+fn build_payload(transactions: &[Vec<u8>], max_bytes: usize) -> Result<serde_json::Value> {
+    if transactions.is_empty() {
+        return Err(TxOptimizerError::EmptyBundle);
+    }
+
+    let encoded_txs: Vec<String> = transactions
+        .iter()
+        .map(|tx_bytes| bs58::encode(tx_bytes).into_string())
+        .collect();
+    if let Some((index, tx)) = encoded_txs
+        .iter()
+        .enumerate()
+        .find(|(_, tx)| tx.len() > MAX_ENCODED_TX_LEN)
+    {
+        return Err(TxOptimizerError::TransactionTooLarge {
+            index,
+            bytes: tx.len(),
+            max: MAX_ENCODED_TX_LEN,
+        });
+    }
+    let bytes: usize = encoded_txs.iter().map(String::len).sum();
+    if bytes > max_bytes {
+        return Err(TxOptimizerError::BundleTooManyBytes {
+            bytes,
+            max: max_bytes,
+        });
+    }
+
+    let payload = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "sendBundle",
+        "params": [encoded_txs]
+    });
+
+    debug!("Built bundle with {} transactions", encoded_txs.len());
+    Ok(payload)
+}
+
+/// Jito's bundle id for serialized `transactions`; see
+/// [`JitoBundleBuilder::bundle_hash`].
+fn bundle_hash(transactions: &[Vec<u8>]) -> Result<String> {
+    let signatures = transactions
+        .iter()
+        .map(|tx_bytes| {
+            let tx: Transaction = bincode::deserialize(tx_bytes)?;
+            Ok(tx.signatures.first().copied().unwrap_or_default().to_string())
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let hash = solana_sdk::hash::hash(signatures.join(",").as_bytes());
+    Ok(hash.to_bytes().iter().map(|b| format!("{b:02x}")).collect())
+}
+
+/// Lamports serialized `transactions` pay to Jito tip accounts in total.
+fn total_tip_lamports(transactions: &[Vec<u8>]) -> Result<u64> {
+    transactions.iter().try_fold(0u64, |total, tx_bytes| {
+        let tx: Transaction = bincode::deserialize(tx_bytes)?;
+        Ok(total.saturating_add(tip_lamports_paid(&tx)))
+    })
+}
+
+/// Whether the block engine rejected a bundle because a transaction's
+/// blockhash expired.
+fn is_blockhash_not_found(status: &BundleStatus) -> bool {
+    matches!(status, BundleStatus::Rejected { reason, .. }
+        if reason.to_lowercase().contains("blockhash not found"))
+}
+
+/// Why the block engine would reject `tx`'s signatures, or `None` if they
+/// are all present and valid.
+fn signature_problem(tx: &Transaction) -> Option<String> {
+    let required = tx.message.header.num_required_signatures as usize;
+    if tx.signatures.len() != required {
+        return Some(format!(
+            "expected {required} signature(s), found {}",
+            tx.signatures.len()
+        ));
+    }
+    let unsigned = Signature::default();
+    if let Some(i) = tx.signatures.iter().position(|s| *s == unsigned) {
+        return Some(match tx.message.account_keys.get(i) {
+            Some(signer) => format!("missing signature for {signer}"),
+            None => format!("missing signature {i}"),
+        });
+    }
+    tx.verify().err().map(|e| e.to_string())
+}
+
+/// Find the Jito tip account paid by a System Program instruction in `tx`.
+///
+/// Returns `None` when the transaction does not touch any known tip account,
+/// which means a bundle ending in it would carry no tip.
+pub fn find_tip_account(tx: &Transaction) -> Option<Pubkey> {
+    let keys = &tx.message.account_keys;
+    tx.message
+        .instructions
+        .iter()
+        .filter(|ix| {
+            keys.get(ix.program_id_index as usize) == Some(&solana_sdk::system_program::id())
+        })
+        .flat_map(|ix| ix.accounts.iter())
+        .filter_map(|&index| keys.get(index as usize))
+        .find(|key| JITO_TIP_ACCOUNTS.contains(&key.to_string().as_str()))
+        .copied()
+}
+
+/// Rewrite the amount of the first System Program transfer to a Jito tip
+/// account in `tx`, returning whether there was one. Leaves the signatures
+/// stale.
+fn set_tip_lamports(tx: &mut Transaction, lamports: u64) -> Result<bool> {
+    let keys = &tx.message.account_keys;
+    let is_tip_account = |index: Option<&u8>| {
+        index
+            .and_then(|&i| keys.get(i as usize))
+            .is_some_and(|key| JITO_TIP_ACCOUNTS.contains(&key.to_string().as_str()))
+    };
+
+    for ix in &mut tx.message.instructions {
+        if keys.get(ix.program_id_index as usize) != Some(&solana_sdk::system_program::id()) {
+            continue;
+        }
+        let replaced = match bincode::deserialize(&ix.data) {
+            Ok(SystemInstruction::Transfer { .. }) if is_tip_account(ix.accounts.get(1)) => {
+                SystemInstruction::Transfer { lamports }
+            }
+            Ok(SystemInstruction::TransferWithSeed {
+                from_seed,
+                from_owner,
+                ..
+            }) if is_tip_account(ix.accounts.get(2)) => SystemInstruction::TransferWithSeed {
+                lamports,
+                from_seed,
+                from_owner,
+            },
+            _ => continue,
+        };
+        ix.data = bincode::serialize(&replaced)?;
+        return Ok(true);
+    }
+    Ok(false)
+}
+
+/// Sum the lamports `tx` transfers to Jito tip accounts through System
+/// Program `Transfer` and `TransferWithSeed` instructions.
+pub fn tip_lamports_paid(tx: &Transaction) -> u64 {
+    let keys = &tx.message.account_keys;
+    let is_tip_account = |index: Option<&u8>| {
+        index
+            .and_then(|&i| keys.get(i as usize))
+            .is_some_and(|key| JITO_TIP_ACCOUNTS.contains(&key.to_string().as_str()))
+    };
+
+    tx.message
+        .instructions
+        .iter()
+        .filter(|ix| {
+            keys.get(ix.program_id_index as usize) == Some(&solana_sdk::system_program::id())
+        })
+        .filter_map(|ix| match bincode::deserialize(&ix.data).ok()? {
+            SystemInstruction::Transfer { lamports } if is_tip_account(ix.accounts.get(1)) => {
+                Some(lamports)
+            }
+            SystemInstruction::TransferWithSeed { lamports, .. }
+                if is_tip_account(ix.accounts.get(2)) =>
+            {
+                Some(lamports)
+            }
+            _ => None,
+        })
+        .fold(0u64, u64::saturating_add)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_random_tip_account_parses() {
-        let pubkey = JitoBundleBuilder::random_tip_account();
-        assert!(pubkey.is_ok(), "Jito tip account should parse as valid Pubkey");
+        let pubkey = JitoTipAccounts.select().to_string();
+        assert!(
+            JITO_TIP_ACCOUNTS.contains(&pubkey.as_str()),
+            "Jito tip account should parse as valid Pubkey"
+        );
+    }
+
+    #[test]
+    fn test_custom_tip_provider() {
+        struct FixedTipAccount(Pubkey);
+
+        impl TipAccountProvider for FixedTipAccount {
+            fn select(&self) -> Pubkey {
+                self.0
+            }
+        }
+
+        let payer = Pubkey::new_unique();
+        let tip_account = Pubkey::new_unique();
+        let provider = Arc::new(FixedTipAccount(tip_account));
+
+        let tip_ix = create_tip_instruction(&payer, 10_000, Some(provider.as_ref())).unwrap();
+        assert_eq!(tip_ix.accounts[1].pubkey, tip_account);
+
+        let mut builder = JitoBundleBuilder::new(&Config::default())
+            .unwrap()
+            .with_tip_provider(provider);
+        builder.set_tip(25_000);
+        let tip_ix = builder.tip_instruction(&payer);
+        assert_eq!(tip_ix.accounts[0].pubkey, payer);
+        assert_eq!(tip_ix.accounts[1].pubkey, tip_account);
+        assert_eq!(
+            bincode::deserialize::<SystemInstruction>(&tip_ix.data).unwrap(),
+            SystemInstruction::Transfer { lamports: 25_000 }
+        );
     }
 
     #[test]
     fn test_empty_bundle_fails() {
         let config = Config::default();
-        let builder = JitoBundleBuilder::new(&config);
+        let builder = JitoBundleBuilder::new(&config).unwrap();
         assert!(builder.build().is_err(), "Empty bundle should fail to build");
+        assert!(matches!(builder.build(), Err(TxOptimizerError::EmptyBundle)));
+    }
+
+    #[test]
+    fn test_new_rejects_unusable_http_config() {
+        let config = Config {
+            http_proxy: Some("not a proxy url".to_string()),
+            ..Config::default()
+        };
+        assert!(JitoBundleBuilder::new(&config).is_err());
+
+        let config = Config {
+            ca_cert_path: Some("/nonexistent/ca.pem".into()),
+            ..Config::default()
+        };
+        assert!(JitoBundleBuilder::new(&config).is_err());
+    }
+
+    #[test]
+    fn test_bundle_too_large() {
+        let config = Config::default();
+        let mut builder = JitoBundleBuilder::new(&config).unwrap();
+        let tx = Transaction::default();
+        for _ in 0..MAX_BUNDLE_SIZE {
+            builder.add_transaction(&tx).unwrap();
+        }
+        assert!(matches!(
+            builder.add_transaction(&tx),
+            Err(TxOptimizerError::BundleTooLarge { max: MAX_BUNDLE_SIZE })
+        ));
+    }
+
+    #[test]
+    fn test_bundle_byte_limit() {
+        let payer = Keypair::new();
+        let blockhash = Hash::new_unique();
+        let sign = |ix: solana_sdk::instruction::Instruction| {
+            Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], blockhash)
+        };
+        let small = sign(solana_sdk::system_instruction::transfer(
+            &payer.pubkey(),
+            &Pubkey::new_unique(),
+            1,
+        ));
+
+        let mut builder = JitoBundleBuilder::new(&Config::default()).unwrap();
+        builder.add_transaction(&small).unwrap();
+        let encoded = bs58::encode(bincode::serialize(&small).unwrap()).into_string();
+        assert_eq!(builder.total_serialized_bytes(), encoded.len());
+        builder.build().unwrap();
+
+        // Far past the packet size, but signed, so only the size checks trip
+        let large = sign(solana_sdk::instruction::Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[7; 4_000],
+            Vec::new(),
+        ));
+        builder.add_transaction(&large).unwrap();
+        assert!(builder.total_serialized_bytes() < builder.max_bundle_bytes());
+        match builder.build() {
+            Err(TxOptimizerError::TransactionTooLarge { index, bytes, max }) => {
+                assert_eq!(index, 1);
+                assert_eq!(bytes, builder.total_serialized_bytes() - encoded.len());
+                assert_eq!(max, MAX_ENCODED_TX_LEN);
+            }
+            other => panic!("expected the transaction limit to trip, got {other:?}"),
+        }
+
+        // Packet-sized transactions can still add up past a bundle limit
+        let medium = |byte| {
+            let tx = sign(solana_sdk::instruction::Instruction::new_with_bytes(
+                Pubkey::new_unique(),
+                &[byte; 1_000],
+                Vec::new(),
+            ));
+            bincode::serialize(&tx).unwrap()
+        };
+        let transactions = [medium(1), medium(2)];
+        build_payload(&transactions, MAX_BUNDLE_BYTES).unwrap();
+        assert!(matches!(
+            build_payload(&transactions, MAX_ENCODED_TX_LEN),
+            Err(TxOptimizerError::BundleTooManyBytes {
+                max: MAX_ENCODED_TX_LEN,
+                ..
+            })
+        ));
+        assert_eq!(
+            JitoBundleBuilder::new(&Config::default())
+                .unwrap()
+                .max_bundle_bytes(),
+            MAX_BUNDLE_BYTES
+        );
+
+        let packet = vec![u8::MAX; solana_sdk::packet::PACKET_DATA_SIZE];
+        assert_eq!(bs58::encode(packet).into_string().len(), MAX_ENCODED_TX_LEN);
+    }
+
+    #[tokio::test]
+    async fn test_tip_bounds() {
+        let permissive = JitoBundleBuilder::new(&Config::default()).unwrap();
+        assert_eq!(permissive.tip_lamports(), 10_000);
+        assert!(!permissive.tip_clamped);
+
+        let config = Config {
+            jito_tip_lamports: 0,
+            min_tip_lamports: 1_000,
+            max_tip_lamports: 100_000,
+            ..Config::default()
+        };
+        let mut builder = JitoBundleBuilder::new(&config).unwrap();
+        assert_eq!(builder.tip_lamports(), 1_000);
+        assert!(builder.tip_clamped);
+
+        builder.set_tip(50_000);
+        assert_eq!(builder.tip_lamports(), 50_000);
+        assert!(!builder.tip_clamped);
+        builder.set_tip(1_000_000);
+        assert_eq!(builder.tip_lamports(), 100_000);
+
+        assert!(matches!(
+            builder.try_set_tip(1_000_000),
+            Err(TxOptimizerError::Config(_))
+        ));
+        assert!(builder.try_set_tip(100_000).is_ok());
+        assert!(JitoBundleBuilder::new(&config)
+            .unwrap()
+            .with_tip_bounds(5, 1)
+            .is_err());
+
+        // Narrowing the bounds re-clamps the current tip
+        let builder = JitoBundleBuilder::new(&config)
+            .unwrap()
+            .with_tip_bounds(0, 500)
+            .unwrap();
+        assert_eq!(builder.tip_lamports(), 500);
+        assert!(builder.tip_clamped);
+
+        let server = wiremock::MockServer::start().await;
+        bundle_endpoint()
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": "abc123",
+            })))
+            .mount(&server)
+            .await;
+        let mut builder = mock_builder(&server).with_tip_bounds(1_000, 2_000).unwrap();
+        builder.set_tip(5_000);
+        assert!(builder.submit().await.unwrap().tip_clamped);
+    }
+
+    #[test]
+    fn test_max_bundle_size_override() {
+        let config = Config::default();
+        let tx = Transaction::default();
+
+        let mut builder = JitoBundleBuilder::new(&config)
+            .unwrap()
+            .with_max_bundle_size(2)
+            .unwrap();
+        assert_eq!(builder.max_bundle_size(), 2);
+        builder.add_transaction(&tx).unwrap();
+        builder.add_transaction(&tx).unwrap();
+        assert!(matches!(
+            builder.add_transaction(&tx),
+            Err(TxOptimizerError::BundleTooLarge { max: 2 })
+        ));
+
+        let mut builder = JitoBundleBuilder::new(&config)
+            .unwrap()
+            .with_max_bundle_size(BUNDLE_SIZE_HARD_CAP)
+            .unwrap();
+        for _ in 0..BUNDLE_SIZE_HARD_CAP {
+            builder.add_transaction(&tx).unwrap();
+        }
+        assert!(builder.add_transaction(&tx).is_err());
+        assert!(matches!(
+            builder.with_max_bundle_size(3),
+            Err(TxOptimizerError::Config(_))
+        ));
+
+        for size in [0, BUNDLE_SIZE_HARD_CAP + 1] {
+            assert!(matches!(
+                JitoBundleBuilder::new(&config)
+                    .unwrap()
+                    .with_max_bundle_size(size),
+                Err(TxOptimizerError::Config(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_rejected_result_into_error() {
+        let result = BundleSubmissionResult {
+            status: BundleStatus::Rejected {
+                reason: "blockhash not found".to_string(),
+                code: Some(-32602),
+            },
+            attempts: 1,
+            elapsed_ms: 0,
+            tip_paid_lamports: 0,
+            tip_clamped: false,
+            bundle_hash: String::new(),
+            retry_limit_reached: None,
+        };
+        assert!(matches!(
+            result.into_result(),
+            Err(TxOptimizerError::BundleRejected { reason, code: Some(-32602) })
+                if reason == "blockhash not found"
+        ));
+    }
+
+    #[cfg(feature = "serialize_v2")]
+    #[test]
+    fn test_status_v2_wire_format() {
+        let landed = BundleStatus::Landed {
+            bundle_id: "abc".to_string(),
+            slot: 42,
+        };
+        assert_eq!(
+            serde_json::to_value(&landed).unwrap(),
+            serde_json::json!({ "status": "landed", "bundle_id": "abc", "slot": 42 })
+        );
+
+        let raw = serde_json::json!({ "status": "rejected", "reason": "tip too low" });
+        let status: BundleStatus = serde_json::from_value(raw).unwrap();
+        assert!(matches!(
+            status,
+            BundleStatus::Rejected { reason, code: None } if reason == "tip too low"
+        ));
+    }
+
+    #[test]
+    fn test_find_tip_account() {
+        let payer = Pubkey::new_unique();
+        let tip_ix = create_tip_instruction(&payer, 10_000, None).unwrap();
+        let tip_account = tip_ix.accounts[1].pubkey;
+
+        let tx = Transaction::new_with_payer(&[tip_ix], Some(&payer));
+        assert_eq!(find_tip_account(&tx), Some(tip_account));
+
+        let transfer = solana_sdk::system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+        let tx = Transaction::new_with_payer(&[transfer], Some(&payer));
+        assert_eq!(find_tip_account(&tx), None);
+    }
+
+    #[test]
+    fn test_retryable_bundle_errors() {
+        assert!(is_retryable("Rate limit exceeded"));
+        assert!(is_retryable("429 Too Many Requests"));
+        assert!(is_retryable("request timed out"));
+        assert!(is_retryable("simulation timeout"));
+        assert!(is_retryable("internal error"));
+    }
+
+    #[test]
+    fn test_non_retryable_bundle_errors() {
+        assert!(!is_retryable("bundle already processed"));
+        assert!(!is_retryable("Blockhash not found"));
+        assert!(!is_retryable("bundle contains an invalid transaction"));
+        assert!(!is_retryable("transaction signature verification failure"));
+        assert!(!is_retryable("Account in use"));
+        assert!(!is_retryable("transaction failed: AccountInUse"));
+    }
+
+    #[test]
+    fn test_bundle_error_codes() {
+        assert_eq!(is_retryable_code(Some(-32097)), Some(true));
+        assert_eq!(is_retryable_code(Some(-32603)), Some(true));
+        assert_eq!(is_retryable_code(Some(-32602)), Some(false));
+        assert_eq!(is_retryable_code(Some(-32601)), Some(false));
+        assert_eq!(is_retryable_code(Some(-1)), None);
+        assert_eq!(is_retryable_code(None), None);
+    }
+
+    #[tokio::test]
+    async fn test_error_code_takes_precedence_over_message() {
+        let server = wiremock::MockServer::start().await;
+        // A retryable-sounding message with a non-retryable code
+        let rejected = bundle_endpoint()
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "code": -32602, "message": "request timed out" },
+            })))
+            .expect(1)
+            .mount_as_scoped(&server)
+            .await;
+        let result = mock_builder(&server).submit().await.unwrap();
+        assert!(matches!(
+            result.status,
+            BundleStatus::Rejected {
+                code: Some(-32602),
+                ..
+            }
+        ));
+        let json = serde_json::to_value(&result).unwrap();
+        #[cfg(not(feature = "serialize_v2"))]
+        assert_eq!(json["status"]["Rejected"]["code"], -32602);
+        #[cfg(feature = "serialize_v2")]
+        assert_eq!(json["status"]["code"], -32602);
+        drop(rejected);
+
+        // Jito's rate-limit code is retried whatever the message says
+        bundle_endpoint()
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "code": -32097, "message": "bundle already processed" },
+            })))
+            .expect(3)
+            .mount(&server)
+            .await;
+        let result = mock_builder(&server).submit().await.unwrap();
+        assert_eq!(result.attempts, 3);
+        assert_eq!(result.retry_limit_reached, Some(RetryLimit::Attempts));
+    }
+
+    /// A one-transaction builder pointed at `server`.
+    fn mock_builder(server: &wiremock::MockServer) -> JitoBundleBuilder {
+        let config = Config {
+            jito_block_engine_url: server.uri(),
+            max_retries: 3,
+            ..Config::default()
+        };
+        let mut builder = JitoBundleBuilder::new(&config).unwrap();
+        builder.add_transaction(&Transaction::default()).unwrap();
+        builder
+    }
+
+    fn bundle_endpoint() -> wiremock::MockBuilder {
+        use wiremock::matchers::{method, path};
+        wiremock::Mock::given(method("POST")).and(path("/api/v1/bundles"))
+    }
+
+    #[tokio::test]
+    async fn test_confirmation_timing_is_configurable() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let server = wiremock::MockServer::start().await;
+        let polls = Arc::new(AtomicUsize::new(0));
+        let counter = polls.clone();
+        bundle_endpoint()
+            .respond_with(move |request: &wiremock::Request| {
+                let body: serde_json::Value = request.body_json().unwrap();
+                let result = match body["method"].as_str() {
+                    Some("sendBundle") => serde_json::json!("pending"),
+                    _ => {
+                        counter.fetch_add(1, Ordering::SeqCst);
+                        serde_json::json!({ "context": { "slot": 1 }, "value": [] })
+                    }
+                };
+                wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": result,
+                }))
+            })
+            .mount(&server)
+            .await;
+
+        assert!(mock_builder(&server)
+            .with_poll_interval(Duration::ZERO)
+            .is_err());
+        let builder = mock_builder(&server)
+            .with_initial_grace(Duration::from_secs(60))
+            .with_poll_interval(Duration::from_millis(10))
+            .unwrap();
+        let result = builder
+            .submit_and_confirm(Duration::from_millis(200))
+            .await
+            .unwrap();
+        assert!(matches!(result.status, BundleStatus::Expired { .. }));
+        // The default 500ms interval would have polled at most once
+        assert!(polls.load(Ordering::SeqCst) > 2);
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        let server = wiremock::MockServer::start().await;
+        bundle_endpoint()
+            .respond_with(
+                wiremock::ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": {} }))
+                    .set_delay(Duration::from_millis(500)),
+            )
+            .mount(&server)
+            .await;
+
+        let builder = mock_builder(&server).with_request_timeout(Duration::from_millis(50));
+        assert!(builder.check_status("slow").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_submit_accepted() {
+        let server = wiremock::MockServer::start().await;
+        bundle_endpoint()
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": "abc123",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = mock_builder(&server).submit().await.unwrap();
+        assert!(matches!(
+            result.status,
+            BundleStatus::Accepted { bundle_id } if bundle_id == "abc123"
+        ));
+        assert_eq!(result.attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_submit_blockhash_not_found_is_not_retried() {
+        let server = wiremock::MockServer::start().await;
+        bundle_endpoint()
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "code": -32602, "message": "blockhash not found" },
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = mock_builder(&server).submit().await.unwrap();
+        assert!(matches!(
+            result.status,
+            BundleStatus::Rejected { reason, code: Some(-32602) } if reason == "blockhash not found"
+        ));
+        assert_eq!(result.attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_submit_server_error_uses_every_retry() {
+        let server = wiremock::MockServer::start().await;
+        bundle_endpoint()
+            .respond_with(
+                wiremock::ResponseTemplate::new(500).set_body_string("Internal Server Error"),
+            )
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let result = mock_builder(&server).submit().await.unwrap();
+        assert!(matches!(
+            result.status,
+            BundleStatus::Rejected { reason, code: None } if reason == "Max retries exceeded"
+        ));
+        assert_eq!(result.attempts, 3);
+        assert_eq!(result.retry_limit_reached, Some(RetryLimit::Attempts));
+    }
+
+    #[tokio::test]
+    async fn test_submit_stops_at_retry_duration() {
+        let server = wiremock::MockServer::start().await;
+        bundle_endpoint()
+            .respond_with(
+                wiremock::ResponseTemplate::new(500).set_body_string("Internal Server Error"),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        // Backoffs are 100ms then 200ms, so the third attempt would start
+        // past the budget
+        let result = mock_builder(&server)
+            .with_max_retry_duration(Duration::from_millis(250))
+            .submit()
+            .await
+            .unwrap();
+        assert!(matches!(
+            result.status,
+            BundleStatus::Rejected { reason, code: None } if reason == "Retry duration exceeded"
+        ));
+        assert_eq!(result.attempts, 2);
+        assert_eq!(result.retry_limit_reached, Some(RetryLimit::Duration));
+        assert!(result.elapsed_ms < 250);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_spaces_submissions() {
+        assert!(RateLimiter::new(0.0).is_err());
+        assert!(RateLimiter::new(f64::NAN).is_err());
+        assert!(RateLimiter::new(1.0).unwrap().with_burst(0).is_err());
+
+        let server = wiremock::MockServer::start().await;
+        bundle_endpoint()
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": "abc123",
+            })))
+            .expect(4)
+            .mount(&server)
+            .await;
+
+        let limiter = Arc::new(RateLimiter::new(20.0).unwrap());
+        assert_eq!(limiter.requests_per_second(), 20.0);
+
+        let start = Instant::now();
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let builder = mock_builder(&server).with_rate_limiter(limiter.clone());
+                tokio::spawn(async move { builder.submit().await })
+            })
+            .collect();
+        for handle in handles {
+            assert!(matches!(
+                handle.await.unwrap().unwrap().status,
+                BundleStatus::Accepted { .. }
+            ));
+        }
+        // The first request goes out at once, the other three 50ms apart
+        assert!(start.elapsed() >= Duration::from_millis(145), "{:?}", start.elapsed());
+    }
+
+    #[tokio::test]
+    async fn test_submit_after_ambiguous_send_checks_status() {
+        use wiremock::matchers::{body_partial_json, header};
+
+        let server = wiremock::MockServer::start().await;
+        let builder = mock_builder(&server);
+        let bundle_hash = builder.bundle_hash().unwrap();
+        assert_eq!(bundle_hash.len(), 64);
+
+        // Known to the engine: report it without resending
+        let status = bundle_endpoint()
+            .and(body_partial_json(serde_json::json!({ "method": "getBundleStatuses" })))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": { "value": [{ "confirmation_status": "confirmed", "slot": 7 }] },
+            })))
+            .expect(1)
+            .mount_as_scoped(&server)
+            .await;
+        builder.mark_submitted(&bundle_hash);
+        let result = builder.submit().await.unwrap();
+        assert!(matches!(result.status, BundleStatus::Landed { slot: 7, .. }));
+        assert_eq!(result.bundle_hash, bundle_hash);
+        drop(status);
+
+        // Unknown to the engine: resend with the idempotency key
+        bundle_endpoint()
+            .and(body_partial_json(serde_json::json!({ "method": "getBundleStatuses" })))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": { "value": [] },
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        bundle_endpoint()
+            .and(body_partial_json(serde_json::json!({ "method": "sendBundle" })))
+            .and(header("Idempotency-Key", bundle_hash.as_str()))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": "abc123",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let result = builder.submit().await.unwrap();
+        assert!(matches!(
+            result.status,
+            BundleStatus::Accepted { bundle_id } if bundle_id == "abc123"
+        ));
+
+        // Adding a transaction changes the key
+        let mut builder = builder;
+        let payer = Keypair::new();
+        builder
+            .add_transaction(&Transaction::new_signed_with_payer(
+                &[],
+                Some(&payer.pubkey()),
+                &[&payer],
+                Hash::default(),
+            ))
+            .unwrap();
+        assert_ne!(builder.bundle_hash().unwrap(), bundle_hash);
+    }
+
+    #[tokio::test]
+    async fn test_stale_blockhash_blocks_submission() {
+        use wiremock::matchers::{method, path};
+
+        let server = wiremock::MockServer::start().await;
+        let fresh = Hash::new_unique();
+        // JSON-RPC node: only `fresh` is a valid blockhash
+        wiremock::Mock::given(method("POST"))
+            .and(path("/"))
+            .respond_with(move |request: &wiremock::Request| {
+                let body: serde_json::Value = request.body_json().unwrap();
+                let result = match body["method"].as_str() {
+                    Some("getVersion") => serde_json::json!({ "solana-core": "1.18.26" }),
+                    _ => serde_json::json!({
+                        "context": { "slot": 1 },
+                        "value": body["params"][0] == fresh.to_string(),
+                    }),
+                };
+                wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": body["id"],
+                    "result": result,
+                }))
+            })
+            .mount(&server)
+            .await;
+        bundle_endpoint()
+            .respond_with(wiremock::ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let payer = Keypair::new();
+        let tx = |blockhash| {
+            Transaction::new_signed_with_payer(&[], Some(&payer.pubkey()), &[&payer], blockhash)
+        };
+        let config = Config {
+            jito_block_engine_url: server.uri(),
+            ..Config::default()
+        };
+        let mut builder = JitoBundleBuilder::new(&config)
+            .unwrap()
+            .with_rpc(Arc::new(RpcClient::new(server.uri())));
+        builder
+            .add_transaction(&tx(fresh))
+            .unwrap()
+            .add_transaction(&tx(fresh))
+            .unwrap();
+        builder.validate_blockhashes().await.unwrap();
+
+        let stale = Hash::new_unique();
+        builder.add_transaction(&tx(stale)).unwrap();
+        assert!(matches!(
+            builder.submit().await,
+            Err(TxOptimizerError::StaleBlockhash { index: 2, blockhash }) if blockhash == stale
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_estimated_expiry() {
+        use wiremock::matchers::{method, path};
+
+        let server = wiremock::MockServer::start().await;
+        let (latest, older) = (Hash::new_unique(), Hash::new_unique());
+        // JSON-RPC node at block height 130, whose latest blockhash is valid
+        // through height 250
+        wiremock::Mock::given(method("POST"))
+            .and(path("/"))
+            .respond_with(move |request: &wiremock::Request| {
+                let body: serde_json::Value = request.body_json().unwrap();
+                let result = match body["method"].as_str() {
+                    Some("getLatestBlockhash") => serde_json::json!({
+                        "context": { "slot": 1 },
+                        "value": { "blockhash": latest.to_string(), "lastValidBlockHeight": 250 },
+                    }),
+                    Some("getVersion") => serde_json::json!({ "solana-core": "1.18.26" }),
+                    Some("getBlockHeight") => serde_json::json!(130),
+                    _ => serde_json::json!({
+                        "context": { "slot": 1 },
+                        "value": body["params"][0] == latest.to_string()
+                            || body["params"][0] == older.to_string(),
+                    }),
+                };
+                wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": body["id"],
+                    "result": result,
+                }))
+            })
+            .mount(&server)
+            .await;
+
+        let client = RpcClient::new(server.uri());
+        let payer = Keypair::new();
+        let expiry = |blockhash| {
+            let mut builder = JitoBundleBuilder::new(&Config::default()).unwrap();
+            builder
+                .add_transaction(&Transaction::new_signed_with_payer(
+                    &[],
+                    Some(&payer.pubkey()),
+                    &[&payer],
+                    blockhash,
+                ))
+                .unwrap();
+            let client = &client;
+            async move { builder.estimated_expiry(client).await.unwrap() }
+        };
+
+        let fresh = expiry(latest).await;
+        assert_eq!(fresh, SlotExpiry::new(latest, 120, true));
+        assert_eq!(fresh.seconds_remaining, 48);
+        assert_eq!(expiry(older).await, SlotExpiry::new(older, 120, false));
+        let stale = Hash::new_unique();
+        assert_eq!(expiry(stale).await, SlotExpiry::new(stale, 0, true));
+
+        assert!(matches!(
+            JitoBundleBuilder::new(&Config::default())
+                .unwrap()
+                .estimated_expiry(&client)
+                .await,
+            Err(TxOptimizerError::EmptyBundle)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resigner_refreshes_blockhash() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use wiremock::matchers::{method, path};
+
+        let server = wiremock::MockServer::start().await;
+        let fresh = Hash::new_unique();
+        // JSON-RPC node: only `fresh` is valid, and it is the latest
+        wiremock::Mock::given(method("POST"))
+            .and(path("/"))
+            .respond_with(move |request: &wiremock::Request| {
+                let body: serde_json::Value = request.body_json().unwrap();
+                let result = match body["method"].as_str() {
+                    Some("getVersion") => serde_json::json!({ "solana-core": "1.18.26" }),
+                    Some("getLatestBlockhash") => serde_json::json!({
+                        "context": { "slot": 1 },
+                        "value": { "blockhash": fresh.to_string(), "lastValidBlockHeight": 100 },
+                    }),
+                    _ => serde_json::json!({
+                        "context": { "slot": 1 },
+                        "value": body["params"][0] == fresh.to_string(),
+                    }),
+                };
+                wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": body["id"],
+                    "result": result,
+                }))
+            })
+            .mount(&server)
+            .await;
+        let accepted = bundle_endpoint()
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": "resigned",
+            })))
+            .expect(1)
+            .mount_as_scoped(&server)
+            .await;
+
+        let payer = Arc::new(Keypair::new());
+        let resigns = Arc::new(AtomicUsize::new(0));
+        let config = Config {
+            jito_block_engine_url: server.uri(),
+            ..Config::default()
+        };
+        let builder_at = |blockhash| {
+            let (signer, resigns) = (payer.clone(), resigns.clone());
+            let mut builder = JitoBundleBuilder::new(&config)
+                .unwrap()
+                .with_rpc(Arc::new(RpcClient::new(server.uri())))
+                .with_resigner(move |tx, blockhash| {
+                    resigns.fetch_add(1, Ordering::SeqCst);
+                    Ok(tx.try_sign(&[signer.as_ref()], blockhash)?)
+                });
+            builder
+                .add_transaction(&Transaction::new_signed_with_payer(
+                    &[],
+                    Some(&payer.pubkey()),
+                    &[payer.as_ref()],
+                    blockhash,
+                ))
+                .unwrap();
+            builder
+        };
+
+        // Stale before sending: re-signed up front
+        let builder = builder_at(Hash::new_unique());
+        let result = builder.submit().await.unwrap();
+        assert!(matches!(result.status, BundleStatus::Accepted { .. }));
+        assert_eq!(resigns.load(Ordering::SeqCst), 1);
+        assert_ne!(result.bundle_hash, builder.bundle_hash().unwrap());
+        drop(accepted);
+
+        // Valid locally but unknown to the engine: re-signed after the
+        // rejection and retried once
+        let builder = builder_at(fresh);
+        let _rejected = bundle_endpoint()
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "message": "Blockhash not found" },
+            })))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount_as_scoped(&server)
+            .await;
+        let _accepted = bundle_endpoint()
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": "retried",
+            })))
+            .expect(1)
+            .mount_as_scoped(&server)
+            .await;
+        let result = builder.submit().await.unwrap();
+        assert!(matches!(
+            result.status,
+            BundleStatus::Accepted { bundle_id } if bundle_id == "retried"
+        ));
+        assert_eq!(result.attempts, 2);
+        assert_eq!(resigns.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_check_status() {
+        use wiremock::matchers::body_partial_json;
+
+        let server = wiremock::MockServer::start().await;
+        bundle_endpoint()
+            .and(body_partial_json(serde_json::json!({ "params": [["landed"]] })))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": { "value": [{ "confirmation_status": "confirmed", "slot": 42 }] },
+            })))
+            .mount(&server)
+            .await;
+        bundle_endpoint()
+            .and(body_partial_json(serde_json::json!({ "params": [["unknown"]] })))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": { "value": [] },
+            })))
+            .mount(&server)
+            .await;
+
+        let builder = mock_builder(&server);
+        assert!(matches!(
+            builder.check_status("landed").await.unwrap(),
+            BundleStatus::Landed { slot: 42, .. }
+        ));
+        assert!(matches!(
+            builder.check_status("unknown").await.unwrap(),
+            BundleStatus::Expired { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_bundle_is_pending_during_grace() {
+        use wiremock::matchers::body_partial_json;
+
+        let server = wiremock::MockServer::start().await;
+        bundle_endpoint()
+            .and(body_partial_json(serde_json::json!({ "method": "sendBundle" })))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": "abc123",
+            })))
+            .mount(&server)
+            .await;
+        bundle_endpoint()
+            .and(body_partial_json(serde_json::json!({ "method": "getBundleStatuses" })))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": { "context": { "slot": 250_000_100u64 }, "value": [] },
+            })))
+            .mount(&server)
+            .await;
+
+        let builder = mock_builder(&server).with_initial_grace(Duration::from_millis(200));
+        builder.submit().await.unwrap();
+        assert!(matches!(
+            builder.check_status("abc123").await.unwrap(),
+            BundleStatus::Accepted { .. }
+        ));
+        // Never accepted by this builder
+        assert!(matches!(
+            builder.check_status("other").await.unwrap(),
+            BundleStatus::Expired { .. }
+        ));
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert!(matches!(
+            builder.check_status("abc123").await.unwrap(),
+            BundleStatus::Expired { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_bundle_result() {
+        let server = wiremock::MockServer::start().await;
+        bundle_endpoint()
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": {
+                    "context": { "slot": 250_000_100u64 },
+                    "value": [{
+                        "bundle_id": "abc123",
+                        "transactions": ["sig1", "sig2"],
+                        "slot": 250_000_000u64,
+                        "confirmation_status": "processed",
+                        "err": { "Ok": null },
+                    }],
+                },
+            })))
+            .mount(&server)
+            .await;
+
+        let result = mock_builder(&server).bundle_result("abc123").await.unwrap();
+        assert_eq!(result.bundle_id, "abc123");
+        assert!(matches!(result.status, BundleStatus::Accepted { .. }));
+        assert_eq!(result.slot, Some(250_000_000));
+        assert_eq!(result.confirmation_status.as_deref(), Some("processed"));
+        assert_eq!(result.transactions, vec!["sig1", "sig2"]);
+    }
+
+    #[test]
+    fn test_build_tip_transaction() {
+        let payer = Keypair::new();
+        let blockhash = Hash::new_unique();
+        let tip = build_tip_transaction(&payer, blockhash, 25_000).unwrap();
+
+        let tx = &tip.transaction;
+        assert_eq!(tx.message.instructions.len(), 1);
+        assert_eq!(tx.message.account_keys[0], payer.pubkey());
+        assert_eq!(tx.message.recent_blockhash, blockhash);
+        tx.verify().unwrap();
+        assert_eq!(find_tip_account(tx), Some(tip.tip_account));
+        assert_eq!(tip_lamports_paid(tx), 25_000);
+    }
+
+    #[test]
+    fn test_total_tip_lamports() {
+        let payer = Pubkey::new_unique();
+        let other = solana_sdk::system_instruction::transfer(&payer, &Pubkey::new_unique(), 7);
+        let first = Transaction::new_with_payer(
+            &[
+                other.clone(),
+                create_tip_instruction(&payer, 10_000, None).unwrap(),
+            ],
+            Some(&payer),
+        );
+        let last = Transaction::new_with_payer(
+            &[
+                create_tip_instruction(&payer, 20_000, None).unwrap(),
+                create_tip_instruction(&payer, 5_000, None).unwrap(),
+            ],
+            Some(&payer),
+        );
+        assert_eq!(tip_lamports_paid(&first), 10_000);
+        assert_eq!(tip_lamports_paid(&last), 25_000);
+
+        // Only the instructions matter here, so leave the transactions unsigned
+        let mut builder = JitoBundleBuilder::new(&Config::default())
+            .unwrap()
+            .with_signature_verification(false);
+        assert_eq!(builder.total_tip_lamports().unwrap(), 0);
+        builder.add_transaction(&first).unwrap();
+        builder.add_transaction(&last).unwrap();
+        builder
+            .add_transaction(&Transaction::new_with_payer(&[other], Some(&payer)))
+            .unwrap();
+        assert_eq!(builder.total_tip_lamports().unwrap(), 35_000);
+    }
+
+    #[test]
+    fn test_add_transaction_verifies_signatures() {
+        let payer = Keypair::new();
+        let transfer =
+            solana_sdk::system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        let unsigned = Transaction::new_with_payer(&[transfer], Some(&payer.pubkey()));
+        let mut signed = unsigned.clone();
+        signed.sign(&[&payer], Hash::new_unique());
+        let mut forged = signed.clone();
+        forged.signatures[0] = Keypair::new().sign_message(&forged.message_data());
+
+        let mut builder = JitoBundleBuilder::new(&Config::default()).unwrap();
+        builder.add_transaction(&signed).unwrap();
+        assert!(matches!(
+            builder.add_transaction(&unsigned),
+            Err(TxOptimizerError::InvalidSignature { index: 1, reason })
+                if reason == format!("missing signature for {}", payer.pubkey())
+        ));
+        assert!(matches!(
+            builder.add_transaction(&forged),
+            Err(TxOptimizerError::InvalidSignature { index: 1, .. })
+        ));
+        assert_eq!(builder.transactions.len(), 1);
+
+        let mut builder = JitoBundleBuilder::new(&Config::default())
+            .unwrap()
+            .with_signature_verification(false);
+        builder.add_transaction(&unsigned).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_resubmit_with_tip_supersedes_previous_bundle() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let server = wiremock::MockServer::start().await;
+        let sends = Arc::new(AtomicUsize::new(0));
+        let counter = sends.clone();
+        bundle_endpoint()
+            .respond_with(move |request: &wiremock::Request| {
+                let body: serde_json::Value = request.body_json().unwrap();
+                let result = match body["method"].as_str() {
+                    Some("sendBundle") => {
+                        let n = counter.fetch_add(1, Ordering::SeqCst);
+                        serde_json::json!(["old", "new"][n])
+                    }
+                    _ => serde_json::json!({ "context": { "slot": 1 }, "value": [] }),
+                };
+                wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": result,
+                }))
+            })
+            .mount(&server)
+            .await;
+
+        let payer = Arc::new(Keypair::new());
+        let tip_account = Pubkey::from_str(JITO_TIP_ACCOUNTS[0]).unwrap();
+        let blockhash = Hash::new_unique();
+        let config = Config {
+            jito_block_engine_url: server.uri(),
+            min_tip_lamports: 0,
+            ..Config::default()
+        };
+        let signer = payer.clone();
+        let mut builder = JitoBundleBuilder::new(&config)
+            .unwrap()
+            .with_resigner(move |tx, blockhash| Ok(tx.try_sign(&[signer.as_ref()], blockhash)?));
+        builder.set_tip(1_000);
+        let sign = |instructions: &[solana_sdk::instruction::Instruction]| {
+            Transaction::new_signed_with_payer(
+                instructions,
+                Some(&payer.pubkey()),
+                &[payer.as_ref()],
+                blockhash,
+            )
+        };
+        let tip = solana_sdk::system_instruction::transfer(&payer.pubkey(), &tip_account, 1_000);
+        builder.add_transaction(&sign(&[])).unwrap();
+        builder.add_transaction(&sign(&[tip])).unwrap();
+        let first_tx = builder.transactions[0].clone();
+
+        let result = builder.submit().await.unwrap();
+        assert!(
+            matches!(result.status, BundleStatus::Accepted { ref bundle_id } if bundle_id == "old")
+        );
+
+        let result = builder.resubmit_with_tip(5_000).await.unwrap();
+        assert!(
+            matches!(result.status, BundleStatus::Accepted { ref bundle_id } if bundle_id == "new")
+        );
+        assert_eq!(result.tip_paid_lamports, 5_000);
+        assert_eq!(builder.tip_lamports(), 5_000);
+        // Only the tip transaction changes
+        assert_eq!(builder.transactions[0], first_tx);
+        let tip_tx: Transaction = bincode::deserialize(&builder.transactions[1]).unwrap();
+        assert!(tip_tx.verify().is_ok());
+
+        // The old id is ignored by polling even inside its grace window
+        assert!(builder.is_superseded("old"));
+        assert!(!builder.is_superseded("new"));
+        assert!(matches!(
+            builder.check_status("old").await.unwrap(),
+            BundleStatus::Expired { .. }
+        ));
+        assert!(matches!(
+            builder.check_status("new").await.unwrap(),
+            BundleStatus::Accepted { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resubmit_with_tip_requires_resigner() {
+        let server = wiremock::MockServer::start().await;
+        let mut builder = mock_builder(&server);
+        assert!(matches!(
+            builder.resubmit_with_tip(5_000).await,
+            Err(TxOptimizerError::Config(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resubmit_without_tip_transfer_keeps_state() {
+        let payer = Arc::new(Keypair::new());
+        let config = Config {
+            min_tip_lamports: 0,
+            ..Config::default()
+        };
+        let signer = payer.clone();
+        let mut builder = JitoBundleBuilder::new(&config)
+            .unwrap()
+            .with_resigner(move |tx, blockhash| Ok(tx.try_sign(&[signer.as_ref()], blockhash)?));
+        builder.set_tip(1_000);
+        builder
+            .add_transaction(&Transaction::new_signed_with_payer(
+                &[],
+                Some(&payer.pubkey()),
+                &[payer.as_ref()],
+                Hash::new_unique(),
+            ))
+            .unwrap();
+        let before = builder.transactions.clone();
+
+        assert!(matches!(
+            builder.resubmit_with_tip(5_000).await,
+            Err(TxOptimizerError::Config(_))
+        ));
+        assert_eq!(builder.tip_lamports(), 1_000);
+        assert_eq!(builder.transactions, before);
     }
 }
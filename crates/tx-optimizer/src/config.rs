@@ -1,5 +1,18 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use solana_client::client_error::{ClientErrorKind, Result as ClientResult};
+use solana_client::rpc_client::{RpcClient, RpcClientConfig};
+use solana_client::rpc_request::{RpcError, RpcRequest, RpcResponseErrorData};
+use solana_client::rpc_sender::{RpcSender, RpcTransportStats};
+use solana_sdk::signature::Keypair;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::warn;
+use zeroize::Zeroize;
+
+use crate::error::TxOptimizerError;
+use crate::priority_fee::StrategyMap;
 
 /// Known Jito block engine endpoints by region.
 /// See: https://jito-labs.gitbook.io/mev/
@@ -22,9 +35,23 @@ pub const JITO_TIP_ACCOUNTS: [&str; 8] = [
     "3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT",
 ];
 
+/// Environment variable [`Config::load_keypair`] takes the wallet's secret
+/// key from, ahead of the wallet file.
+pub const WALLET_KEY_ENV: &str = "SOLANA_WALLET_KEY";
+
+/// How long [`Config::health_check`] waits on each endpoint.
+pub const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Limit on each request sent by [`Config::rpc_client`], the same as
+/// `RpcClient::new` uses.
+pub const RPC_CLIENT_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// Default priority fee in microlamports per compute unit.
 pub const DEFAULT_PRIORITY_FEE_MICROLAMPORTS: u64 = 10_000;
 
+/// Default cap on the priority fee in microlamports per compute unit.
+pub const DEFAULT_MAX_PRIORITY_FEE_MICROLAMPORTS: u64 = 500_000;
+
 /// Default compute unit limit for a standard transaction.
 pub const DEFAULT_COMPUTE_UNIT_LIMIT: u32 = 200_000;
 
@@ -52,11 +79,51 @@ pub struct Config {
     /// Jito tip amount in lamports
     pub jito_tip_lamports: u64,
 
+    /// Smallest tip a bundle builder accepts; lower tips are clamped up
+    #[serde(default)]
+    pub min_tip_lamports: u64,
+
+    /// Largest tip a bundle builder accepts; higher tips are clamped down
+    #[serde(default = "default_max_tip_lamports")]
+    pub max_tip_lamports: u64,
+
     /// Number of retries for failed transactions
     pub max_retries: u8,
 
     /// Commitment level for transaction confirmation
     pub commitment: String,
+
+    /// Extra headers sent with every RPC request, e.g. a provider API key
+    #[serde(default)]
+    pub rpc_headers: Vec<(String, String)>,
+
+    /// Proxy URL for all outbound HTTP (RPC and block engine)
+    #[serde(default)]
+    pub http_proxy: Option<String>,
+
+    /// Simulate transactions before [`send_and_confirm`](crate::tx::send_and_confirm)
+    /// sends them, and abort if the simulation fails
+    #[serde(default)]
+    pub simulate_first: bool,
+
+    /// Percentile each fee strategy draws its fee from
+    #[serde(default)]
+    pub strategy_map: StrategyMap,
+
+    /// PEM file of extra root certificates to trust for RPC and block engine
+    /// requests, e.g. an internal CA fronting a self-hosted node. The
+    /// system's public roots stay trusted
+    #[serde(default)]
+    pub ca_cert_path: Option<PathBuf>,
+
+    /// Skip TLS certificate verification for RPC and block engine requests.
+    ///
+    /// UNSAFE: anyone on the network path can then impersonate the endpoint,
+    /// read RPC API keys and rewrite responses, including fee estimates and
+    /// bundle statuses. Only for throwaway staging setups; trust the CA with
+    /// `ca_cert_path` instead wherever possible
+    #[serde(default)]
+    pub accept_invalid_certs: bool,
 }
 
 impl Default for Config {
@@ -66,11 +133,19 @@ impl Default for Config {
             ws_url: "wss://api.mainnet-beta.solana.com".to_string(),
             jito_block_engine_url: JITO_BLOCK_ENGINE_MAINNET.to_string(),
             wallet_path: PathBuf::from("~/.config/solana/id.json"),
-            max_priority_fee: 500_000, // 0.5 SOL max
+            max_priority_fee: DEFAULT_MAX_PRIORITY_FEE_MICROLAMPORTS,
             default_slippage_bps: 50,  // 0.5%
             jito_tip_lamports: 10_000, // 0.00001 SOL
+            min_tip_lamports: 0,
+            max_tip_lamports: default_max_tip_lamports(),
             max_retries: 3,
             commitment: "confirmed".to_string(),
+            rpc_headers: Vec::new(),
+            http_proxy: None,
+            simulate_first: false,
+            strategy_map: StrategyMap::default(),
+            ca_cert_path: None,
+            accept_invalid_certs: false,
         }
     }
 }
@@ -100,16 +175,294 @@ impl Config {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(default.jito_tip_lamports),
+            min_tip_lamports: std::env::var("MIN_TIP_LAMPORTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.min_tip_lamports),
+            max_tip_lamports: std::env::var("MAX_TIP_LAMPORTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.max_tip_lamports),
             max_retries: std::env::var("MAX_RETRIES")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(default.max_retries),
             commitment: std::env::var("COMMITMENT_LEVEL").unwrap_or(default.commitment),
+            rpc_headers: std::env::var("RPC_HEADERS")
+                .map(|v| parse_headers(&v))
+                .unwrap_or(default.rpc_headers),
+            http_proxy: std::env::var("HTTP_PROXY").ok().or(default.http_proxy),
+            simulate_first: std::env::var("SIMULATE_FIRST")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.simulate_first),
+            strategy_map: std::env::var("FEE_STRATEGY_PERCENTILES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.strategy_map),
+            ca_cert_path: std::env::var("CA_CERT_PATH")
+                .map(PathBuf::from)
+                .ok()
+                .or(default.ca_cert_path),
+            accept_invalid_certs: std::env::var("ACCEPT_INVALID_CERTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.accept_invalid_certs),
+        }
+    }
+
+    /// WebSocket URL to subscribe on.
+    ///
+    /// If `ws_url` is still the default but `rpc_url` was changed, the
+    /// default would point subscriptions at mainnet while requests go
+    /// elsewhere, so a URL is derived from `rpc_url` instead by swapping its
+    /// scheme (`https` to `wss`, `http` to `ws`). An explicitly set `ws_url`,
+    /// or an RPC URL with any other scheme, is used as-is.
+    pub fn effective_ws_url(&self) -> String {
+        let default = Self::default();
+        if self.ws_url != default.ws_url || self.rpc_url == default.rpc_url {
+            return self.ws_url.clone();
+        }
+        if let Some(rest) = self.rpc_url.strip_prefix("https://") {
+            format!("wss://{rest}")
+        } else if let Some(rest) = self.rpc_url.strip_prefix("http://") {
+            format!("ws://{rest}")
+        } else {
+            self.ws_url.clone()
         }
     }
 
+    /// Build a blocking HTTP client for RPC requests, sending
+    /// [`rpc_headers`](Self::rpc_headers) and routed through
+    /// [`http_proxy`](Self::http_proxy) if set.
+    ///
+    /// Trusts the certificates in [`ca_cert_path`](Self::ca_cert_path) on top
+    /// of the public roots, and fails with [`TxOptimizerError::Config`] if
+    /// that file can't be read.
+    pub fn rpc_http_client(&self) -> crate::error::Result<reqwest::blocking::Client> {
+        let mut builder = reqwest::blocking::Client::builder().default_headers(self.header_map()?);
+        if let Some(proxy) = self.proxy()? {
+            builder = builder.proxy(proxy);
+        }
+        for cert in self.root_certificates()? {
+            builder = builder.add_root_certificate(cert);
+        }
+        Ok(builder
+            .danger_accept_invalid_certs(self.skip_cert_verification())
+            .build()?)
+    }
+
+    /// Async counterpart of [`rpc_http_client`](Self::rpc_http_client).
+    fn async_rpc_http_client(&self) -> crate::error::Result<reqwest::Client> {
+        let mut builder = reqwest::Client::builder().default_headers(self.header_map()?);
+        if let Some(proxy) = self.proxy()? {
+            builder = builder.proxy(proxy);
+        }
+        for cert in self.root_certificates()? {
+            builder = builder.add_root_certificate(cert);
+        }
+        Ok(builder
+            .danger_accept_invalid_certs(self.skip_cert_verification())
+            .build()?)
+    }
+
+    /// Build a blocking `RpcClient` whose requests carry the same headers,
+    /// proxy and TLS settings as [`rpc_http_client`](Self::rpc_http_client).
+    ///
+    /// Fails with [`TxOptimizerError::Config`] under the same conditions.
+    pub fn rpc_client(&self) -> crate::error::Result<RpcClient> {
+        let sender = ConfiguredRpcSender {
+            client: self.async_rpc_http_client()?,
+            url: self.rpc_url.clone(),
+            request_id: AtomicU64::new(0),
+            stats: Mutex::default(),
+        };
+        Ok(RpcClient::new_sender(sender, RpcClientConfig::default()))
+    }
+
+    /// [`rpc_headers`](Self::rpc_headers) as a validated header map.
+    fn header_map(&self) -> crate::error::Result<reqwest::header::HeaderMap> {
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (name, value) in &self.rpc_headers {
+            let name = reqwest::header::HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+                TxOptimizerError::Config(format!("Invalid RPC header name {name:?}: {e}"))
+            })?;
+            let value = reqwest::header::HeaderValue::from_str(value).map_err(|e| {
+                TxOptimizerError::Config(format!("Invalid value for RPC header {name}: {e}"))
+            })?;
+            headers.append(name, value);
+        }
+        Ok(headers)
+    }
+
+    /// Build an HTTP client for block engine requests, routed through
+    /// [`http_proxy`](Self::http_proxy) if set.
+    ///
+    /// `rpc_headers` are deliberately not sent, so RPC provider API keys
+    /// never reach the block engine. TLS settings are the same as for
+    /// [`rpc_http_client`](Self::rpc_http_client).
+    pub fn block_engine_http_client(&self) -> crate::error::Result<reqwest::Client> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(proxy) = self.proxy()? {
+            builder = builder.proxy(proxy);
+        }
+        for cert in self.root_certificates()? {
+            builder = builder.add_root_certificate(cert);
+        }
+        Ok(builder
+            .danger_accept_invalid_certs(self.skip_cert_verification())
+            .build()?)
+    }
+
+    /// Certificates from [`ca_cert_path`](Self::ca_cert_path), if set.
+    fn root_certificates(&self) -> crate::error::Result<Vec<reqwest::Certificate>> {
+        let Some(path) = &self.ca_cert_path else {
+            return Ok(Vec::new());
+        };
+        let invalid = |reason: String| {
+            TxOptimizerError::Config(format!(
+                "Cannot load CA certificates from {}: {reason}",
+                path.display()
+            ))
+        };
+        let pem = std::fs::read(expand_home(path)).map_err(|e| invalid(e.to_string()))?;
+        let certs =
+            reqwest::Certificate::from_pem_bundle(&pem).map_err(|e| invalid(e.to_string()))?;
+        if certs.is_empty() {
+            return Err(invalid("no PEM certificates found".to_string()));
+        }
+        Ok(certs)
+    }
+
+    /// [`accept_invalid_certs`](Self::accept_invalid_certs), warning each
+    /// time a client is built with verification off.
+    fn skip_cert_verification(&self) -> bool {
+        if self.accept_invalid_certs {
+            warn!("TLS certificate verification is off (accept_invalid_certs), connections can be intercepted");
+        }
+        self.accept_invalid_certs
+    }
+
+    fn proxy(&self) -> crate::error::Result<Option<reqwest::Proxy>> {
+        self.http_proxy
+            .as_deref()
+            .map(|url| {
+                reqwest::Proxy::all(url).map_err(|e| {
+                    TxOptimizerError::Config(format!("Invalid HTTP proxy {url:?}: {e}"))
+                })
+            })
+            .transpose()
+    }
+
+    /// Check that the RPC node, block engine and WebSocket endpoint are
+    /// reachable.
+    ///
+    /// Sends `getHealth` to the RPC node, `getTipAccounts` to the block
+    /// engine and opens and closes a WebSocket connection to
+    /// [`effective_ws_url`](Self::effective_ws_url), all at once and each
+    /// limited to [`HEALTH_CHECK_TIMEOUT`]. Failures are reported per
+    /// endpoint rather than returned as an error.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use solana_tx_optimizer::config::Config;
+    ///
+    /// # async fn run() {
+    /// let report = Config::from_env().health_check().await;
+    /// for endpoint in report.endpoints() {
+    ///     println!("{}: {}", endpoint.name, endpoint.healthy);
+    /// }
+    /// # }
+    /// ```
+    pub async fn health_check(&self) -> HealthReport {
+        let ws_url = self.effective_ws_url();
+        let (rpc, block_engine, websocket) = tokio::join!(
+            probe("rpc", &self.rpc_url, self.check_rpc()),
+            probe(
+                "block_engine",
+                self.resolve_jito_endpoint(),
+                self.check_block_engine()
+            ),
+            probe("websocket", &ws_url, check_websocket(&ws_url)),
+        );
+        HealthReport {
+            rpc,
+            block_engine,
+            websocket,
+        }
+    }
+
+    async fn check_rpc(&self) -> std::result::Result<(), String> {
+        let client = self.async_rpc_http_client().map_err(|e| e.to_string())?;
+        let body = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "getHealth" });
+        let response = post_json(client.post(&self.rpc_url).json(&body)).await?;
+        match response.get("result").and_then(|r| r.as_str()) {
+            Some("ok") => Ok(()),
+            _ => Err(json_rpc_error(&response)),
+        }
+    }
+
+    async fn check_block_engine(&self) -> std::result::Result<(), String> {
+        let client = self.block_engine_http_client().map_err(|e| e.to_string())?;
+        let url = format!("{}/api/v1/bundles", self.resolve_jito_endpoint());
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getTipAccounts",
+            "params": [],
+        });
+        let response = post_json(client.post(url).json(&body)).await?;
+        match response.get("result").and_then(|r| r.as_array()) {
+            Some(accounts) if !accounts.is_empty() => Ok(()),
+            Some(_) => Err("no tip accounts returned".to_string()),
+            None => Err(json_rpc_error(&response)),
+        }
+    }
+
+    /// Load the wallet keypair from [`wallet_path`](Self::wallet_path).
+    ///
+    /// A leading `~` is expanded to `$HOME`. A missing file or one that is
+    /// not a `solana-keygen` JSON keypair fails with a
+    /// [`TxOptimizerError::Config`] naming the path.
+    ///
+    /// When [`WALLET_KEY_ENV`] (`SOLANA_WALLET_KEY`) is set, the keypair is
+    /// read from it instead, as a JSON array of 64 bytes or a base58-encoded
+    /// secret key, for CI jobs and containers that can't mount a file. The
+    /// parsed copies are zeroed afterwards, but the variable itself stays
+    /// visible to anything that can read the process environment (child
+    /// processes, `/proc/<pid>/environ`, crash dumps) and often ends up in
+    /// CI logs or container specs, so prefer a file with tight permissions
+    /// where one can be mounted.
+    pub fn load_keypair(&self) -> crate::error::Result<Keypair> {
+        if let Ok(mut secret) = std::env::var(WALLET_KEY_ENV) {
+            let keypair = keypair_from_secret(&secret);
+            secret.zeroize();
+            return keypair;
+        }
+
+        let path = expand_home(&self.wallet_path);
+        if !path.exists() {
+            return Err(TxOptimizerError::Config(format!(
+                "wallet not found at {} (set SOLANA_WALLET_PATH to your keypair file, or \
+                 {WALLET_KEY_ENV} to its secret key)",
+                path.display()
+            )));
+        }
+
+        let contents = std::fs::read_to_string(&path)?;
+        let invalid = |reason: String| {
+            TxOptimizerError::Config(format!(
+                "invalid wallet keypair at {}: {reason} (expected a JSON array of 64 bytes, \
+                 as written by `solana-keygen new`)",
+                path.display()
+            ))
+        };
+        let bytes: Vec<u8> = serde_json::from_str(&contents).map_err(|e| invalid(e.to_string()))?;
+        Keypair::from_bytes(&bytes).map_err(|e| invalid(e.to_string()))
+    }
+
     /// Load configuration from a JSON file path.
-    pub fn from_file(path: &std::path::Path) -> anyhow::Result<Self> {
+    pub fn from_file(path: &Path) -> crate::error::Result<Self> {
         let contents = std::fs::read_to_string(path)?;
         let config: Self = serde_json::from_str(&contents)?;
         Ok(config)
@@ -132,6 +485,200 @@ impl Config {
     }
 }
 
+/// Reachability of every endpoint probed by [`Config::health_check`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub rpc: EndpointHealth,
+    pub block_engine: EndpointHealth,
+    pub websocket: EndpointHealth,
+}
+
+impl HealthReport {
+    /// The probed endpoints: RPC, block engine, then WebSocket.
+    pub fn endpoints(&self) -> [&EndpointHealth; 3] {
+        [&self.rpc, &self.block_engine, &self.websocket]
+    }
+
+    /// Whether every endpoint answered as expected.
+    pub fn all_healthy(&self) -> bool {
+        self.endpoints().iter().all(|endpoint| endpoint.healthy)
+    }
+}
+
+/// Outcome of probing a single endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EndpointHealth {
+    /// `rpc`, `block_engine` or `websocket`
+    pub name: String,
+    pub url: String,
+    pub healthy: bool,
+    /// Time until the probe finished or timed out
+    pub latency_ms: u128,
+    /// Why the probe failed; `None` when healthy
+    pub error: Option<String>,
+}
+
+/// Run `check` against `url` with [`HEALTH_CHECK_TIMEOUT`], timing it.
+async fn probe(
+    name: &str,
+    url: &str,
+    check: impl std::future::Future<Output = std::result::Result<(), String>>,
+) -> EndpointHealth {
+    let start = Instant::now();
+    let outcome = match tokio::time::timeout(HEALTH_CHECK_TIMEOUT, check).await {
+        Ok(outcome) => outcome,
+        Err(_) => Err(format!(
+            "no response within {}s",
+            HEALTH_CHECK_TIMEOUT.as_secs()
+        )),
+    };
+    EndpointHealth {
+        name: name.to_string(),
+        url: url.to_string(),
+        healthy: outcome.is_ok(),
+        latency_ms: start.elapsed().as_millis(),
+        error: outcome.err(),
+    }
+}
+
+/// Send a JSON-RPC request and parse the response body.
+async fn post_json(
+    request: reqwest::RequestBuilder,
+) -> std::result::Result<serde_json::Value, String> {
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    let status = response.status();
+    let body = response.text().await.map_err(|e| e.to_string())?;
+    serde_json::from_str(&body).map_err(|_| format!("HTTP {status}: unexpected response {body:?}"))
+}
+
+/// The JSON-RPC error message in `response`, or a note that there was none.
+fn json_rpc_error(response: &serde_json::Value) -> String {
+    response
+        .pointer("/error/message")
+        .and_then(|message| message.as_str())
+        .map_or_else(|| format!("unexpected response {response}"), str::to_string)
+}
+
+/// Open a WebSocket connection to `url` and close it again.
+async fn check_websocket(url: &str) -> std::result::Result<(), String> {
+    use solana_client::nonblocking::pubsub_client::PubsubClient;
+
+    let client = PubsubClient::new(url).await.map_err(|e| e.to_string())?;
+    client.shutdown().await.map_err(|e| e.to_string())
+}
+
+/// [`RpcSender`] over a client from [`Config`]'s HTTP settings, in place of
+/// solana-client's own `HttpSender`, which can't be given one.
+struct ConfiguredRpcSender {
+    client: reqwest::Client,
+    url: String,
+    request_id: AtomicU64,
+    stats: Mutex<RpcTransportStats>,
+}
+
+impl ConfiguredRpcSender {
+    async fn post(&self, body: serde_json::Value) -> reqwest::Result<serde_json::Value> {
+        self.client
+            .post(&self.url)
+            .json(&body)
+            .timeout(RPC_CLIENT_TIMEOUT)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl RpcSender for ConfiguredRpcSender {
+    async fn send(
+        &self,
+        request: RpcRequest,
+        params: serde_json::Value,
+    ) -> ClientResult<serde_json::Value> {
+        let request_id = self.request_id.fetch_add(1, Ordering::Relaxed);
+        let start = Instant::now();
+        let response = self
+            .post(request.build_request_json(request_id, params))
+            .await;
+        if let Ok(mut stats) = self.stats.lock() {
+            stats.request_count += 1;
+            stats.elapsed_time += start.elapsed();
+        }
+
+        let mut json = response.map_err(|e| ClientErrorKind::Custom(e.to_string()))?;
+        if let Some(error) = json.get("error").filter(|error| error.is_object()) {
+            return Err(RpcError::RpcResponseError {
+                code: error["code"].as_i64().unwrap_or_default(),
+                message: json_rpc_error(&json),
+                data: RpcResponseErrorData::Empty,
+            }
+            .into());
+        }
+        Ok(json["result"].take())
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.stats
+            .lock()
+            .map(|stats| stats.clone())
+            .unwrap_or_default()
+    }
+
+    fn url(&self) -> String {
+        self.url.clone()
+    }
+}
+
+/// Parse a secret key given as a JSON byte array or base58, as accepted in
+/// [`WALLET_KEY_ENV`], zeroing the decoded bytes afterwards.
+///
+/// Errors never quote the secret.
+fn keypair_from_secret(secret: &str) -> crate::error::Result<Keypair> {
+    let invalid = |reason: &str| {
+        TxOptimizerError::Config(format!(
+            "invalid {WALLET_KEY_ENV}: {reason} (expected a JSON array of 64 bytes or a \
+             base58-encoded secret key)"
+        ))
+    };
+    let secret = secret.trim();
+    let mut bytes: Vec<u8> = if secret.starts_with('[') {
+        serde_json::from_str(secret).map_err(|_| invalid("malformed JSON byte array"))?
+    } else {
+        bs58::decode(secret)
+            .into_vec()
+            .map_err(|_| invalid("not valid base58"))?
+    };
+    let keypair = Keypair::from_bytes(&bytes).map_err(|_| invalid("not a valid ed25519 keypair"));
+    bytes.zeroize();
+    keypair
+}
+
+/// No upper tip bound unless one is configured.
+fn default_max_tip_lamports() -> u64 {
+    u64::MAX
+}
+
+/// Replace a leading `~` with `$HOME`, leaving the path as-is if `HOME`
+/// is unset.
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), std::env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Parse `RPC_HEADERS`: comma-separated `Name: value` pairs.
+/// Entries without a colon are ignored.
+fn parse_headers(raw: &str) -> Vec<(String, String)> {
+    raw.split(',')
+        .filter_map(|pair| pair.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .filter(|(name, _)| !name.is_empty())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,6 +691,269 @@ mod tests {
         assert!(config.rpc_url.contains("mainnet"));
     }
 
+    #[test]
+    fn test_effective_ws_url() {
+        let default = Config::default();
+        assert_eq!(default.effective_ws_url(), default.ws_url);
+
+        let private = Config {
+            rpc_url: "https://rpc.example.com/api-key".to_string(),
+            ..Config::default()
+        };
+        assert_eq!(private.effective_ws_url(), "wss://rpc.example.com/api-key");
+
+        let local = Config {
+            rpc_url: "http://127.0.0.1:8899".to_string(),
+            ..Config::default()
+        };
+        assert_eq!(local.effective_ws_url(), "ws://127.0.0.1:8899");
+
+        let explicit = Config {
+            ws_url: "wss://ws.example.com".to_string(),
+            ..private
+        };
+        assert_eq!(explicit.effective_ws_url(), "wss://ws.example.com");
+    }
+
+    #[test]
+    fn test_http_settings() {
+        assert_eq!(
+            parse_headers("x-api-key: abc123, Authorization: Bearer t:o:k, junk"),
+            vec![
+                ("x-api-key".to_string(), "abc123".to_string()),
+                ("Authorization".to_string(), "Bearer t:o:k".to_string()),
+            ]
+        );
+
+        let mut config = Config {
+            rpc_headers: vec![("x-api-key".to_string(), "abc123".to_string())],
+            http_proxy: Some("http://proxy.internal:3128".to_string()),
+            ..Config::default()
+        };
+        assert!(config.rpc_http_client().is_ok());
+        assert!(config.block_engine_http_client().is_ok());
+
+        config
+            .rpc_headers
+            .push(("bad header".to_string(), "x".to_string()));
+        assert!(matches!(
+            config.rpc_http_client(),
+            Err(TxOptimizerError::Config(_))
+        ));
+
+        // Older config files without the new fields still load
+        let json = serde_json::to_value(Config::default()).unwrap();
+        let mut json = json.as_object().unwrap().clone();
+        json.remove("rpc_headers");
+        json.remove("http_proxy");
+        json.remove("simulate_first");
+        let config: Config = serde_json::from_value(json.into()).unwrap();
+        assert!(config.rpc_headers.is_empty());
+        assert!(!config.simulate_first);
+    }
+
+    #[tokio::test]
+    async fn test_rpc_client_sends_configured_headers() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::header("x-api-key", "abc123"))
+            .respond_with(|request: &wiremock::Request| {
+                let body: serde_json::Value = request.body_json().unwrap();
+                let reply = match body["method"].as_str() {
+                    Some("getVersion") => serde_json::json!({
+                        "result": { "solana-core": "1.18.26", "feature-set": 0 },
+                    }),
+                    Some("getSlot") => serde_json::json!({ "result": 42 }),
+                    _ => serde_json::json!({
+                        "error": { "code": -32602, "message": "Invalid param" },
+                    }),
+                };
+                wiremock::ResponseTemplate::new(200).set_body_json(reply)
+            })
+            .mount(&server)
+            .await;
+
+        let config = Config {
+            rpc_url: server.uri(),
+            rpc_headers: vec![("x-api-key".to_string(), "abc123".to_string())],
+            ..Config::default()
+        };
+        // The blocking client runs its own runtime, so keep it off this one
+        let (slot, balance) = tokio::task::spawn_blocking(move || {
+            let client = config.rpc_client().unwrap();
+            let balance = client.get_balance(&solana_sdk::pubkey::Pubkey::new_unique());
+            (client.get_slot().unwrap(), balance)
+        })
+        .await
+        .unwrap();
+        assert_eq!(slot, 42);
+        let err = balance.unwrap_err();
+        assert!(err.to_string().contains("Invalid param"), "{err}");
+    }
+
+    #[test]
+    fn test_custom_ca_certificates() {
+        // Self-signed `CN=tx-optimizer test CA`
+        const TEST_CA: &str = "-----BEGIN CERTIFICATE-----
+MIIBlTCCATugAwIBAgIUHHubkvKyIZ6AmzbCqMEx2aPekgUwCgYIKoZIzj0EAwIw
+HzEdMBsGA1UEAwwUdHgtb3B0aW1pemVyIHRlc3QgQ0EwIBcNMjYxMDE1MDEzMjA3
+WhgPMjEyNjA5MjEwMTMyMDdaMB8xHTAbBgNVBAMMFHR4LW9wdGltaXplciB0ZXN0
+IENBMFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAErdKayGP7GvPiQ4p2/2T3KOSb
+lr5t1p8nkUKUGKGqwgzm30Knf4KXdWSdbSykS4KWDJsGGxTf2NGjgdK1w51LYaNT
+MFEwHQYDVR0OBBYEFPb7UiEzBOZpP8QayvQmRvYqhwLpMB8GA1UdIwQYMBaAFPb7
+UiEzBOZpP8QayvQmRvYqhwLpMA8GA1UdEwEB/wQFMAMBAf8wCgYIKoZIzj0EAwID
+SAAwRQIhANgfz85Q7mv0DhDRSKV3GImsPqzNc1IFoRx0xXO6PJepAiA1E44/ieq7
+zfeJP+bRWZNSHSuDDbMmRKNga8vaTTme9g==
+-----END CERTIFICATE-----
+";
+        let path = std::env::temp_dir().join(format!("ca-{}.pem", std::process::id()));
+        std::fs::write(&path, TEST_CA).unwrap();
+        let mut config = Config {
+            ca_cert_path: Some(path.clone()),
+            accept_invalid_certs: true,
+            ..Config::default()
+        };
+        assert!(config.rpc_http_client().is_ok());
+        assert!(config.block_engine_http_client().is_ok());
+        assert_eq!(config.root_certificates().unwrap().len(), 1);
+
+        std::fs::write(&path, "not a certificate").unwrap();
+        let err = config.block_engine_http_client().unwrap_err();
+        assert!(err.to_string().contains("no PEM certificates"), "{err}");
+
+        std::fs::remove_file(&path).unwrap();
+        let err = config.rpc_http_client().unwrap_err();
+        assert!(matches!(err, TxOptimizerError::Config(_)));
+        assert!(
+            err.to_string().contains(&path.display().to_string()),
+            "{err}"
+        );
+
+        config.ca_cert_path = None;
+        assert!(config.root_certificates().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_keypair_from_secret() {
+        use solana_sdk::signer::Signer;
+
+        let keypair = Keypair::new();
+        let json = serde_json::to_string(&keypair.to_bytes().to_vec()).unwrap();
+        let from_json = keypair_from_secret(&json).unwrap();
+        assert_eq!(from_json.pubkey(), keypair.pubkey());
+        let from_base58 = keypair_from_secret(&format!("{}\n", keypair.to_base58_string()));
+        assert_eq!(from_base58.unwrap().pubkey(), keypair.pubkey());
+
+        for bad in ["[1, 2, 3]", "[1, 2", "0OIl", ""] {
+            let err = keypair_from_secret(bad).unwrap_err().to_string();
+            assert!(err.contains(WALLET_KEY_ENV), "{err}");
+            if !bad.is_empty() {
+                assert!(!err.contains(bad), "{err}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_load_keypair() {
+        use solana_sdk::signer::Signer;
+
+        let path = std::env::temp_dir().join(format!("wallet-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut config = Config {
+            wallet_path: path.clone(),
+            ..Config::default()
+        };
+
+        let err = config.load_keypair().unwrap_err().to_string();
+        assert!(err.contains("wallet not found at"), "{err}");
+
+        std::fs::write(&path, "not a keypair").unwrap();
+        let err = config.load_keypair().unwrap_err().to_string();
+        assert!(err.contains("expected a JSON array of 64 bytes"), "{err}");
+
+        std::fs::write(&path, "[1, 2, 3]").unwrap();
+        assert!(matches!(
+            config.load_keypair(),
+            Err(TxOptimizerError::Config(_))
+        ));
+
+        let keypair = Keypair::new();
+        std::fs::write(
+            &path,
+            serde_json::to_string(&keypair.to_bytes().to_vec()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(config.load_keypair().unwrap().pubkey(), keypair.pubkey());
+
+        if let Some(home) = std::env::var_os("HOME") {
+            config.wallet_path = PathBuf::from("~/.config/solana/id.json");
+            assert_eq!(
+                expand_home(&config.wallet_path),
+                PathBuf::from(home).join(".config/solana/id.json")
+            );
+        }
+        assert_eq!(expand_home(&path), path);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_health_check() {
+        use wiremock::matchers::{body_partial_json, method, path};
+
+        let respond = |body: serde_json::Value| {
+            wiremock::ResponseTemplate::new(200).set_body_json(body)
+        };
+        let server = wiremock::MockServer::start().await;
+        let get_health = serde_json::json!({ "method": "getHealth" });
+        wiremock::Mock::given(method("POST"))
+            .and(path("/"))
+            .and(body_partial_json(get_health))
+            .respond_with(respond(serde_json::json!({
+                "jsonrpc": "2.0", "id": 1, "result": "ok"
+            })))
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(method("POST"))
+            .and(path("/api/v1/bundles"))
+            .respond_with(respond(serde_json::json!({
+                "jsonrpc": "2.0", "id": 1, "result": [JITO_TIP_ACCOUNTS[0]]
+            })))
+            .mount(&server)
+            .await;
+
+        let config = Config {
+            rpc_url: server.uri(),
+            jito_block_engine_url: server.uri(),
+            // Nothing listens on port 1
+            ws_url: "ws://127.0.0.1:1".to_string(),
+            ..Config::default()
+        };
+        let report = config.health_check().await;
+        assert!(report.rpc.healthy, "{:?}", report.rpc.error);
+        let block_engine = &report.block_engine;
+        assert!(block_engine.healthy, "{:?}", block_engine.error);
+        assert_eq!(report.rpc.error, None);
+        assert!(!report.websocket.healthy);
+        assert!(report.websocket.error.is_some());
+        assert!(!report.all_healthy());
+
+        // A node that is behind reports the JSON-RPC error
+        server.reset().await;
+        wiremock::Mock::given(method("POST"))
+            .respond_with(respond(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "code": -32005, "message": "Node is behind by 42 slots" }
+            })))
+            .mount(&server)
+            .await;
+        let report = config.health_check().await;
+        let error = report.rpc.error.as_deref();
+        assert_eq!(error, Some("Node is behind by 42 slots"));
+        assert!(!report.block_engine.healthy);
+    }
+
     #[test]
     fn test_random_tip_account_is_valid() {
         let account = Config::random_tip_account();
@@ -0,0 +1,144 @@
+use solana_sdk::{hash::Hash, signature::Signature};
+use std::time::Duration;
+
+/// Result alias used across the library's public API.
+pub type Result<T, E = TxOptimizerError> = std::result::Result<T, E>;
+
+/// Errors returned by the library.
+///
+/// Implements [`std::error::Error`], so `?` converts it into `anyhow::Error`
+/// in applications that don't need to match on the variant.
+#[derive(Debug, thiserror::Error)]
+pub enum TxOptimizerError {
+    /// The RPC node returned an error or could not be queried
+    #[error("RPC error: {0}")]
+    Rpc(String),
+
+    /// HTTP transport failure talking to an RPC node or block engine
+    #[error("HTTP request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// An RPC request got no response within the configured timeout
+    #[error("{method} request timed out after {timeout:?}")]
+    Timeout { method: String, timeout: Duration },
+
+    /// A transaction, payload or response could not be (de)serialized
+    #[error("Serialization failed: {0}")]
+    Serialization(String),
+
+    /// The bundle already holds the maximum number of transactions
+    #[error("Bundle already contains the maximum of {max} transactions")]
+    BundleTooLarge { max: usize },
+
+    /// The bundle's base58-encoded transactions exceed the block engine's
+    /// size limit
+    #[error("Bundle is {bytes} bytes once base58-encoded, over the {max}-byte limit")]
+    BundleTooManyBytes { bytes: usize, max: usize },
+
+    /// A single bundled transaction is larger than a packet once
+    /// base58-encoded
+    #[error("Transaction {index} is {bytes} bytes once base58-encoded, over the {max}-byte limit")]
+    TransactionTooLarge {
+        index: usize,
+        bytes: usize,
+        max: usize,
+    },
+
+    /// A bundle with no transactions cannot be built or submitted
+    #[error("Cannot build an empty bundle")]
+    EmptyBundle,
+
+    /// A bundled transaction's recent blockhash has expired
+    #[error("Transaction {index} in the bundle uses expired blockhash {blockhash}")]
+    StaleBlockhash { index: usize, blockhash: Hash },
+
+    /// A bundled transaction is missing a signature or has an invalid one
+    #[error("Transaction {index} in the bundle is not properly signed: {reason}")]
+    InvalidSignature { index: usize, reason: String },
+
+    /// The RPC response carried no prioritization fee data
+    #[error("No priority fee data in RPC response")]
+    NoFeeData,
+
+    /// The block engine refused the bundle
+    #[error("Bundle rejected: {reason}")]
+    BundleRejected {
+        reason: String,
+        /// JSON-RPC error code, when the engine sent one
+        code: Option<i64>,
+    },
+
+    /// Simulating the transaction before sending it failed
+    #[error("Transaction simulation failed: {reason}")]
+    SimulationFailed {
+        reason: String,
+        /// Program logs from the simulation
+        logs: Vec<String>,
+    },
+
+    /// The transaction landed but failed on-chain
+    #[error("Transaction {signature} failed: {reason}")]
+    TransactionFailed { signature: Signature, reason: String },
+
+    /// Every send attempt was used up without the transaction confirming
+    #[error("Transaction not confirmed after {attempts} attempt(s): {last_error}")]
+    RetriesExhausted {
+        /// Signature of the last attempt, if it got as far as being sent
+        last_signature: Option<Signature>,
+        attempts: u8,
+        last_error: String,
+    },
+
+    /// Signing the transaction failed
+    #[error("Signing failed: {0}")]
+    Signing(#[from] solana_sdk::signer::SignerError),
+
+    /// The operation was cancelled before it finished
+    #[error("Operation cancelled")]
+    Cancelled,
+
+    /// A config value could not be parsed
+    #[error("Invalid config: {0}")]
+    Config(String),
+
+    /// Reading a file failed
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl From<solana_client::client_error::ClientError> for TxOptimizerError {
+    fn from(error: solana_client::client_error::ClientError) -> Self {
+        Self::Rpc(error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for TxOptimizerError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Serialization(error.to_string())
+    }
+}
+
+impl From<bincode::Error> for TxOptimizerError {
+    fn from(error: bincode::Error) -> Self {
+        Self::Serialization(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_converts_into_anyhow() {
+        let err: anyhow::Error = TxOptimizerError::BundleRejected {
+            reason: "bundle already processed".to_string(),
+            code: None,
+        }
+        .into();
+        assert_eq!(err.to_string(), "Bundle rejected: bundle already processed");
+        assert!(matches!(
+            err.downcast_ref::<TxOptimizerError>(),
+            Some(TxOptimizerError::BundleRejected { .. })
+        ));
+    }
+}
@@ -0,0 +1,190 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Result, TxOptimizerError};
+use crate::priority_fee::FeeEstimate;
+
+/// One line of the history file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HistoryRecord {
+    timestamp: DateTime<Utc>,
+    estimate: FeeEstimate,
+}
+
+/// Min/median/max of the recommended fee across a history file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeHistorySummary {
+    /// Number of estimates in the file
+    pub count: usize,
+    /// Lowest recommended fee in the file
+    pub min: u64,
+    /// Upper median for an even count, matching the estimator's p50 rank
+    pub median: u64,
+    /// Highest recommended fee in the file
+    pub max: u64,
+}
+
+/// Append-only JSON-lines log of fee estimates.
+///
+/// Each line is `{"timestamp": <RFC 3339>, "estimate": <FeeEstimate>}`, so the
+/// file can be inspected with `jq` or loaded back with [`FeeHistory::load`].
+///
+/// # Example
+/// ```no_run
+/// use solana_tx_optimizer::history::FeeHistory;
+/// use solana_tx_optimizer::priority_fee::{FeeStrategy, PriorityFeeEstimator};
+///
+/// let estimator = PriorityFeeEstimator::new("https://api.mainnet-beta.solana.com");
+/// let history = FeeHistory::new("fees.jsonl");
+/// history.append(&estimator.estimate(FeeStrategy::Fast).unwrap()).unwrap();
+/// println!("{:?}", history.summary().unwrap());
+/// ```
+pub struct FeeHistory {
+    path: PathBuf,
+}
+
+impl FeeHistory {
+    /// Create a writer for the history file at `path`.
+    /// The file is created on the first [`append`](Self::append).
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Path of the history file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append `estimate` with the current UTC time.
+    pub fn append(&self, estimate: &FeeEstimate) -> Result<()> {
+        self.append_at(Utc::now(), estimate)
+    }
+
+    fn append_at(&self, timestamp: DateTime<Utc>, estimate: &FeeEstimate) -> Result<()> {
+        let record = HistoryRecord {
+            timestamp,
+            estimate: estimate.clone(),
+        };
+        let mut line = serde_json::to_string(&record)?;
+        line.push('\n');
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    /// Load every estimate from a history file, oldest first.
+    ///
+    /// Blank lines are skipped; a malformed line fails the whole load.
+    pub fn load(path: impl AsRef<Path>) -> Result<Vec<(DateTime<Utc>, FeeEstimate)>> {
+        let contents = std::fs::read_to_string(path)?;
+        contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                let record: HistoryRecord = serde_json::from_str(line).map_err(|e| {
+                    TxOptimizerError::Serialization(format!("history line {}: {e}", i + 1))
+                })?;
+                Ok((record.timestamp, record.estimate))
+            })
+            .collect()
+    }
+
+    /// Summarize the recommended fee over the whole file.
+    ///
+    /// Returns `None` if the file holds no estimates.
+    pub fn summary(&self) -> Result<Option<FeeHistorySummary>> {
+        let mut fees: Vec<u64> = Self::load(&self.path)?
+            .into_iter()
+            .map(|(_, estimate)| estimate.recommended_fee)
+            .collect();
+        if fees.is_empty() {
+            return Ok(None);
+        }
+        fees.sort_unstable();
+
+        Ok(Some(FeeHistorySummary {
+            count: fees.len(),
+            min: fees[0],
+            median: fees[fees.len() / 2],
+            max: fees[fees.len() - 1],
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::priority_fee::{FeeDataSource, FeePercentiles, FeeStrategy};
+
+    fn estimate(recommended_fee: u64) -> FeeEstimate {
+        FeeEstimate {
+            recommended_fee,
+            strategy: FeeStrategy::Fast,
+            slots_sampled: 150,
+            percentiles: FeePercentiles {
+                p10: None,
+                p25: 1,
+                p50: 2,
+                p75: 3,
+                p90: 4,
+                p95: None,
+                p99: None,
+                max: 5,
+            },
+            commitment: solana_sdk::commitment_config::CommitmentLevel::Confirmed,
+            sanity_clamped: false,
+            effective_percentile: None,
+            window_slots: None,
+            data_source: FeeDataSource::Sampled,
+            scoped: false,
+            scoped_accounts: Vec::new(),
+            fallback_used: None,
+        }
+    }
+
+    #[test]
+    fn test_append_load_summary() {
+        let path = std::env::temp_dir().join(format!("fee-history-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let history = FeeHistory::new(&path);
+        assert!(
+            FeeHistory::load(&path).is_err(),
+            "missing file should fail to load"
+        );
+
+        let first = DateTime::parse_from_rfc3339("2024-05-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        for (i, fee) in [300, 100, 200, 400].into_iter().enumerate() {
+            history
+                .append_at(first + chrono::Duration::seconds(i as i64), &estimate(fee))
+                .unwrap();
+        }
+
+        let records = FeeHistory::load(&path).unwrap();
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0].0, first);
+        assert_eq!(records[0].1.recommended_fee, 300);
+        assert_eq!(records[3].1.strategy, FeeStrategy::Fast);
+
+        let summary = history.summary().unwrap().unwrap();
+        assert_eq!(
+            summary,
+            FeeHistorySummary {
+                count: 4,
+                min: 100,
+                median: 300,
+                max: 400,
+            }
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
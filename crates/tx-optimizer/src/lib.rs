@@ -9,6 +9,14 @@
 //!   percentile-based fee recommendations.
 //! - **Jito Bundle Builder**: Construct, submit, and track Jito MEV bundles
 //!   with retry logic and exponential backoff.
+//! - **Priority Transactions**: Assemble compute-budget and payload
+//!   instructions into a signed transaction, then send it with
+//!   blockhash-refreshing retries and wait for confirmation.
+//! - **Fee History** (opt-in `history` feature, `--features history`): Append
+//!   estimates to a JSON-lines log and summarize them later.
+//! - **Vault Client**: List, derive and decode accounts of the companion
+//!   `tx-vault` on-chain program, build and send its instructions, and split
+//!   large payouts into `execute_batch` transactions.
 //! - **Configuration**: Flexible config via environment variables or JSON files.
 //!
 //! ## Quick Start
@@ -26,9 +34,21 @@
 
 pub mod bundle;
 pub mod config;
+pub mod error;
+#[cfg(feature = "history")]
+pub mod history;
 pub mod priority_fee;
+pub mod tx;
+pub mod units;
+pub mod util;
+pub mod vault_client;
 
 // Re-export key types for ergonomic usage
-pub use bundle::{BundleStatus, BundleSubmissionResult, JitoBundleBuilder};
+pub use bundle::{
+    BundleObserver, BundleResult, BundleStatus, BundleSubmissionResult, JitoBundleBuilder,
+    TipAccountProvider,
+};
 pub use config::Config;
+pub use error::TxOptimizerError;
 pub use priority_fee::{FeeEstimate, FeeStrategy, PriorityFeeEstimator};
+pub use units::Lamports;
@@ -1,11 +1,31 @@
-use anyhow::Result;
-use clap::{Parser, Subcommand};
+use anyhow::{Context, Result};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
 use solana_tx_optimizer::{
-    bundle::JitoBundleBuilder,
+    bundle::{find_tip_account, BundleStatus, JitoBundleBuilder},
     config::Config,
-    priority_fee::{FeeStrategy, PriorityFeeEstimator},
+    error::Result as TxResult,
+    priority_fee::{
+        DiffSide, FeeDataSource, FeeDelta, FeeDiff, FeeEstimate, FeeRpc, FeeStrategy, HttpFeeRpc,
+        PrioritizationFeeEntry, PriorityFeeEstimator, QuickNodeFeeLevels,
+    },
+    units::Lamports,
+    util::poll_until_cancelled,
+};
+use base64::Engine;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    signature::Signature,
+    transaction::{self, Transaction},
 };
-use tracing::{info, Level};
+use solana_transaction_status::TransactionConfirmationStatus;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
 use tracing_subscriber::EnvFilter;
 
 #[derive(Parser)]
@@ -24,6 +44,10 @@ struct Cli {
     #[arg(long, global = true, default_value = "info")]
     log_level: String,
 
+    /// Log format. Logs always go to stderr, so stdout carries only results
+    #[arg(long, global = true, value_enum, default_value_t = LogFormat::Human)]
+    log_format: LogFormat,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -32,7 +56,8 @@ struct Cli {
 enum Commands {
     /// Estimate optimal priority fee based on recent network activity
     EstimateFee {
-        /// Fee strategy: economy, standard, fast, turbo
+        /// Fee strategy: economy, standard, fast, turbo, or custom:NN for the
+        /// NNth percentile
         #[arg(short, long, default_value = "standard")]
         strategy: String,
 
@@ -41,15 +66,76 @@ enum Commands {
         buffer: Option<f64>,
 
         /// Scope estimation to transactions involving these program IDs (comma-separated)
-        #[arg(long)]
+        #[arg(long, conflicts_with = "programs_file")]
         programs: Option<String>,
 
+        /// Read program IDs to scope estimation to from a file, one base58 ID
+        /// per line; blank lines and `#` comments are ignored
+        #[arg(long, value_name = "PATH")]
+        programs_file: Option<PathBuf>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Human)]
+        format: OutputFormat,
+
+        /// Output as JSON (alias for --format json)
+        #[arg(long)]
+        json: bool,
+
+        /// Omit the CSV header row (useful when appending to an existing file)
+        #[arg(long)]
+        no_header: bool,
+
+        /// Append the estimate to a JSON-lines history file (requires the
+        /// `history` feature)
+        #[cfg(feature = "history")]
+        #[arg(long, value_name = "PATH")]
+        log: Option<PathBuf>,
+
+        /// Re-estimate every N seconds until Ctrl-C. JSON output becomes one
+        /// compact object per line with `elapsed_ms` and `rpc_attempts`
+        /// added; CSV prints the header once
+        #[arg(long, value_name = "SECONDS")]
+        watch: Option<u64>,
+
+        /// Fail when the recommended fee exceeds this many microlamports/CU
+        /// [default: MAX_PRIORITY_FEE / the config's max_priority_fee]
+        #[arg(long, value_name = "MICROLAMPORTS")]
+        max_fee: Option<u64>,
+
+        /// Cap the recommended fee at --max-fee with a warning instead of
+        /// failing
+        #[arg(long)]
+        cap: bool,
+
+        /// Exit with status 3 when the RPC node returned no fee data and the
+        /// recommended fee is only a fallback. In watch mode such ticks are
+        /// skipped instead
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Estimate the same fee against two RPC endpoints and show the difference
+    CompareFees {
+        /// First RPC endpoint URL
+        #[arg(long, value_name = "URL")]
+        rpc_a: String,
+
+        /// Second RPC endpoint URL, compared against the first
+        #[arg(long, value_name = "URL")]
+        rpc_b: String,
+
+        /// Fee strategy: economy, standard, fast, turbo, or custom:NN for the
+        /// NNth percentile
+        #[arg(short, long, default_value = "standard")]
+        strategy: String,
+
         /// Output as JSON
         #[arg(long)]
         json: bool,
     },
 
-    /// Submit a Jito bundle (reads transactions from stdin)
+    /// Submit a Jito bundle (reads transactions from stdin, --file or --json)
     Bundle {
         /// Tip amount in lamports for Jito validators
         #[arg(short, long, default_value = "10000")]
@@ -62,6 +148,38 @@ enum Commands {
         /// Confirmation timeout in seconds
         #[arg(long, default_value = "30")]
         timeout: u64,
+
+        /// Read base58-encoded transactions from a file (one per line) instead of stdin
+        #[arg(long, value_name = "PATH", conflicts_with = "json_file")]
+        file: Option<PathBuf>,
+
+        /// Read base58-encoded transactions from a file containing a JSON array of strings
+        #[arg(long = "json", value_name = "PATH")]
+        json_file: Option<PathBuf>,
+
+        /// Validate and print the sendBundle payload without submitting it
+        #[arg(long, conflicts_with = "confirm")]
+        dry_run: bool,
+
+        /// Fail when the tip, or the tips the transactions pay, exceed this
+        /// many lamports [default: MAX_TIP_LAMPORTS if set, else 10000000]
+        #[arg(long, value_name = "LAMPORTS")]
+        max_tip: Option<u64>,
+    },
+
+    /// Check the status of a previously submitted Jito bundle
+    BundleStatus {
+        /// Bundle ID returned by `bundle`
+        #[arg(required = true)]
+        bundle_id: String,
+
+        /// Keep polling until the bundle lands or expires
+        #[arg(long)]
+        watch: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Monitor a transaction's confirmation status
@@ -73,20 +191,705 @@ enum Commands {
         /// Use WebSocket subscription instead of polling
         #[arg(long)]
         websocket: bool,
+
+        /// Confirmation timeout in seconds
+        #[arg(long, default_value = "30")]
+        timeout: u64,
+
+        /// Output the final status as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Include program log lines in the transaction details
+        #[arg(long)]
+        logs: bool,
+
+        /// Commitment to wait for (defaults to COMMITMENT_LEVEL / the config)
+        #[arg(long, value_enum)]
+        commitment: Option<CommitmentArg>,
+    },
+
+    /// Decode an encoded transaction and print its signers and instructions
+    DecodeTx {
+        /// Encoded, bincode-serialized transaction
+        #[arg(required = true)]
+        encoded: String,
+
+        /// Encoding of the transaction string
+        #[arg(long, value_enum, default_value_t = TxEncoding::Base58)]
+        encoding: TxEncoding,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check that the RPC, block engine and WebSocket endpoints are reachable
+    Doctor {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
     },
+
+    /// Generate a shell completion script on stdout
+    ///
+    /// Install it with the one-liner for your shell:
+    ///
+    ///   bash:       tx-optimizer completions bash > ~/.local/share/bash-completion/completions/tx-optimizer
+    ///   zsh:        tx-optimizer completions zsh > "${fpath[1]}/_tx-optimizer"
+    ///   fish:       tx-optimizer completions fish > ~/.config/fish/completions/tx-optimizer.fish
+    ///   powershell: tx-optimizer completions powershell >> $PROFILE
+    #[command(hide = true, verbatim_doc_comment)]
+    Completions {
+        /// Target shell
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+}
+
+/// [`HttpFeeRpc`] that counts the requests it sends, failed ones included.
+struct CountingFeeRpc {
+    inner: HttpFeeRpc,
+    requests: AtomicU32,
+}
+
+impl FeeRpc for CountingFeeRpc {
+    fn recent_fees(
+        &self,
+        accounts: &[solana_sdk::pubkey::Pubkey],
+    ) -> TxResult<Vec<PrioritizationFeeEntry>> {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.inner.recent_fees(accounts)
+    }
+
+    fn slot(&self, commitment: CommitmentConfig) -> TxResult<u64> {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.inner.slot(commitment)
+    }
+
+    fn quicknode_fee_levels(
+        &self,
+        account: Option<&solana_sdk::pubkey::Pubkey>,
+    ) -> TxResult<Option<QuickNodeFeeLevels>> {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.inner.quicknode_fee_levels(account)
+    }
+}
+
+/// How fetching one estimate went, reported per line by `--watch --json`.
+#[derive(Serialize)]
+struct FetchStats {
+    elapsed_ms: u128,
+    /// RPC requests sent for the estimate
+    rpc_attempts: u32,
+}
+
+/// One line of `--watch --json` output.
+#[derive(Serialize)]
+struct WatchRecord<'a> {
+    #[serde(flatten)]
+    estimate: &'a FeeEstimate,
+    #[serde(flatten)]
+    stats: &'a FetchStats,
+}
+
+/// Run one estimate on a blocking thread.
+///
+/// The estimator uses blocking HTTP, which panics if driven directly from the
+/// async runtime; its client is created and dropped on the blocking thread
+/// for the same reason.
+async fn fetch_fee_estimate(
+    estimator: PriorityFeeEstimator,
+    config: &Config,
+    strategy: FeeStrategy,
+    buffer: Option<f64>,
+) -> Result<(FeeEstimate, FetchStats)> {
+    let config = config.clone();
+    let started = Instant::now();
+    let (estimate, rpc_attempts) = tokio::task::spawn_blocking(move || {
+        let rpc = Arc::new(CountingFeeRpc {
+            inner: HttpFeeRpc::new(&config.rpc_url).with_http_client(config.rpc_http_client()?),
+            requests: AtomicU32::new(0),
+        });
+        let estimator = estimator.with_rpc(rpc.clone());
+        let estimate = match buffer {
+            Some(buf) => estimator.estimate_with_buffer(strategy, buf),
+            None => estimator.estimate(strategy),
+        };
+        estimate.map(|estimate| (estimate, rpc.requests.load(Ordering::Relaxed)))
+    })
+    .await
+    .context("Fee estimation task panicked")??;
+    let stats = FetchStats {
+        elapsed_ms: started.elapsed().as_millis(),
+        rpc_attempts,
+    };
+    Ok((estimate, stats))
+}
+
+/// `bundle --max-tip` when neither the flag nor `MAX_TIP_LAMPORTS` sets one:
+/// 0.01 SOL.
+const DEFAULT_MAX_TIP_GUARD_LAMPORTS: u64 = 10_000_000;
+
+/// Enforce `estimate-fee --max-fee`: fail when the recommended fee is above
+/// `max_fee`, or with `cap`, lower it to `max_fee` with a warning.
+fn guard_max_fee(estimate: &mut FeeEstimate, max_fee: u64, cap: bool) -> Result<()> {
+    if estimate.recommended_fee <= max_fee {
+        return Ok(());
+    }
+    if !cap {
+        anyhow::bail!(
+            "Recommended fee {} microlamports/CU exceeds --max-fee {max_fee} (pass --cap to cap it instead)",
+            estimate.recommended_fee
+        );
+    }
+    warn!(
+        "Recommended fee {} microlamports/CU exceeds --max-fee, capping to {max_fee}",
+        estimate.recommended_fee
+    );
+    estimate.recommended_fee = max_fee;
+    Ok(())
+}
+
+/// Exit status of `estimate-fee --strict` when the estimate is a fallback
+/// because the RPC node returned no fee data.
+const EXIT_NO_FEE_DATA: i32 = 3;
+
+/// Warn on stderr, whatever the log level, when `estimate` is a fallback
+/// because the RPC node returned no fee data, so scripts don't trust its
+/// all-zero percentiles. Returns whether it was.
+fn warn_if_no_fee_data(estimate: &FeeEstimate) -> bool {
+    if estimate.data_source != FeeDataSource::Fallback {
+        return false;
+    }
+    eprintln!(
+        "WARNING: no priority fee data from the RPC node; {} microlamports/CU is a fallback, \
+         not an estimate",
+        estimate.recommended_fee
+    );
+    true
+}
+
+/// `compare-fees --json` output.
+#[derive(Serialize)]
+struct FeeComparison<'a> {
+    a: &'a FeeEstimate,
+    b: &'a FeeEstimate,
+    diff: &'a FeeDiff,
+}
+
+/// Format a delta as `+500 (+50.0%)`.
+fn format_fee_delta(delta: &FeeDelta) -> String {
+    match delta.percent {
+        Some(percent) => format!("{:+} ({percent:+.1}%)", delta.absolute),
+        None => format!("{:+}", delta.absolute),
+    }
+}
+
+/// Print an estimate in `format`.
+///
+/// In watch mode, given the tick's `FetchStats`, human output redraws the
+/// screen and JSON is printed as a single line per estimate, with the stats
+/// added.
+fn print_fee_estimate(
+    format: OutputFormat,
+    estimate: &FeeEstimate,
+    csv_header: bool,
+    watch: Option<&FetchStats>,
+) -> Result<()> {
+    match format {
+        OutputFormat::Json => match watch {
+            Some(stats) => {
+                let record = WatchRecord { estimate, stats };
+                println!("{}", serde_json::to_string(&record)?);
+            }
+            None => println!("{}", serde_json::to_string_pretty(estimate)?),
+        },
+        OutputFormat::Csv => {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .context("System clock is before the unix epoch")?
+                .as_secs();
+            if csv_header {
+                println!("{FEE_CSV_HEADER}");
+            }
+            println!("{}", fee_csv_row(timestamp, estimate)?);
+        }
+        OutputFormat::Human => {
+            if watch.is_some() {
+                // Clear the screen and move the cursor home.
+                print!("\x1b[2J\x1b[H");
+            }
+            println!("Priority Fee Estimation");
+            println!("=======================");
+            println!("Strategy:        {}", estimate.strategy);
+            println!(
+                "Recommended fee: {} microlamports/CU",
+                estimate.recommended_fee
+            );
+            println!("Slots sampled:   {}", estimate.slots_sampled);
+            println!("Commitment:      {:?}", estimate.commitment);
+            if estimate.sanity_clamped {
+                println!("Note:            clamped to the configured sanity bounds");
+            }
+            match estimate.data_source {
+                FeeDataSource::Sampled | FeeDataSource::QuickNode => {}
+                FeeDataSource::AllZeroFiltered => {
+                    println!("Note:            no recent slot paid a priority fee; default used")
+                }
+                FeeDataSource::Fallback => println!("Note:            no fee data; default used"),
+            }
+            println!();
+            println!("Percentile breakdown:");
+            println!("  p25: {} microlamports/CU", estimate.percentiles.p25);
+            println!("  p50: {} microlamports/CU", estimate.percentiles.p50);
+            println!("  p75: {} microlamports/CU", estimate.percentiles.p75);
+            println!("  p90: {} microlamports/CU", estimate.percentiles.p90);
+            if let Some(p95) = estimate.percentiles.p95 {
+                println!("  p95: {p95} microlamports/CU");
+            }
+            if let Some(p99) = estimate.percentiles.p99 {
+                println!("  p99: {p99} microlamports/CU");
+            }
+            println!("  max: {} microlamports/CU", estimate.percentiles.max);
+        }
+    }
+    Ok(())
+}
+
+/// Log line format, selected with `--log-format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum LogFormat {
+    Human,
+    /// One JSON object per line, for log aggregators
+    Json,
+}
+
+/// Output format for `estimate-fee`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Human,
+    Json,
+    Csv,
+}
+
+/// Column order for `estimate-fee --format csv`.
+const FEE_CSV_HEADER: &str = "timestamp,strategy,recommended_fee,p25,p50,p75,p90,max,slots_sampled";
+
+/// Format an estimate as a single CSV row matching [`FEE_CSV_HEADER`].
+/// `timestamp` is unix seconds; the strategy is serialized exactly as in the
+/// JSON output (`Fast`, `fast` with `serialize_v2`, or `custom:95`; not
+/// `Fast (p75)`).
+fn fee_csv_row(timestamp: u64, estimate: &FeeEstimate) -> Result<String> {
+    let strategy = serde_json::to_value(estimate.strategy)?;
+    let strategy = strategy.as_str().unwrap_or_default();
+    Ok(format!(
+        "{},{},{},{},{},{},{},{},{}",
+        timestamp,
+        strategy,
+        estimate.recommended_fee,
+        estimate.percentiles.p25,
+        estimate.percentiles.p50,
+        estimate.percentiles.p75,
+        estimate.percentiles.p90,
+        estimate.percentiles.max,
+        estimate.slots_sampled
+    ))
+}
+
+/// Commitment levels accepted on the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CommitmentArg {
+    Processed,
+    Confirmed,
+    Finalized,
+}
+
+impl From<CommitmentArg> for CommitmentConfig {
+    fn from(commitment: CommitmentArg) -> Self {
+        match commitment {
+            CommitmentArg::Processed => CommitmentConfig::processed(),
+            CommitmentArg::Confirmed => CommitmentConfig::confirmed(),
+            CommitmentArg::Finalized => CommitmentConfig::finalized(),
+        }
+    }
+}
+
+/// String encodings accepted for serialized transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum TxEncoding {
+    Base58,
+    Base64,
+}
+
+/// Decode a single encoded transaction and deserialize it with bincode.
+fn decode_transaction(encoded: &str, encoding: TxEncoding) -> Result<Transaction> {
+    let tx_bytes = match encoding {
+        TxEncoding::Base58 => bs58::decode(encoded)
+            .into_vec()
+            .map_err(|e| anyhow::anyhow!("invalid base58: {e}"))?,
+        TxEncoding::Base64 => base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|e| anyhow::anyhow!("invalid base64: {e}"))?,
+    };
+    bincode::deserialize(&tx_bytes).context("not a valid transaction")
+}
+
+/// Structured view of a transaction printed by `decode-tx`.
+#[derive(Serialize)]
+struct DecodedTransaction {
+    fee_payer: Option<String>,
+    recent_blockhash: String,
+    signatures: Vec<String>,
+    instructions: Vec<DecodedInstruction>,
+}
+
+#[derive(Serialize)]
+struct DecodedInstruction {
+    program_id: String,
+    accounts: Vec<DecodedAccount>,
+    /// Instruction data, base58-encoded
+    data: String,
+}
+
+#[derive(Serialize)]
+struct DecodedAccount {
+    pubkey: String,
+    signer: bool,
+    writable: bool,
+}
+
+impl DecodedTransaction {
+    fn new(tx: &Transaction) -> Result<Self> {
+        let message = &tx.message;
+        let key = |index: u8| {
+            message
+                .account_keys
+                .get(index as usize)
+                .map(|k| k.to_string())
+                .with_context(|| format!("account index {index} out of range"))
+        };
+
+        let instructions = message
+            .instructions
+            .iter()
+            .map(|ix| {
+                let accounts = ix
+                    .accounts
+                    .iter()
+                    .map(|&index| {
+                        Ok(DecodedAccount {
+                            pubkey: key(index)?,
+                            signer: message.is_signer(index as usize),
+                            writable: message.is_writable(index as usize),
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(DecodedInstruction {
+                    program_id: key(ix.program_id_index)?,
+                    accounts,
+                    data: bs58::encode(&ix.data).into_string(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            fee_payer: message.account_keys.first().map(|k| k.to_string()),
+            recent_blockhash: message.recent_blockhash.to_string(),
+            signatures: tx.signatures.iter().map(|s| s.to_string()).collect(),
+            instructions,
+        })
+    }
+}
+
+/// Final outcome of the `monitor` command, printed once when it exits.
+#[derive(Serialize)]
+struct MonitorReport {
+    signature: String,
+    /// Commitment reached on success (`processed`, `confirmed` or
+    /// `finalized`), otherwise `failed` or `timeout`
+    status: &'static str,
+    error: Option<String>,
+    elapsed_ms: u128,
+    /// Highest commitment observed for the transaction, if it was found
+    commitment: Option<TransactionConfirmationStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<TxDetails>,
+}
+
+/// Cost and log summary of a landed transaction.
+#[derive(Serialize)]
+struct TxDetails {
+    slot: u64,
+    fee_lamports: u64,
+    compute_units_consumed: Option<u64>,
+    /// Only populated when `--logs` is passed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    logs: Option<Vec<String>>,
+}
+
+impl MonitorReport {
+    fn new(
+        signature: String,
+        status: Option<transaction::Result<()>>,
+        reached: Option<TransactionConfirmationStatus>,
+        elapsed: Duration,
+    ) -> Self {
+        let (status, error) = match status {
+            Some(Ok(())) => {
+                let reached = reached.as_ref().map_or("confirmed", confirmation_name);
+                (reached, None)
+            }
+            Some(Err(e)) => ("failed", Some(e.to_string())),
+            None => ("timeout", None),
+        };
+        Self {
+            signature,
+            status,
+            error,
+            elapsed_ms: elapsed.as_millis(),
+            commitment: reached,
+            details: None,
+        }
+    }
 }
 
 fn parse_strategy(s: &str) -> Result<FeeStrategy> {
-    match s.to_lowercase().as_str() {
-        "economy" => Ok(FeeStrategy::Economy),
-        "standard" => Ok(FeeStrategy::Standard),
-        "fast" => Ok(FeeStrategy::Fast),
-        "turbo" => Ok(FeeStrategy::Turbo),
-        _ => anyhow::bail!(
-            "Unknown strategy '{}'. Valid options: economy, standard, fast, turbo",
-            s
-        ),
+    Ok(s.parse()?)
+}
+
+/// Wait for `signature` to reach `commitment` via a `signatureSubscribe`
+/// WebSocket subscription.
+///
+/// Returns `Ok(None)` on timeout and `Err` only when the subscription itself
+/// could not be established or was dropped, so callers can fall back to polling.
+fn subscribe_signature_status(
+    ws_url: &str,
+    signature: &Signature,
+    commitment: CommitmentConfig,
+    timeout: Duration,
+) -> Result<Option<transaction::Result<()>>> {
+    use solana_client::{
+        pubsub_client::PubsubClient, rpc_config::RpcSignatureSubscribeConfig,
+        rpc_response::RpcSignatureResult,
+    };
+
+    let config = RpcSignatureSubscribeConfig {
+        commitment: Some(commitment),
+        enable_received_notification: Some(false),
+    };
+    let (mut subscription, receiver) =
+        PubsubClient::signature_subscribe(ws_url, signature, Some(config))?;
+
+    let outcome = match receiver.recv_timeout(timeout) {
+        Ok(response) => match response.value {
+            RpcSignatureResult::ProcessedSignature(result) => Ok(Some(match result.err {
+                None => Ok(()),
+                Some(err) => Err(err),
+            })),
+            // Received notifications are disabled above, so this is never sent.
+            RpcSignatureResult::ReceivedSignature(_) => Ok(None),
+        },
+        Err(e) if e.is_timeout() => Ok(None),
+        Err(e) => Err(anyhow::anyhow!("Signature subscription dropped: {e}")),
+    };
+
+    // Best effort: the server also drops the subscription once it has fired.
+    let _ = subscription.shutdown();
+    outcome
+}
+
+/// The confirmation status at which a wait for `commitment` completes.
+fn confirmation_status(commitment: CommitmentConfig) -> TransactionConfirmationStatus {
+    if commitment.is_finalized() {
+        TransactionConfirmationStatus::Finalized
+    } else if commitment.is_confirmed() {
+        TransactionConfirmationStatus::Confirmed
+    } else {
+        TransactionConfirmationStatus::Processed
+    }
+}
+
+/// Lowercase name, as the RPC reports it in `confirmationStatus`.
+fn confirmation_name(status: &TransactionConfirmationStatus) -> &'static str {
+    match status {
+        TransactionConfirmationStatus::Processed => "processed",
+        TransactionConfirmationStatus::Confirmed => "confirmed",
+        TransactionConfirmationStatus::Finalized => "finalized",
+    }
+}
+
+/// Interval between status polls in the `monitor` and `bundle-status` commands.
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Poll the signature status until the transaction reaches `commitment` or
+/// `timeout` elapses.
+///
+/// Returns the transaction result once `commitment` is reached (`None` on
+/// timeout) together with the highest commitment seen, so a `finalized`
+/// wait that times out can still report that the transaction confirmed.
+///
+/// When `progress` is set, a spinner with the elapsed time is redrawn on
+/// stderr after every poll so stdout stays clean for the final result.
+/// Ctrl-C stops the wait with an error.
+async fn poll_signature_status(
+    client: &solana_client::rpc_client::RpcClient,
+    signature: &Signature,
+    commitment: CommitmentConfig,
+    timeout: Duration,
+    progress: bool,
+) -> Result<(
+    Option<transaction::Result<()>>,
+    Option<TransactionConfirmationStatus>,
+)> {
+    const SPINNER: [char; 4] = ['|', '/', '-', '\\'];
+
+    let cancel = CancellationToken::new();
+    let ctrl_c = tokio::spawn({
+        let cancel = cancel.clone();
+        async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                cancel.cancel();
+            }
+        }
+    });
+
+    let start = Instant::now();
+    let mut tick = 0;
+    let mut reached = None;
+    let status = poll_until_cancelled(STATUS_POLL_INTERVAL, timeout, &cancel, || {
+        // Same lookup as `get_signature_status_with_commitment`, but keeps
+        // the confirmation level when the requested one isn't reached yet.
+        let response = client.get_signature_statuses(&[*signature]);
+        let polled = response.map(|response| {
+            let status = response.value.into_iter().next().flatten()?;
+            reached = Some(status.confirmation_status());
+            let done = status.satisfies_commitment(commitment);
+            done.then_some(status.status)
+        });
+
+        if progress && matches!(polled, Ok(None)) {
+            let seen = match &reached {
+                Some(level) => format!(" (currently {})", confirmation_name(level)),
+                None => String::new(),
+            };
+            eprint!(
+                "\r{} Waiting for {}{seen}... {}s",
+                SPINNER[tick % SPINNER.len()],
+                confirmation_name(&confirmation_status(commitment)),
+                start.elapsed().as_secs()
+            );
+            tick += 1;
+        }
+        std::future::ready(polled.map_err(Into::into))
+    })
+    .await;
+    ctrl_c.abort();
+
+    if progress && tick > 0 {
+        // Clear the spinner line before the caller prints the result.
+        eprint!("\r\x1b[2K");
+    }
+    Ok((status?, reached))
+}
+
+/// Number of `getTransaction` attempts before giving up on transaction details.
+const TX_DETAILS_ATTEMPTS: u32 = 5;
+
+/// Fetch fee, compute units and (optionally) logs for a resolved transaction.
+///
+/// A signature status can be visible slightly before the node serves the full
+/// transaction at the same commitment, so a missing transaction is retried a
+/// few times before returning `None`. `getTransaction` does not accept
+/// `processed`, so that level is upgraded to `confirmed`.
+async fn fetch_transaction_details(
+    client: &solana_client::rpc_client::RpcClient,
+    signature: &Signature,
+    commitment: CommitmentConfig,
+    include_logs: bool,
+) -> Result<Option<TxDetails>> {
+    use solana_client::rpc_config::RpcTransactionConfig;
+    use solana_transaction_status::UiTransactionEncoding;
+
+    let commitment = if commitment.is_at_least_confirmed() {
+        commitment
+    } else {
+        CommitmentConfig::confirmed()
+    };
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Json),
+        commitment: Some(commitment),
+        max_supported_transaction_version: Some(0),
+    };
+
+    for attempt in 1..=TX_DETAILS_ATTEMPTS {
+        match client.get_transaction_with_config(signature, config) {
+            Ok(tx) => {
+                let Some(meta) = tx.transaction.meta else {
+                    return Ok(None);
+                };
+                let logs: Option<Vec<String>> = meta.log_messages.into();
+                return Ok(Some(TxDetails {
+                    slot: tx.slot,
+                    fee_lamports: meta.fee,
+                    compute_units_consumed: meta.compute_units_consumed.into(),
+                    logs: if include_logs { logs } else { None },
+                }));
+            }
+            Err(e) if attempt < TX_DETAILS_ATTEMPTS => {
+                debug!("Transaction not yet available ({e}), retrying");
+                tokio::time::sleep(Duration::from_millis(500)).await;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(None)
+}
+
+/// Collect trimmed lines from `reader` until EOF or the first blank line.
+///
+/// The blank-line terminator lets interactive stdin users finish without EOF.
+fn read_lines(reader: impl BufRead) -> Result<Vec<String>> {
+    let mut lines = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            break;
+        }
+        lines.push(trimmed.to_string());
     }
+    Ok(lines)
+}
+
+/// Read one base58 program ID per line from `path`.
+///
+/// Blank lines are skipped, and `#` starts a comment that runs to the end of
+/// the line.
+fn read_programs_file(path: &std::path::Path) -> Result<Vec<solana_sdk::pubkey::Pubkey>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut programs = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        let id = line.split('#').next().unwrap_or_default().trim();
+        if id.is_empty() {
+            continue;
+        }
+        let program = id.parse().with_context(|| {
+            format!(
+                "{} line {}: invalid program ID '{id}'",
+                path.display(),
+                i + 1
+            )
+        })?;
+        programs.push(program);
+    }
+    debug!(
+        "Read {} program ID(s) from {}",
+        programs.len(),
+        path.display()
+    );
+    Ok(programs)
 }
 
 #[tokio::main]
@@ -97,10 +900,14 @@ async fn main() -> Result<()> {
     let filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new(&cli.log_level));
 
-    tracing_subscriber::fmt()
+    let subscriber = tracing_subscriber::fmt()
         .with_env_filter(filter)
         .with_target(false)
-        .init();
+        .with_writer(std::io::stderr);
+    match cli.log_format {
+        LogFormat::Human => subscriber.init(),
+        LogFormat::Json => subscriber.json().init(),
+    }
 
     // Load config from env, then apply CLI overrides
     let mut config = Config::from_env();
@@ -109,14 +916,34 @@ async fn main() -> Result<()> {
     }
 
     match cli.command {
+        Commands::Completions { shell } => {
+            let mut cmd = Cli::command();
+            let name = cmd.get_name().to_string();
+            clap_complete::generate(shell, &mut cmd, name, &mut std::io::stdout());
+        }
+
         Commands::EstimateFee {
             strategy,
             buffer,
             programs,
+            programs_file,
+            format,
             json,
+            no_header,
+            #[cfg(feature = "history")]
+            log,
+            watch,
+            max_fee,
+            cap,
+            strict,
         } => {
+            let format = if json { OutputFormat::Json } else { format };
+            let max_fee = max_fee.unwrap_or(config.max_priority_fee);
             let strategy = parse_strategy(&strategy)?;
-            let mut estimator = PriorityFeeEstimator::new(&config.rpc_url);
+            let mut estimator = PriorityFeeEstimator::new(&config.rpc_url)
+                .with_commitment(&config.commitment)?
+                .with_max_priority_fee(config.max_priority_fee)
+                .with_strategy_map(config.strategy_map)?;
 
             // Optionally scope to specific program IDs
             if let Some(program_ids) = programs {
@@ -125,64 +952,237 @@ async fn main() -> Result<()> {
                     .map(|s| s.trim().parse())
                     .collect::<std::result::Result<Vec<_>, _>>()?;
                 estimator = estimator.with_scoped_accounts(pubkeys);
+            } else if let Some(path) = programs_file {
+                estimator = estimator.with_scoped_accounts(read_programs_file(&path)?);
             }
 
-            let estimate = if let Some(buf) = buffer {
-                estimator.estimate_with_buffer(strategy, buf)?
-            } else {
-                estimator.estimate(strategy)?
+            let Some(interval) = watch else {
+                let (mut estimate, _) =
+                    fetch_fee_estimate(estimator, &config, strategy, buffer).await?;
+                guard_max_fee(&mut estimate, max_fee, cap)?;
+                let no_fee_data = warn_if_no_fee_data(&estimate);
+                #[cfg(feature = "history")]
+                if let Some(path) = &log {
+                    solana_tx_optimizer::history::FeeHistory::new(path)
+                        .append(&estimate)
+                        .with_context(|| format!("Failed to append to {}", path.display()))?;
+                    debug!("Appended estimate to {}", path.display());
+                }
+                print_fee_estimate(format, &estimate, !no_header, None)?;
+                if strict && no_fee_data {
+                    std::process::exit(EXIT_NO_FEE_DATA);
+                }
+                return Ok(());
+            };
+
+            let mut ticker = tokio::time::interval(Duration::from_secs(interval.max(1)));
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            let ctrl_c = tokio::signal::ctrl_c();
+            tokio::pin!(ctrl_c);
+            let mut csv_header = !no_header;
+
+            loop {
+                let result = tokio::select! {
+                    _ = &mut ctrl_c => break,
+                    result = async {
+                        ticker.tick().await;
+                        fetch_fee_estimate(estimator.clone(), &config, strategy, buffer).await
+                    } => result,
+                };
+
+                // A failed tick keeps the previous readout on screen.
+                let (estimate, stats) = match result.and_then(|(mut estimate, stats)| {
+                    guard_max_fee(&mut estimate, max_fee, cap)?;
+                    Ok((estimate, stats))
+                }) {
+                    Ok(fetched) => fetched,
+                    Err(e) => {
+                        warn!("Fee estimation failed: {e:#}");
+                        continue;
+                    }
+                };
+                if warn_if_no_fee_data(&estimate) && strict {
+                    continue;
+                }
+
+                #[cfg(feature = "history")]
+                if let Some(path) = &log {
+                    if let Err(e) =
+                        solana_tx_optimizer::history::FeeHistory::new(path).append(&estimate)
+                    {
+                        warn!("Failed to append to {}: {e}", path.display());
+                    }
+                }
+                print_fee_estimate(format, &estimate, csv_header, Some(&stats))?;
+                if format == OutputFormat::Human {
+                    println!();
+                    println!("Refreshing every {}s (Ctrl-C to stop)", interval.max(1));
+                }
+                // Hand each tick to a downstream pipe as soon as it's printed.
+                std::io::stdout().flush()?;
+                csv_header = false;
+            }
+        }
+
+        Commands::CompareFees {
+            rpc_a,
+            rpc_b,
+            strategy,
+            json,
+        } => {
+            let strategy = parse_strategy(&strategy)?;
+            let fetch = |rpc_url: String| {
+                let config = Config {
+                    rpc_url,
+                    ..config.clone()
+                };
+                async move {
+                    let estimator = PriorityFeeEstimator::new(&config.rpc_url)
+                        .with_commitment(&config.commitment)?
+                        .with_max_priority_fee(config.max_priority_fee)
+                        .with_strategy_map(config.strategy_map)?;
+                    let (estimate, _) = fetch_fee_estimate(estimator, &config, strategy, None)
+                        .await
+                        .with_context(|| {
+                            format!("Fee estimation against {} failed", config.rpc_url)
+                        })?;
+                    anyhow::Ok(estimate)
+                }
             };
+            let (a, b) = tokio::try_join!(fetch(rpc_a.clone()), fetch(rpc_b.clone()))?;
+            let diff = a.diff(&b);
 
             if json {
-                println!("{}", serde_json::to_string_pretty(&estimate)?);
+                let comparison = FeeComparison {
+                    a: &a,
+                    b: &b,
+                    diff: &diff,
+                };
+                println!("{}", serde_json::to_string_pretty(&comparison)?);
             } else {
-                println!("Priority Fee Estimation");
-                println!("=======================");
-                println!("Strategy:        {}", estimate.strategy);
+                println!("Fee Comparison");
+                println!("==============");
+                println!("Strategy: {strategy}");
+                println!("A: {rpc_a}");
+                println!("B: {rpc_b}");
+                println!();
                 println!(
-                    "Recommended fee: {} microlamports/CU",
-                    estimate.recommended_fee
+                    "{:<12} {:>12} {:>12}  B - A (microlamports/CU)",
+                    "", "A", "B"
                 );
-                println!("Slots sampled:   {}", estimate.slots_sampled);
+                let rows = std::iter::once(("recommended".to_string(), &diff.recommended_fee))
+                    .chain(diff.percentiles.iter().map(|(pct, delta)| {
+                        let label = if *pct == 100 {
+                            "max".to_string()
+                        } else {
+                            format!("p{pct}")
+                        };
+                        (label, delta)
+                    }));
+                for (label, delta) in rows {
+                    println!(
+                        "{label:<12} {:>12} {:>12}  {}",
+                        delta.from,
+                        delta.to,
+                        format_fee_delta(delta)
+                    );
+                }
                 println!();
-                println!("Percentile breakdown:");
-                println!("  p25: {} microlamports/CU", estimate.percentiles.p25);
-                println!("  p50: {} microlamports/CU", estimate.percentiles.p50);
-                println!("  p75: {} microlamports/CU", estimate.percentiles.p75);
-                println!("  p90: {} microlamports/CU", estimate.percentiles.p90);
-                println!("  max: {} microlamports/CU", estimate.percentiles.max);
+                let more = match diff.more_samples {
+                    Some(DiffSide::This) => " (A has more)",
+                    Some(DiffSide::Other) => " (B has more)",
+                    None => "",
+                };
+                println!(
+                    "Slots sampled: A {}, B {}{more}",
+                    a.slots_sampled, b.slots_sampled
+                );
             }
         }
 
-        Commands::Bundle { tip, confirm, timeout } => {
-            info!("Building Jito bundle with {} lamports tip", tip);
+        Commands::Bundle {
+            tip,
+            confirm,
+            timeout,
+            file,
+            json_file,
+            dry_run,
+            max_tip,
+        } => {
+            let max_tip = max_tip.unwrap_or(match config.max_tip_lamports {
+                u64::MAX => DEFAULT_MAX_TIP_GUARD_LAMPORTS,
+                configured => configured,
+            });
+            if tip > max_tip {
+                anyhow::bail!("Tip {} exceeds --max-tip {}", Lamports(tip), Lamports(max_tip));
+            }
+            info!("Building Jito bundle with {} tip", Lamports(tip));
 
-            let mut builder = JitoBundleBuilder::new(&config);
+            let mut builder = JitoBundleBuilder::new(&config)?;
             builder.set_tip(tip);
 
-            // TODO: read serialized transactions from stdin or file
-            // For now, this demonstrates the bundle builder API
-            eprintln!("Reading base58-encoded transactions from stdin (one per line)...");
-            eprintln!("Send EOF (Ctrl+D) when done.");
-
-            let stdin = std::io::stdin();
-            let mut line = String::new();
-            while std::io::BufRead::read_line(&mut stdin.lock(), &mut line)? > 0 {
-                let trimmed = line.trim();
-                if trimmed.is_empty() {
-                    break;
+            let (encoded_txs, source) = match (file, json_file) {
+                (Some(path), _) => {
+                    let contents = std::fs::read_to_string(&path)
+                        .with_context(|| format!("Failed to read {}", path.display()))?;
+                    let txs = contents
+                        .lines()
+                        .map(str::trim)
+                        .filter(|l| !l.is_empty())
+                        .map(str::to_string)
+                        .collect();
+                    (txs, path.display().to_string())
+                }
+                (_, Some(path)) => {
+                    let contents = std::fs::read_to_string(&path)
+                        .with_context(|| format!("Failed to read {}", path.display()))?;
+                    let txs: Vec<String> = serde_json::from_str(&contents).with_context(|| {
+                        format!("{} must contain a JSON array of strings", path.display())
+                    })?;
+                    (txs, path.display().to_string())
                 }
+                (None, None) => {
+                    eprintln!("Reading base58-encoded transactions from stdin (one per line)...");
+                    eprintln!("Send EOF (Ctrl+D) when done.");
+                    (read_lines(std::io::stdin().lock())?, "stdin".to_string())
+                }
+            };
 
-                let tx_bytes = bs58::decode(trimmed)
-                    .into_vec()
-                    .map_err(|e| anyhow::anyhow!("Invalid base58: {e}"))?;
-                let tx: solana_sdk::transaction::Transaction = bincode::deserialize(&tx_bytes)?;
-                builder.add_transaction(&tx)?;
+            if encoded_txs.len() > builder.max_bundle_size() {
+                anyhow::bail!(
+                    "Read {} transactions from {source}, but a bundle holds at most {}",
+                    encoded_txs.len(),
+                    builder.max_bundle_size()
+                );
+            }
+            eprintln!("Read {} transaction(s) from {source}", encoded_txs.len());
 
-                line.clear();
+            let mut tip_account = None;
+            for (i, encoded) in encoded_txs.iter().enumerate() {
+                let tx = decode_transaction(encoded, TxEncoding::Base58)
+                    .with_context(|| format!("Transaction {i}"))?;
+                tip_account = find_tip_account(&tx);
+                builder.add_transaction(&tx)?;
+            }
+            let tip_paid = builder.total_tip_lamports()?;
+            if tip_paid > max_tip {
+                anyhow::bail!(
+                    "Transactions pay {} in Jito tips, over --max-tip {}",
+                    Lamports(tip_paid),
+                    Lamports(max_tip)
+                );
             }
 
-            if confirm {
+            if dry_run {
+                let payload = builder.build()?;
+                eprintln!("Dry run: {} encoded transaction(s), not submitted", encoded_txs.len());
+                match tip_account {
+                    Some(account) => eprintln!("Tip account: {account}"),
+                    None => warn!("Last transaction does not pay a Jito tip account"),
+                }
+                eprintln!("Tip paid:   {}", Lamports(tip_paid));
+                println!("{}", serde_json::to_string_pretty(&payload)?);
+            } else if confirm {
                 let timeout_duration = std::time::Duration::from_secs(timeout);
                 let result = builder.submit_and_confirm(timeout_duration).await?;
                 println!("{}", serde_json::to_string_pretty(&result)?);
@@ -192,29 +1192,237 @@ async fn main() -> Result<()> {
             }
         }
 
-        Commands::Monitor { signature, websocket } => {
+        Commands::BundleStatus {
+            bundle_id,
+            watch,
+            json,
+        } => {
+            let builder = JitoBundleBuilder::new(&config)?;
+
+            let status = loop {
+                let status = builder.check_status(&bundle_id).await?;
+                if !watch || !matches!(status, BundleStatus::Accepted { .. }) {
+                    break status;
+                }
+                debug!("Bundle {bundle_id} still pending...");
+                tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+            };
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&status)?);
+            } else {
+                match status {
+                    BundleStatus::Landed { bundle_id, slot } => {
+                        println!("Bundle {bundle_id} landed in slot {slot}")
+                    }
+                    BundleStatus::Accepted { bundle_id } => {
+                        println!("Bundle {bundle_id} accepted, not yet landed")
+                    }
+                    BundleStatus::Rejected { reason, code } => match code {
+                        Some(code) => println!("Bundle rejected ({code}): {reason}"),
+                        None => println!("Bundle rejected: {reason}"),
+                    },
+                    BundleStatus::Expired { bundle_id } => {
+                        println!("Bundle {bundle_id} expired or unknown to the block engine")
+                    }
+                }
+            }
+        }
+
+        Commands::DecodeTx {
+            encoded,
+            encoding,
+            json,
+        } => {
+            let tx = decode_transaction(encoded.trim(), encoding)?;
+            let decoded = DecodedTransaction::new(&tx)?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&decoded)?);
+            } else {
+                println!("Fee payer:        {}", decoded.fee_payer.as_deref().unwrap_or("(none)"));
+                println!("Recent blockhash: {}", decoded.recent_blockhash);
+                println!("Signatures:");
+                for sig in &decoded.signatures {
+                    println!("  {sig}");
+                }
+                println!("Instructions:");
+                for (i, ix) in decoded.instructions.iter().enumerate() {
+                    println!("  #{i} program {}", ix.program_id);
+                    for account in &ix.accounts {
+                        let flags = match (account.signer, account.writable) {
+                            (true, true) => " [signer, writable]",
+                            (true, false) => " [signer]",
+                            (false, true) => " [writable]",
+                            (false, false) => "",
+                        };
+                        println!("    {}{flags}", account.pubkey);
+                    }
+                    println!("    data: {}", ix.data);
+                }
+            }
+        }
+
+        Commands::Monitor {
+            signature,
+            websocket,
+            timeout,
+            json,
+            logs,
+            commitment,
+        } => {
             info!("Monitoring transaction: {signature}");
+            let sig: Signature = signature.parse()?;
+            let timeout = Duration::from_secs(timeout);
+            let commitment: CommitmentConfig = match commitment {
+                Some(commitment) => commitment.into(),
+                None => config.commitment.parse()?,
+            };
+            let client = config.rpc_client()?;
+            let start = Instant::now();
 
+            let mut status = None;
+            let mut reached = None;
+            let mut resolved = false;
             if websocket {
-                // TODO: implement WebSocket-based monitoring via signatureSubscribe
-                eprintln!("WebSocket monitoring not yet implemented, falling back to polling");
+                let ws_url = config.effective_ws_url();
+                match subscribe_signature_status(&ws_url, &sig, commitment, timeout) {
+                    Ok(ws_status) => {
+                        // The notification fires exactly at the requested level.
+                        if ws_status.is_some() {
+                            reached = Some(confirmation_status(commitment));
+                        }
+                        status = ws_status;
+                        resolved = true;
+                    }
+                    Err(e) => {
+                        warn!("WebSocket monitoring failed ({e}), falling back to polling");
+                    }
+                }
+            }
+
+            if !resolved {
+                let remaining = timeout.saturating_sub(start.elapsed());
+                (status, reached) =
+                    poll_signature_status(&client, &sig, commitment, remaining, !json).await?;
+            }
+
+            // Fees are charged and logs emitted for failed transactions too.
+            let details = if status.is_some() {
+                fetch_transaction_details(&client, &sig, commitment, logs)
+                    .await
+                    .unwrap_or_else(|e| {
+                        warn!("Could not fetch transaction details: {e}");
+                        None
+                    })
+            } else {
+                None
+            };
+
+            if json {
+                let mut report = MonitorReport::new(signature, status, reached, start.elapsed());
+                report.details = details;
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                match (status, &reached) {
+                    (Some(Ok(())), Some(reached)) => println!(
+                        "Transaction confirmed successfully ({})",
+                        confirmation_name(reached)
+                    ),
+                    (Some(Ok(())), None) => println!("Transaction confirmed successfully"),
+                    (Some(Err(e)), _) => println!("Transaction failed: {e}"),
+                    (None, Some(reached)) => println!(
+                        "Timed out after {}s: transaction reached {} but not {}",
+                        timeout.as_secs(),
+                        confirmation_name(reached),
+                        confirmation_name(&confirmation_status(commitment))
+                    ),
+                    (None, None) => println!(
+                        "Timed out after {}s: transaction not found or still pending",
+                        timeout.as_secs()
+                    ),
+                }
+
+                if let Some(details) = details {
+                    println!();
+                    println!("Slot:            {}", details.slot);
+                    println!("Fee:             {}", Lamports(details.fee_lamports));
+                    match details.compute_units_consumed {
+                        Some(units) => println!("Compute units:   {units}"),
+                        None => println!("Compute units:   unavailable"),
+                    }
+                    if let Some(log_lines) = details.logs {
+                        println!();
+                        println!("Program logs:");
+                        for line in log_lines {
+                            println!("  {line}");
+                        }
+                    }
+                }
             }
+        }
 
-            // Poll for transaction status using RPC
-            let client = solana_client::rpc_client::RpcClient::new(&config.rpc_url);
-            let sig: solana_sdk::signature::Signature = signature.parse()?;
+        Commands::Doctor { json } => {
+            let report = config.health_check().await;
 
-            let status = client.get_signature_status(&sig)?;
-            match status {
-                Some(Ok(())) => println!("Transaction confirmed successfully"),
-                Some(Err(e)) => println!("Transaction failed: {e}"),
-                None => println!("Transaction not found or still pending"),
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                println!(
+                    "{:<14} {:<8} {:>9}  Details",
+                    "Endpoint", "Status", "Latency"
+                );
+                for endpoint in report.endpoints() {
+                    let status = if endpoint.healthy { "ok" } else { "FAIL" };
+                    let latency = format!("{}ms", endpoint.latency_ms);
+                    let details = endpoint.error.as_deref().unwrap_or(&endpoint.url);
+                    println!("{:<14} {status:<8} {latency:>9}  {details}", endpoint.name);
+                }
             }
 
-            // TODO: add continuous polling with progress indicator
-            // TODO: display transaction details (fee, CU consumed, logs) on confirmation
+            if !report.all_healthy() {
+                anyhow::bail!("One or more endpoints are unreachable");
+            }
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_tx_optimizer::priority_fee::FeeBackend;
+
+    #[tokio::test]
+    async fn test_fetch_fee_estimate_reaches_quicknode() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::body_partial_json(
+                serde_json::json!({ "method": "qn_estimatePriorityFees" }),
+            ))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "result": {
+                    "per_compute_unit": {
+                        "low": 1_000, "medium": 2_000, "high": 3_000, "extreme": 9_000,
+                    },
+                },
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = Config {
+            rpc_url: server.uri(),
+            ..Config::default()
+        };
+        let estimator =
+            PriorityFeeEstimator::new(&config.rpc_url).with_backend(FeeBackend::QuickNode);
+        let (estimate, stats) = fetch_fee_estimate(estimator, &config, FeeStrategy::Fast, None)
+            .await
+            .unwrap();
+        assert_eq!(estimate.data_source, FeeDataSource::QuickNode);
+        assert_eq!(estimate.recommended_fee, 3_000);
+        assert!(stats.rpc_attempts >= 1);
+    }
+}
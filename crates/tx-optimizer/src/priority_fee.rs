@@ -1,12 +1,58 @@
-use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use solana_client::rpc_client::RpcClient;
-use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{
+    commitment_config::{CommitmentConfig, CommitmentLevel},
+    pubkey::Pubkey,
+};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tracing::{debug, info, warn};
 
+use crate::error::{Result, TxOptimizerError};
+
+/// Default time to wait for each RPC response, matching the block engine
+/// request timeout.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Most compute units a transaction may request.
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Heap every transaction gets without asking, in bytes.
+pub const MIN_HEAP_FRAME_BYTES: u32 = 32 * 1024;
+
+/// Largest heap frame a transaction may request, in bytes.
+pub const MAX_HEAP_FRAME_BYTES: u32 = 256 * 1024;
+
+/// Headroom [`estimate_cu_for_instructions`] adds to the simulated compute
+/// units, in percent.
+pub const CU_ESTIMATE_MARGIN_PERCENT: u64 = 10;
+
+/// Fewest non-zero samples a [scoped](PriorityFeeEstimator::with_scoped_accounts)
+/// estimate can rest on before the estimator warns that it is unreliable.
+pub const MIN_SCOPED_SAMPLES: usize = 5;
+
+/// Default number of accounts
+/// [`estimate_per_account`](PriorityFeeEstimator::estimate_per_account)
+/// fetches at once.
+pub const DEFAULT_PER_ACCOUNT_CONCURRENCY: usize = 4;
+
+/// Most recent outcomes [`PriorityFeeEstimator::record_outcome`] keeps.
+pub const LANDING_FEEDBACK_WINDOW: usize = 50;
+
+/// Fewest recorded outcomes before the
+/// [landing target](PriorityFeeEstimator::with_landing_target) moves the
+/// percentile.
+pub const MIN_LANDING_OUTCOMES: usize = 5;
+
 /// Fee strategy presets that map to different percentile targets.
 /// Users pick a strategy; the estimator translates it to the right fee level.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Serializes presets by name (`"Fast"`, or `"fast"` with the
+/// `serialize_v2` feature) and custom percentiles as `"custom:NN"`, and reads
+/// back anything [`FromStr`](std::str::FromStr) accepts, so older history
+/// logs still load.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum FeeStrategy {
     /// p25 — cheapest, may take longer to land
     Economy,
@@ -16,20 +62,91 @@ pub enum FeeStrategy {
     Fast,
     /// p90 — near-guaranteed fast inclusion
     Turbo,
+    /// Any percentile from 0 to 100, written `custom:NN`; a [`StrategyMap`]
+    /// does not remap it
+    Custom(u8),
 }
 
 impl FeeStrategy {
-    /// Returns the target percentile for this strategy.
+    /// Every preset, cheapest first.
+    pub const ALL: [FeeStrategy; 4] = [
+        FeeStrategy::Economy,
+        FeeStrategy::Standard,
+        FeeStrategy::Fast,
+        FeeStrategy::Turbo,
+    ];
+
+    /// Returns the default target percentile for this strategy; an
+    /// estimator's [`StrategyMap`] may override it.
     pub fn percentile(&self) -> usize {
         match self {
             FeeStrategy::Economy => 25,
             FeeStrategy::Standard => 50,
             FeeStrategy::Fast => 75,
             FeeStrategy::Turbo => 90,
+            FeeStrategy::Custom(pct) => *pct as usize,
+        }
+    }
+}
+
+impl std::str::FromStr for FeeStrategy {
+    type Err = TxOptimizerError;
+
+    /// Parse a preset name (`economy`, `standard`, `fast` or `turbo`) or a
+    /// custom percentile such as `custom:95`, ignoring case.
+    fn from_str(s: &str) -> Result<Self> {
+        let name = s.to_lowercase();
+        if let Some(pct) = name.strip_prefix("custom:") {
+            return match pct.trim().parse::<u8>() {
+                Ok(pct) if pct <= 100 => Ok(FeeStrategy::Custom(pct)),
+                _ => Err(TxOptimizerError::Config(format!(
+                    "Invalid custom strategy '{s}' (expected custom:NN with NN from 0 to 100)"
+                ))),
+            };
+        }
+        match name.as_str() {
+            "economy" => Ok(FeeStrategy::Economy),
+            "standard" => Ok(FeeStrategy::Standard),
+            "fast" => Ok(FeeStrategy::Fast),
+            "turbo" => Ok(FeeStrategy::Turbo),
+            _ => Err(TxOptimizerError::Config(format!(
+                "Unknown strategy '{s}'. Valid options: economy, standard, fast, turbo, custom:NN"
+            ))),
+        }
+    }
+}
+
+impl Serialize for FeeStrategy {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        let name = match self {
+            FeeStrategy::Economy => "Economy",
+            FeeStrategy::Standard => "Standard",
+            FeeStrategy::Fast => "Fast",
+            FeeStrategy::Turbo => "Turbo",
+            FeeStrategy::Custom(pct) => {
+                return serializer.collect_str(&format_args!("custom:{pct}"));
+            }
+        };
+        if cfg!(feature = "serialize_v2") {
+            serializer.serialize_str(&name.to_lowercase())
+        } else {
+            serializer.serialize_str(name)
         }
     }
 }
 
+impl<'de> Deserialize<'de> for FeeStrategy {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 impl std::fmt::Display for FeeStrategy {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -37,7 +154,95 @@ impl std::fmt::Display for FeeStrategy {
             FeeStrategy::Standard => write!(f, "Standard (p50)"),
             FeeStrategy::Fast => write!(f, "Fast (p75)"),
             FeeStrategy::Turbo => write!(f, "Turbo (p90)"),
+            FeeStrategy::Custom(pct) => write!(f, "Custom (p{pct})"),
+        }
+    }
+}
+
+/// Target percentile for each [`FeeStrategy`], so deployments can tune
+/// the presets to their observed landing behavior.
+///
+/// Defaults to [`FeeStrategy::percentile`]: 25, 50, 75 and 90. Loaded from
+/// [`Config::strategy_map`](crate::config::Config::strategy_map) and set with
+/// [`PriorityFeeEstimator::with_strategy_map`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StrategyMap {
+    pub economy: u8,
+    pub standard: u8,
+    pub fast: u8,
+    pub turbo: u8,
+}
+
+impl Default for StrategyMap {
+    fn default() -> Self {
+        Self {
+            economy: 25,
+            standard: 50,
+            fast: 75,
+            turbo: 90,
+        }
+    }
+}
+
+impl StrategyMap {
+    /// The percentile `strategy` draws its fee from.
+    pub fn percentile(&self, strategy: FeeStrategy) -> usize {
+        let pct = match strategy {
+            FeeStrategy::Economy => self.economy,
+            FeeStrategy::Standard => self.standard,
+            FeeStrategy::Fast => self.fast,
+            FeeStrategy::Turbo => self.turbo,
+            FeeStrategy::Custom(pct) => pct,
+        };
+        pct as usize
+    }
+
+    /// Check that every percentile is within `0..=100` and that none is
+    /// below the one of a cheaper strategy.
+    pub fn validate(&self) -> Result<()> {
+        let percentiles = FeeStrategy::ALL.map(|strategy| (strategy, self.percentile(strategy)));
+        if let Some((strategy, pct)) = percentiles.iter().find(|(_, pct)| *pct > 100) {
+            return Err(TxOptimizerError::Config(format!(
+                "Invalid percentile {pct} for {strategy:?} (expected 0 to 100)"
+            )));
+        }
+        if let Some(pair) = percentiles.windows(2).find(|pair| pair[1].1 < pair[0].1) {
+            let ((cheaper, low), (strategy, pct)) = (pair[0], pair[1]);
+            return Err(TxOptimizerError::Config(format!(
+                "Percentile {pct} for {strategy:?} is below {low} for {cheaper:?}"
+            )));
         }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for StrategyMap {
+    type Err = TxOptimizerError;
+
+    /// Parse four comma-separated percentiles, cheapest strategy first,
+    /// e.g. `25,50,80,95`. The result is [validated](Self::validate).
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            TxOptimizerError::Config(format!(
+                "Invalid strategy map '{s}' (expected four comma-separated percentiles, \
+                 e.g. 25,50,75,90)"
+            ))
+        };
+        let percentiles = s
+            .split(',')
+            .map(|pct| pct.trim().parse().map_err(|_| invalid()))
+            .collect::<Result<Vec<u8>>>()?;
+        let [economy, standard, fast, turbo] = percentiles[..] else {
+            return Err(invalid());
+        };
+        let map = Self {
+            economy,
+            standard,
+            fast,
+            turbo,
+        };
+        map.validate()?;
+        Ok(map)
     }
 }
 
@@ -52,229 +257,2790 @@ pub struct FeeEstimate {
     pub slots_sampled: usize,
     /// Fee percentile breakdown for transparency
     pub percentiles: FeePercentiles,
+    /// Commitment level the samples were capped at
+    pub commitment: CommitmentLevel,
+    /// Whether `recommended_fee` was clamped to the estimator's
+    /// [sanity bounds](PriorityFeeEstimator::with_sanity_bounds)
+    #[serde(default)]
+    pub sanity_clamped: bool,
+    /// Percentile the fee was effectively drawn from, when it was
+    /// [blended](PriorityFeeEstimator::estimate_blend) from two strategies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_percentile: Option<f64>,
+    /// Number of slots in the merged window, when the estimator keeps a
+    /// [rolling buffer](PriorityFeeEstimator::with_rolling_buffer)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_slots: Option<usize>,
+    /// Whether `recommended_fee` comes from samples or is the default
+    /// fallback, and why
+    #[serde(default)]
+    pub data_source: FeeDataSource,
+    /// Whether samples were limited to transactions touching
+    /// [specific accounts](PriorityFeeEstimator::with_scoped_accounts)
+    #[serde(default)]
+    pub scoped: bool,
+    /// The accounts samples were limited to, base58-encoded; empty when
+    /// unscoped
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scoped_accounts: Vec<String>,
+    /// The [fallback step](PriorityFeeEstimator::with_fallback_chain) that
+    /// supplied `recommended_fee` because the samples were empty;
+    /// `data_source` says why they were
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_used: Option<FallbackStep>,
+}
+
+/// One step of an estimator's
+/// [fallback chain](PriorityFeeEstimator::with_fallback_chain).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FallbackStep {
+    /// Estimate from network-wide samples instead of the scoped accounts'.
+    /// Produces nothing for an unscoped estimator or when those samples are
+    /// empty too.
+    Unscoped,
+    /// A fixed fee in microlamports/CU
+    Static(u64),
+    /// The network-wide estimate raised to at least this fee, or the fee
+    /// itself when there are no network-wide samples either
+    MaxOf(u64),
+}
+
+/// Where a [`FeeEstimate`]'s recommended fee came from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeDataSource {
+    /// Percentiles of the non-zero samples
+    #[default]
+    Sampled,
+    /// Every sample was zero, so nobody is paying priority fees and a low fee
+    /// should land. The recommended fee is still the default fallback.
+    AllZeroFiltered,
+    /// The RPC node returned no samples at all; the recommended fee is the
+    /// default fallback
+    Fallback,
+    /// QuickNode's `qn_estimatePriorityFees` add-on; see
+    /// [`FeeBackend::QuickNode`]
+    QuickNode,
+}
+
+/// Where [`PriorityFeeEstimator::estimate`] gets its recommended fee.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FeeBackend {
+    /// Percentiles of `getRecentPrioritizationFees` samples
+    #[default]
+    RecentFees,
+    /// QuickNode's `qn_estimatePriorityFees` add-on, which returns
+    /// ready-made fee levels. Falls back to `RecentFees` on nodes that don't
+    /// offer the method.
+    QuickNode,
+}
+
+/// Blocks `qn_estimatePriorityFees` is asked to sample, matching the
+/// ~150-slot window of `getRecentPrioritizationFees` as closely as the
+/// add-on's block count allows.
+const QUICKNODE_LAST_N_BLOCKS: usize = 100;
+
+/// Per-compute-unit price levels from `qn_estimatePriorityFees`, in
+/// microlamports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuickNodeFeeLevels {
+    pub low: u64,
+    pub medium: u64,
+    pub high: u64,
+    pub extreme: u64,
+}
+
+impl QuickNodeFeeLevels {
+    /// The level matching `strategy`: Economy is `low`, Standard `medium`,
+    /// Fast `high` and Turbo `extreme`. A custom percentile gets the level of
+    /// the cheapest preset whose default percentile is at least as high.
+    pub fn for_strategy(&self, strategy: FeeStrategy) -> u64 {
+        match strategy {
+            FeeStrategy::Economy => self.low,
+            FeeStrategy::Standard => self.medium,
+            FeeStrategy::Fast => self.high,
+            FeeStrategy::Turbo => self.extreme,
+            FeeStrategy::Custom(pct) => match pct {
+                0..=25 => self.low,
+                26..=50 => self.medium,
+                51..=75 => self.high,
+                _ => self.extreme,
+            },
+        }
+    }
 }
 
 /// Breakdown of fee percentiles from recent slots.
+///
+/// The optional tail percentiles are filled in by
+/// [`PriorityFeeEstimator::estimate`] whenever there are samples, and are
+/// absent from estimates serialized before they existed.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeePercentiles {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p10: Option<u64>,
     pub p25: u64,
     pub p50: u64,
     pub p75: u64,
     pub p90: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p95: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p99: Option<u64>,
     pub max: u64,
 }
 
+impl FeePercentiles {
+    /// Look up a percentile by number; `100` is the max.
+    ///
+    /// Returns `None` for percentiles that aren't part of the breakdown
+    /// (e.g. `60`) or weren't computed.
+    pub fn get(&self, pct: u8) -> Option<u64> {
+        match pct {
+            10 => self.p10,
+            25 => Some(self.p25),
+            50 => Some(self.p50),
+            75 => Some(self.p75),
+            90 => Some(self.p90),
+            95 => self.p95,
+            99 => self.p99,
+            100 => Some(self.max),
+            _ => None,
+        }
+    }
+}
+
+/// Percentiles [`FeeEstimate::diff`] compares, when both estimates have them.
+const DIFF_PERCENTILES: [u8; 8] = [10, 25, 50, 75, 90, 95, 99, 100];
+
+impl FeeEstimate {
+    /// Compare this estimate against `other`, e.g. the same strategy
+    /// estimated through a different RPC provider.
+    ///
+    /// Deltas run from this estimate to `other`, so a positive delta means
+    /// `other` recommends more.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use solana_tx_optimizer::priority_fee::{FeeStrategy, PriorityFeeEstimator};
+    ///
+    /// let a = PriorityFeeEstimator::new("https://rpc-a.example").estimate(FeeStrategy::Fast)?;
+    /// let b = PriorityFeeEstimator::new("https://rpc-b.example").estimate(FeeStrategy::Fast)?;
+    /// let diff = a.diff(&b);
+    /// println!("B recommends {:+} microlamports/CU", diff.recommended_fee.absolute);
+    /// # Ok::<(), solana_tx_optimizer::error::TxOptimizerError>(())
+    /// ```
+    pub fn diff(&self, other: &FeeEstimate) -> FeeDiff {
+        let percentiles = DIFF_PERCENTILES
+            .iter()
+            .filter_map(|&pct| {
+                let from = self.percentiles.get(pct)?;
+                let to = other.percentiles.get(pct)?;
+                Some((pct, FeeDelta::new(from, to)))
+            })
+            .collect();
+        let more_samples = match self.slots_sampled.cmp(&other.slots_sampled) {
+            std::cmp::Ordering::Greater => Some(DiffSide::This),
+            std::cmp::Ordering::Less => Some(DiffSide::Other),
+            std::cmp::Ordering::Equal => None,
+        };
+        FeeDiff {
+            recommended_fee: FeeDelta::new(self.recommended_fee, other.recommended_fee),
+            percentiles,
+            more_samples,
+        }
+    }
+}
+
+/// Result of [`FeeEstimate::diff`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeDiff {
+    pub recommended_fee: FeeDelta,
+    /// `(percentile, delta)` for each percentile both estimates have, lowest
+    /// first; `100` is the max
+    pub percentiles: Vec<(u8, FeeDelta)>,
+    /// Which estimate sampled more slots; `None` on a tie
+    pub more_samples: Option<DiffSide>,
+}
+
+/// Change in one fee level between two estimates, in microlamports/CU.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FeeDelta {
+    pub from: u64,
+    pub to: u64,
+    /// `to - from`
+    pub absolute: i128,
+    /// `absolute` as a percentage of `from`; `None` when `from` is zero
+    pub percent: Option<f64>,
+}
+
+impl FeeDelta {
+    fn new(from: u64, to: u64) -> Self {
+        let absolute = i128::from(to) - i128::from(from);
+        Self {
+            from,
+            to,
+            absolute,
+            percent: (from > 0).then(|| absolute as f64 / from as f64 * 100.0),
+        }
+    }
+}
+
+/// One side of a [`FeeDiff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffSide {
+    /// The estimate `diff` was called on
+    This,
+    /// The estimate passed to `diff`
+    Other,
+}
+
 /// Raw response item from `getRecentPrioritizationFees` RPC method.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PrioritizationFeeEntry {
     pub slot: u64,
     #[serde(rename = "prioritizationFee")]
     pub prioritization_fee: u64,
 }
 
-/// Estimates optimal priority fees by sampling recent on-chain data.
+/// RPC calls the estimator depends on.
 ///
-/// Uses the `getRecentPrioritizationFees` RPC method to collect fee data
-/// from the last 150 slots, then computes percentiles to recommend a fee
-/// based on the chosen [`FeeStrategy`].
-pub struct PriorityFeeEstimator {
-    rpc_client: RpcClient,
-    /// Optional: scope fee estimation to specific accounts (e.g., program IDs).
-    /// When provided, only fees from transactions touching these accounts are considered.
-    scoped_accounts: Vec<Pubkey>,
+/// [`HttpFeeRpc`] is the JSON-RPC implementation used by default; tests and
+/// callers with their own transport can supply another through
+/// [`PriorityFeeEstimator::with_rpc`].
+pub trait FeeRpc: Send + Sync {
+    /// `getRecentPrioritizationFees`, scoped to `accounts` when non-empty.
+    fn recent_fees(&self, accounts: &[Pubkey]) -> Result<Vec<PrioritizationFeeEntry>>;
+
+    /// `getSlot` at `commitment`.
+    fn slot(&self, commitment: CommitmentConfig) -> Result<u64>;
+
+    /// QuickNode's `qn_estimatePriorityFees`, scoped to `account` if given.
+    ///
+    /// Returns `Ok(None)` when the node doesn't offer the method, which is
+    /// what implementations without it report by default.
+    fn quicknode_fee_levels(&self, account: Option<&Pubkey>) -> Result<Option<QuickNodeFeeLevels>> {
+        let _ = account;
+        Ok(None)
+    }
 }
 
-impl PriorityFeeEstimator {
-    /// Create a new estimator targeting the given RPC endpoint.
+/// [`FeeRpc`] over JSON-RPC HTTP requests.
+///
+/// Uses reqwest directly rather than `RpcClient`, since solana-client
+/// doesn't expose getRecentPrioritizationFees as a typed method yet and every
+/// request must go through the configured HTTP client.
+#[derive(Clone)]
+pub struct HttpFeeRpc {
+    rpc_url: String,
+    /// Client carrying any custom headers and proxy settings. When unset a
+    /// default client is created per call, which keeps this safe to
+    /// construct and drop from async code.
+    http_client: Option<reqwest::blocking::Client>,
+    /// Limit on each request, including reading the response body
+    request_timeout: Duration,
+}
+
+impl HttpFeeRpc {
+    /// Send requests to `rpc_url` with a default HTTP client.
     pub fn new(rpc_url: &str) -> Self {
         Self {
-            rpc_client: RpcClient::new(rpc_url.to_string()),
-            scoped_accounts: Vec::new(),
+            rpc_url: rpc_url.to_string(),
+            http_client: None,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
         }
     }
 
-    /// Scope fee estimation to transactions involving specific accounts.
-    /// This is useful for getting more accurate fees for a particular program
-    /// (e.g., pass the Jupiter program ID to get swap-specific fee data).
-    pub fn with_scoped_accounts(mut self, accounts: Vec<Pubkey>) -> Self {
-        self.scoped_accounts = accounts;
+    /// Send requests through `client`.
+    pub fn with_http_client(mut self, client: reqwest::blocking::Client) -> Self {
+        self.http_client = Some(client);
         self
     }
 
-    /// Fetch recent prioritization fees from the RPC node.
-    ///
-    /// Calls `getRecentPrioritizationFees` which returns fee data from
-    /// the last 150 confirmed slots.
-    fn fetch_recent_fees(&self) -> Result<Vec<u64>> {
-        // Build the RPC request params — if scoped_accounts is non-empty,
-        // pass them to filter fees by relevant transactions.
-        let params = if self.scoped_accounts.is_empty() {
-            serde_json::json!([])
-        } else {
-            let accounts: Vec<String> = self
-                .scoped_accounts
-                .iter()
-                .map(|pk| pk.to_string())
-                .collect();
-            serde_json::json!([accounts])
-        };
-
-        debug!("Fetching recent prioritization fees with params: {params}");
+    /// Fail requests with [`TxOptimizerError::Timeout`] after `timeout`
+    /// instead of [`DEFAULT_REQUEST_TIMEOUT`].
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
 
-        // Use reqwest to call RPC directly since solana-client doesn't expose
-        // getRecentPrioritizationFees as a typed method yet.
+    /// Send a JSON-RPC request and return the raw response.
+    fn request(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
         let body = serde_json::json!({
             "jsonrpc": "2.0",
             "id": 1,
-            "method": "getRecentPrioritizationFees",
+            "method": method,
             "params": params,
         });
 
-        let response: serde_json::Value = reqwest::blocking::Client::new()
-            .post(self.rpc_client.url())
+        let timed_out = |e: reqwest::Error| {
+            if e.is_timeout() {
+                TxOptimizerError::Timeout {
+                    method: method.to_string(),
+                    timeout: self.request_timeout,
+                }
+            } else {
+                e.into()
+            }
+        };
+        self.http_client
+            .clone()
+            .unwrap_or_default()
+            .post(&self.rpc_url)
             .json(&body)
+            .timeout(self.request_timeout)
             .send()
-            .context("Failed to call getRecentPrioritizationFees")?
+            .map_err(timed_out)?
             .json()
-            .context("Failed to parse RPC response")?;
-
-        let entries: Vec<PrioritizationFeeEntry> =
-            serde_json::from_value(response["result"].clone())
-                .context("Failed to deserialize fee entries")?;
-
-        // Filter out zero-fee entries (slots with no priority transactions)
-        let fees: Vec<u64> = entries
-            .into_iter()
-            .map(|e| e.prioritization_fee)
-            .filter(|&fee| fee > 0)
-            .collect();
-
-        info!("Collected {} non-zero fee samples", fees.len());
-        Ok(fees)
+            .map_err(timed_out)
     }
+}
 
-    /// Compute percentile value from a sorted list of fees.
-    fn percentile(sorted_fees: &[u64], pct: usize) -> u64 {
-        if sorted_fees.is_empty() {
-            return 0;
-        }
-        let index = (pct as f64 / 100.0 * (sorted_fees.len() - 1) as f64).round() as usize;
-        sorted_fees[index.min(sorted_fees.len() - 1)]
+impl FeeRpc for HttpFeeRpc {
+    fn recent_fees(&self, accounts: &[Pubkey]) -> Result<Vec<PrioritizationFeeEntry>> {
+        let params = fee_request_params(accounts);
+        debug!("Fetching recent prioritization fees with params: {params}");
+        parse_fee_response(self.request("getRecentPrioritizationFees", params)?)
     }
 
-    /// Estimate the optimal priority fee for the given strategy.
-    ///
-    /// # Example
-    /// ```no_run
-    /// use solana_tx_optimizer::priority_fee::{PriorityFeeEstimator, FeeStrategy};
-    ///
-    /// let estimator = PriorityFeeEstimator::new("https://api.mainnet-beta.solana.com");
-    /// let estimate = estimator.estimate(FeeStrategy::Fast).unwrap();
-    /// println!("Recommended fee: {} microlamports/CU", estimate.recommended_fee);
-    /// ```
-    pub fn estimate(&self, strategy: FeeStrategy) -> Result<FeeEstimate> {
-        let mut fees = self.fetch_recent_fees()?;
+    fn slot(&self, commitment: CommitmentConfig) -> Result<u64> {
+        parse_slot_response(self.request("getSlot", serde_json::json!([commitment]))?)
+    }
 
-        if fees.is_empty() {
-            warn!("No recent priority fee data found, using default fallback");
-            return Ok(FeeEstimate {
-                recommended_fee: crate::config::DEFAULT_PRIORITY_FEE_MICROLAMPORTS,
-                strategy,
-                slots_sampled: 0,
-                percentiles: FeePercentiles {
-                    p25: 0,
-                    p50: 0,
-                    p75: 0,
-                    p90: 0,
-                    max: 0,
-                },
-            });
+    fn quicknode_fee_levels(&self, account: Option<&Pubkey>) -> Result<Option<QuickNodeFeeLevels>> {
+        let mut params = serde_json::json!({
+            "last_n_blocks": QUICKNODE_LAST_N_BLOCKS,
+            "api_version": 2,
+        });
+        if let Some(account) = account {
+            params["account"] = account.to_string().into();
         }
+        parse_quicknode_response(self.request("qn_estimatePriorityFees", params)?)
+    }
+}
 
-        fees.sort_unstable();
-        let slots_sampled = fees.len();
+/// Build the `getRecentPrioritizationFees` params — if `accounts` is
+/// non-empty, pass them to filter fees by relevant transactions.
+fn fee_request_params(accounts: &[Pubkey]) -> serde_json::Value {
+    if accounts.is_empty() {
+        serde_json::json!([])
+    } else {
+        let accounts: Vec<String> = accounts.iter().map(|pk| pk.to_string()).collect();
+        serde_json::json!([accounts])
+    }
+}
 
-        let percentiles = FeePercentiles {
-            p25: Self::percentile(&fees, 25),
-            p50: Self::percentile(&fees, 50),
-            p75: Self::percentile(&fees, 75),
-            p90: Self::percentile(&fees, 90),
-            max: *fees.last().unwrap_or(&0),
-        };
+/// Estimates optimal priority fees by sampling recent on-chain data.
+///
+/// Uses the `getRecentPrioritizationFees` RPC method to collect fee data
+/// from the last 150 slots, then computes percentiles to recommend a fee
+/// based on the chosen [`FeeStrategy`].
+#[derive(Clone)]
+pub struct PriorityFeeEstimator {
+    rpc_url: String,
+    /// Source of fee samples and slots; [`HttpFeeRpc`] on `rpc_url` by default
+    rpc: Arc<dyn FeeRpc>,
+    /// Optional: scope fee estimation to specific accounts (e.g., program IDs).
+    /// When provided, only fees from transactions touching these accounts are considered.
+    scoped_accounts: Vec<Pubkey>,
+    /// Samples from slots newer than this commitment's slot are discarded
+    commitment: CommitmentConfig,
+    /// Upper bound for escalated fees, in microlamports/CU
+    max_priority_fee: u64,
+    /// Inclusive `(min, max)` band for recommended fees; off when `None`
+    sanity_bounds: Option<(u64, u64)>,
+    /// Samples kept across estimates; shared between clones
+    rolling: Option<Arc<Mutex<RollingBuffer>>>,
+    /// Keep zero-fee slots in the percentile samples
+    include_zero_fees: bool,
+    /// Where `estimate` gets its recommended fee
+    backend: FeeBackend,
+    /// Most accounts `estimate_per_account` fetches at once
+    per_account_concurrency: usize,
+    /// Tried in order when the samples are empty
+    fallback_chain: Vec<FallbackStep>,
+    /// Landing rate `estimate` steers toward, with the outcomes it is
+    /// measured on; shared between clones. Off when `None`
+    landing: Option<Arc<Mutex<LandingFeedback>>>,
+    /// Percentile each strategy draws its fee from
+    strategy_map: StrategyMap,
+    /// Client and timeout the default [`HttpFeeRpc`] is built with
+    http_client: Option<reqwest::blocking::Client>,
+    request_timeout: Duration,
+}
 
-        let recommended_fee = Self::percentile(&fees, strategy.percentile());
+/// Result of [`PriorityFeeEstimator::estimate_per_account`].
+#[derive(Debug, Clone, Default)]
+pub struct PerAccountEstimates {
+    /// Estimates for the accounts that could be fetched, in request order
+    pub estimates: Vec<(Pubkey, FeeEstimate)>,
+    /// Accounts whose estimate failed, with the error, in request order
+    pub errors: Vec<(Pubkey, String)>,
+}
 
-        info!(
-            strategy = %strategy,
-            recommended_fee,
-            slots_sampled,
-            "Fee estimation complete"
-        );
+/// Sorted samples an estimate is drawn from, after the fallback chain ran.
+struct FeeSamples {
+    sorted: Vec<u64>,
+    source: FeeDataSource,
+    /// Lowest fee to recommend; the fee itself when `sorted` is empty
+    floor: u64,
+    fallback_used: Option<FallbackStep>,
+}
 
-        Ok(FeeEstimate {
-            recommended_fee,
-            strategy,
-            slots_sampled,
-            percentiles,
-        })
+impl FeeSamples {
+    /// Set the recommended fee, source and fallback step on an estimate
+    /// computed from `sorted`.
+    fn apply(&self, estimate: &mut FeeEstimate) {
+        estimate.recommended_fee = if self.sorted.is_empty() {
+            self.floor
+        } else {
+            estimate.recommended_fee.max(self.floor)
+        };
+        estimate.data_source = self.source;
+        estimate.fallback_used = self.fallback_used;
     }
+}
 
-    /// Estimate fee and apply a multiplier for extra safety margin.
-    /// Useful during network congestion where fees spike rapidly.
-    pub fn estimate_with_buffer(
-        &self,
-        strategy: FeeStrategy,
-        buffer_multiplier: f64,
-    ) -> Result<FeeEstimate> {
-        let mut estimate = self.estimate(strategy)?;
-        estimate.recommended_fee = (estimate.recommended_fee as f64 * buffer_multiplier) as u64;
-        debug!(
-            "Applied {}x buffer: {} -> {} microlamports/CU",
-            buffer_multiplier,
-            estimate.recommended_fee as f64 / buffer_multiplier,
-            estimate.recommended_fee
-        );
-        Ok(estimate)
-    }
+/// Fee samples retained across estimates, keyed by slot.
+struct RollingBuffer {
+    capacity: usize,
+    fees_by_slot: BTreeMap<u64, u64>,
 }
 
-/// Build a `SetComputeUnitPrice` instruction for the given fee.
-///
-/// This is the instruction you prepend to your transaction to set priority fees.
-pub fn build_priority_fee_instruction(microlamports_per_cu: u64) -> solana_sdk::instruction::Instruction {
-    solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(
-        microlamports_per_cu,
-    )
+impl RollingBuffer {
+    /// Merge `entries` into the window, keeping only the newest `capacity`
+    /// slots, and return the fees of every slot in the window.
+    ///
+    /// A slot seen again replaces its earlier sample instead of counting twice.
+    fn merge(&mut self, entries: Vec<PrioritizationFeeEntry>) -> Vec<u64> {
+        for entry in entries {
+            self.fees_by_slot
+                .insert(entry.slot, entry.prioritization_fee);
+        }
+        while self.fees_by_slot.len() > self.capacity {
+            self.fees_by_slot.pop_first();
+        }
+        self.fees_by_slot.values().copied().collect()
+    }
 }
 
-/// Build a `SetComputeUnitLimit` instruction.
-///
-/// Setting an accurate CU limit reduces wasted fees since priority fee = CU_price * CU_consumed.
-pub fn build_compute_unit_limit_instruction(units: u32) -> solana_sdk::instruction::Instruction {
-    solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(units)
+/// Whether recent transactions sent at estimated fees landed, kept for
+/// [`PriorityFeeEstimator::with_landing_target`].
+struct LandingFeedback {
+    /// Landing rate to steer toward, within `(0, 1]`
+    target: f64,
+    /// Whether each transaction landed, oldest first, at most
+    /// [`LANDING_FEEDBACK_WINDOW`]
+    outcomes: VecDeque<bool>,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl LandingFeedback {
+    fn record(&mut self, landed: bool) {
+        self.outcomes.push_back(landed);
+        while self.outcomes.len() > LANDING_FEEDBACK_WINDOW {
+            self.outcomes.pop_front();
+        }
+    }
 
-    #[test]
-    fn test_percentile_calculation() {
-        let fees = vec![100, 200, 300, 400, 500, 600, 700, 800, 900, 1000];
-        assert_eq!(PriorityFeeEstimator::percentile(&fees, 50), 600);
-        assert_eq!(PriorityFeeEstimator::percentile(&fees, 90), 1000);
-        assert_eq!(PriorityFeeEstimator::percentile(&fees, 0), 100);
+    /// Share of recorded outcomes that landed, or `None` with fewer than
+    /// [`MIN_LANDING_OUTCOMES`].
+    fn landing_rate(&self) -> Option<f64> {
+        if self.outcomes.len() < MIN_LANDING_OUTCOMES {
+            return None;
+        }
+        let landed = self.outcomes.iter().filter(|&&landed| landed).count();
+        Some(landed as f64 / self.outcomes.len() as f64)
     }
 
-    #[test]
-    fn test_percentile_empty() {
-        let fees: Vec<u64> = vec![];
-        assert_eq!(PriorityFeeEstimator::percentile(&fees, 50), 0);
+    /// `base` shifted by the gap between target and landing rate, one
+    /// percentile point per percent, within p1 to p99.
+    fn adjusted_percentile(&self, base: usize) -> Option<f64> {
+        let rate = self.landing_rate()?;
+        Some((base as f64 + (self.target - rate) * 100.0).clamp(1.0, 99.0))
     }
+}
 
-    #[test]
-    fn test_fee_strategy_display() {
-        assert_eq!(FeeStrategy::Turbo.to_string(), "Turbo (p90)");
-        assert_eq!(FeeStrategy::Economy.percentile(), 25);
+impl PriorityFeeEstimator {
+    /// Create a new estimator targeting the given RPC endpoint.
+    pub fn new(rpc_url: &str) -> Self {
+        Self {
+            rpc_url: rpc_url.to_string(),
+            rpc: Arc::new(HttpFeeRpc::new(rpc_url)),
+            scoped_accounts: Vec::new(),
+            // Matches the `Config::default()` commitment
+            commitment: CommitmentConfig::confirmed(),
+            max_priority_fee: crate::config::DEFAULT_MAX_PRIORITY_FEE_MICROLAMPORTS,
+            sanity_bounds: None,
+            rolling: None,
+            include_zero_fees: false,
+            backend: FeeBackend::RecentFees,
+            per_account_concurrency: DEFAULT_PER_ACCOUNT_CONCURRENCY,
+            fallback_chain: vec![FallbackStep::Static(
+                crate::config::DEFAULT_PRIORITY_FEE_MICROLAMPORTS,
+            )],
+            landing: None,
+            strategy_map: StrategyMap::default(),
+            http_client: None,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+        }
+    }
+
+    /// Clamp recommended fees to `[min, max]` microlamports/CU.
+    ///
+    /// Guards against a single bad sample (e.g. from a misbehaving RPC node)
+    /// producing a wildly wrong fee. A clamped estimate logs a warning and
+    /// has [`FeeEstimate::sanity_clamped`] set. Off by default.
+    pub fn with_sanity_bounds(mut self, min: u64, max: u64) -> Result<Self> {
+        if min > max {
+            return Err(TxOptimizerError::Config(format!(
+                "Invalid fee sanity bounds: min {min} is greater than max {max}"
+            )));
+        }
+        self.sanity_bounds = Some((min, max));
+        Ok(self)
+    }
+
+    /// Keep samples from up to `capacity` slots across successive estimates
+    /// and compute percentiles over the merged window.
+    ///
+    /// `getRecentPrioritizationFees` only covers the last 150 slots, so a
+    /// larger capacity smooths estimates over a longer period when the
+    /// estimator is called repeatedly. Samples are deduplicated by slot, and
+    /// the oldest slots are evicted once the window is full. Estimates
+    /// report the window size in [`FeeEstimate::window_slots`]. Clones of
+    /// the estimator share the buffer.
+    ///
+    /// Fails with [`TxOptimizerError::Config`] if `capacity` is zero.
+    pub fn with_rolling_buffer(mut self, capacity: usize) -> Result<Self> {
+        if capacity == 0 {
+            return Err(TxOptimizerError::Config(
+                "Rolling buffer capacity must be at least 1 slot".to_string(),
+            ));
+        }
+        self.rolling = Some(Arc::new(Mutex::new(RollingBuffer {
+            capacity,
+            fees_by_slot: BTreeMap::new(),
+        })));
+        Ok(self)
+    }
+
+    /// Fetch at most `concurrency` accounts at once in
+    /// [`estimate_per_account`](Self::estimate_per_account). Defaults to
+    /// [`DEFAULT_PER_ACCOUNT_CONCURRENCY`].
+    ///
+    /// Fails with [`TxOptimizerError::Config`] if `concurrency` is zero.
+    pub fn with_per_account_concurrency(mut self, concurrency: usize) -> Result<Self> {
+        if concurrency == 0 {
+            return Err(TxOptimizerError::Config(
+                "Per-account concurrency must be at least 1".to_string(),
+            ));
+        }
+        self.per_account_concurrency = concurrency;
+        Ok(self)
+    }
+
+    /// Decide the fee when there are no samples to estimate from by trying
+    /// each step of `chain` in order, recording the one that produced a fee
+    /// in [`FeeEstimate::fallback_used`].
+    ///
+    /// The default chain is `[Static(DEFAULT_PRIORITY_FEE_MICROLAMPORTS)]`,
+    /// the fixed default fee. A step whose RPC request fails is skipped with
+    /// a warning, and if no step produces a fee the default one is used
+    /// anyway. Applies to sampled estimates, not to the
+    /// [QuickNode backend](FeeBackend::QuickNode).
+    pub fn with_fallback_chain(mut self, chain: Vec<FallbackStep>) -> Self {
+        self.fallback_chain = chain;
+        self
+    }
+
+    /// Adapt [`estimate`](Self::estimate) to how often transactions sent at
+    /// its fees actually land, aiming for `rate` (e.g. `0.9`).
+    ///
+    /// Report each outcome with [`record_outcome`](Self::record_outcome).
+    /// Once [`MIN_LANDING_OUTCOMES`] are recorded, the strategy's percentile
+    /// moves up one point for every percent the landing rate over the last
+    /// [`LANDING_FEEDBACK_WINDOW`] outcomes falls short of `rate`, and down
+    /// the same way when more land than needed, staying within p1 to p99.
+    /// The percentile used is reported in
+    /// [`FeeEstimate::effective_percentile`]. Everything stays client-side;
+    /// clones of the estimator share the outcomes. Off by default, and not
+    /// applied to the [QuickNode backend](FeeBackend::QuickNode) or fallback
+    /// fees.
+    ///
+    /// Fails with [`TxOptimizerError::Config`] unless `rate` is above 0 and
+    /// at most 1.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use solana_tx_optimizer::priority_fee::{FeeStrategy, PriorityFeeEstimator};
+    ///
+    /// let estimator = PriorityFeeEstimator::new("https://api.mainnet-beta.solana.com")
+    ///     .with_landing_target(0.9)?;
+    /// let estimate = estimator.estimate(FeeStrategy::Standard)?;
+    /// // ... send a transaction at estimate.recommended_fee ...
+    /// estimator.record_outcome(estimate.recommended_fee, true);
+    /// # Ok::<(), solana_tx_optimizer::error::TxOptimizerError>(())
+    /// ```
+    pub fn with_landing_target(mut self, rate: f64) -> Result<Self> {
+        if !(rate > 0.0 && rate <= 1.0) {
+            return Err(TxOptimizerError::Config(format!(
+                "Invalid landing target {rate} (expected above 0 and at most 1)"
+            )));
+        }
+        self.landing = Some(Arc::new(Mutex::new(LandingFeedback {
+            target: rate,
+            outcomes: VecDeque::new(),
+        })));
+        Ok(self)
+    }
+
+    /// Draw each strategy's fee from the percentile in `map` rather than
+    /// the defaults of [`FeeStrategy::percentile`]. Not applied to the
+    /// [QuickNode backend](FeeBackend::QuickNode), whose levels are fixed.
+    ///
+    /// Fails with [`TxOptimizerError::Config`] unless `map`
+    /// [validates](StrategyMap::validate).
+    pub fn with_strategy_map(mut self, map: StrategyMap) -> Result<Self> {
+        map.validate()?;
+        self.strategy_map = map;
+        Ok(self)
+    }
+
+    /// Record whether a transaction sent at `fee` microlamports/CU landed.
+    ///
+    /// Ignored unless a [landing target](Self::with_landing_target) is set.
+    pub fn record_outcome(&self, fee: u64, landed: bool) {
+        let Some(feedback) = &self.landing else {
+            debug!("No landing target set, ignoring outcome at {fee} microlamports/CU");
+            return;
+        };
+        debug!(landed, "Recorded outcome at {fee} microlamports/CU");
+        feedback.lock().unwrap().record(landed);
+    }
+
+    /// Share of recently recorded outcomes that landed, once there are at
+    /// least [`MIN_LANDING_OUTCOMES`]; `None` before that or without a
+    /// [landing target](Self::with_landing_target).
+    pub fn landing_rate(&self) -> Option<f64> {
+        self.landing.as_ref()?.lock().unwrap().landing_rate()
+    }
+
+    /// Redraw a sampled estimate's fee from the percentile the landing
+    /// feedback calls for, if any.
+    fn apply_landing_feedback(&self, sorted_fees: &[u64], estimate: &mut FeeEstimate) {
+        let Some(feedback) = &self.landing else {
+            return;
+        };
+        if sorted_fees.is_empty() {
+            return;
+        }
+        let feedback = feedback.lock().unwrap();
+        let base = self.strategy_map.percentile(estimate.strategy);
+        let Some(pct) = feedback.adjusted_percentile(base) else {
+            return;
+        };
+        estimate.recommended_fee = Self::percentile(sorted_fees, pct.round() as usize);
+        estimate.effective_percentile = Some(pct);
+        debug!(
+            landing_rate = feedback.landing_rate(),
+            target = feedback.target,
+            "Moved p{base} to p{pct:.0} for landing feedback"
+        );
+    }
+
+    /// Record the account scope samples were fetched with on `estimate`.
+    fn record_scope(&self, estimate: &mut FeeEstimate) {
+        estimate.scoped = !self.scoped_accounts.is_empty();
+        estimate.scoped_accounts = self.scoped_accounts.iter().map(Pubkey::to_string).collect();
+    }
+
+    /// Number of slots currently held by the rolling buffer, if one is set.
+    fn window_slots(&self) -> Option<usize> {
+        self.rolling
+            .as_ref()
+            .map(|buffer| buffer.lock().unwrap().fees_by_slot.len())
+    }
+
+    /// Use a custom HTTP client for RPC requests, e.g. one from
+    /// [`Config::rpc_http_client`](crate::config::Config::rpc_http_client)
+    /// that sends API key headers or goes through a proxy.
+    ///
+    /// A blocking client must not be dropped inside an async runtime, so
+    /// async callers should build and use the estimator on a blocking thread.
+    ///
+    /// Replaces any RPC set with [`with_rpc`](Self::with_rpc).
+    pub fn with_http_client(mut self, client: reqwest::blocking::Client) -> Self {
+        self.http_client = Some(client);
+        self.rpc = self.http_rpc();
+        self
+    }
+
+    /// Give up on each RPC request after `timeout`, failing the estimate with
+    /// [`TxOptimizerError::Timeout`]. Defaults to [`DEFAULT_REQUEST_TIMEOUT`].
+    ///
+    /// Every request gets the full timeout, so an estimate making two
+    /// requests can take up to twice as long. Replaces any RPC set with
+    /// [`with_rpc`](Self::with_rpc), which is responsible for its own
+    /// timeouts.
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self.rpc = self.http_rpc();
+        self
+    }
+
+    /// [`HttpFeeRpc`] on `rpc_url` with the configured client and timeout.
+    fn http_rpc(&self) -> Arc<dyn FeeRpc> {
+        let mut rpc = HttpFeeRpc::new(&self.rpc_url).with_request_timeout(self.request_timeout);
+        if let Some(client) = &self.http_client {
+            rpc = rpc.with_http_client(client.clone());
+        }
+        Arc::new(rpc)
+    }
+
+    /// Fetch samples and slots through a custom [`FeeRpc`].
+    pub fn with_rpc(mut self, rpc: Arc<dyn FeeRpc>) -> Self {
+        self.rpc = rpc;
+        self
+    }
+
+    /// Cap fees returned by [`escalated_fee`](Self::escalated_fee) and
+    /// [`estimate_with_buffer`](Self::estimate_with_buffer), typically to
+    /// `Config::max_priority_fee`.
+    pub fn with_max_priority_fee(mut self, microlamports_per_cu: u64) -> Self {
+        self.max_priority_fee = microlamports_per_cu;
+        self
+    }
+
+    /// Only sample slots that have reached `commitment`
+    /// (`processed`, `confirmed` or `finalized`).
+    ///
+    /// `getRecentPrioritizationFees` takes no commitment parameter, so the
+    /// estimator fetches the latest slot at this commitment and drops any
+    /// newer samples.
+    pub fn with_commitment(mut self, commitment: &str) -> Result<Self> {
+        self.commitment = match commitment {
+            "processed" => CommitmentConfig::processed(),
+            "confirmed" => CommitmentConfig::confirmed(),
+            "finalized" => CommitmentConfig::finalized(),
+            other => {
+                return Err(TxOptimizerError::Config(format!(
+                    "Invalid commitment level: {other} (expected processed, confirmed or finalized)"
+                )))
+            }
+        };
+        Ok(self)
+    }
+
+    /// The commitment level samples are capped at.
+    pub fn commitment(&self) -> CommitmentLevel {
+        self.commitment.commitment
+    }
+
+    /// Get [`estimate`](Self::estimate)'s recommended fee from `backend`.
+    ///
+    /// With [`FeeBackend::QuickNode`], the fee is the add-on's level for the
+    /// strategy and `percentiles` holds its levels: `low`, `medium` and
+    /// `high` as p25, p50 and p75, and `extreme` as p90 and the max. When the
+    /// node doesn't offer the method, or more than one account is scoped
+    /// (the add-on takes one), the estimate falls back to recent fee
+    /// samples. Every other estimate method always uses samples.
+    pub fn with_backend(mut self, backend: FeeBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Count slots that paid no priority fee as zero-fee samples instead of
+    /// dropping them.
+    ///
+    /// Off by default, which skews estimates upward when the network is
+    /// calm: a slot with no priority transactions is evidence that a zero
+    /// fee lands, but filtering it out leaves only the slots where someone
+    /// paid. With zero fees kept, percentiles are taken over every sampled
+    /// slot, so once more than a strategy's percentile of slots paid
+    /// nothing (a quarter of them for Economy's p25) that strategy
+    /// recommends 0, and `slots_sampled` counts the zero-fee slots too.
+    /// [Sanity bounds](Self::with_sanity_bounds) still apply afterwards.
+    pub fn with_include_zero_fees(mut self, include: bool) -> Self {
+        self.include_zero_fees = include;
+        self
+    }
+
+    /// Scope fee estimation to transactions involving specific accounts.
+    /// This is useful for getting more accurate fees for a particular program
+    /// (e.g., pass the Jupiter program ID to get swap-specific fee data).
+    pub fn with_scoped_accounts(mut self, accounts: Vec<Pubkey>) -> Self {
+        self.scoped_accounts = accounts;
+        self
+    }
+
+    /// Sort `fees` and, if none are left, walk the fallback chain, taking
+    /// network-wide samples from `unscoped` at most once.
+    ///
+    /// Zero fees are dropped unless
+    /// [included](Self::with_include_zero_fees). Warns when a scoped
+    /// estimator is left with fewer than [`MIN_SCOPED_SAMPLES`] non-zero
+    /// samples.
+    fn samples_from(&self, fees: &[u64], mut unscoped: impl FnMut() -> Vec<u64>) -> FeeSamples {
+        let (sorted, source) = Self::sorted_samples(fees, self.include_zero_fees);
+        let non_zero = sorted.iter().filter(|&&fee| fee > 0).count();
+        if !self.scoped_accounts.is_empty() && non_zero < MIN_SCOPED_SAMPLES {
+            warn!(
+                "Only {non_zero} fee samples for the {} scoped account(s); the estimate is \
+                 unreliable, consider broadening the scope",
+                self.scoped_accounts.len()
+            );
+        }
+        let mut samples = FeeSamples {
+            sorted,
+            source,
+            floor: 0,
+            fallback_used: None,
+        };
+        if !samples.sorted.is_empty() {
+            return samples;
+        }
+
+        // Network-wide samples, fetched at most once across the chain
+        let mut unscoped_fees: Option<Vec<u64>> = None;
+        for &step in &self.fallback_chain {
+            let floor = match step {
+                FallbackStep::Static(fee) => {
+                    samples.floor = fee;
+                    samples.fallback_used = Some(step);
+                    return samples;
+                }
+                FallbackStep::Unscoped => None,
+                FallbackStep::MaxOf(fee) => Some(fee),
+            };
+            let fees = unscoped_fees.get_or_insert_with(&mut unscoped);
+            if fees.is_empty() && floor.is_none() {
+                continue;
+            }
+            samples.sorted = fees.clone();
+            samples.floor = floor.unwrap_or(0);
+            samples.fallback_used = Some(step);
+            return samples;
+        }
+
+        samples.floor = crate::config::DEFAULT_PRIORITY_FEE_MICROLAMPORTS;
+        samples
+    }
+
+    /// Fetch recent fees and resolve them with
+    /// [`samples_from`](Self::samples_from).
+    fn fetch_fee_samples(&self) -> Result<FeeSamples> {
+        let fees = self.fetch_recent_fees()?;
+        Ok(self.samples_from(&fees, || self.fetch_unscoped_fees()))
+    }
+
+    /// Sorted network-wide samples for the fallback chain; empty for an
+    /// unscoped estimator, whose samples were already empty, or on error.
+    fn fetch_unscoped_fees(&self) -> Vec<u64> {
+        if self.scoped_accounts.is_empty() {
+            return Vec::new();
+        }
+        let mut unscoped = self.clone().with_scoped_accounts(Vec::new());
+        unscoped.rolling = None;
+        match unscoped.fetch_recent_fees() {
+            Ok(fees) => Self::sorted_samples(&fees, self.include_zero_fees).0,
+            Err(e) => {
+                warn!("Unscoped fallback fee fetch failed: {e}");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Sort fees ascending, dropping zero fees unless `include_zero_fees`.
+    ///
+    /// Also reports whether there were samples to estimate from, telling
+    /// all-zero samples apart from none at all.
+    fn sorted_samples(fees: &[u64], include_zero_fees: bool) -> (Vec<u64>, FeeDataSource) {
+        let mut sorted: Vec<u64> = fees
+            .iter()
+            .copied()
+            .filter(|&fee| include_zero_fees || fee > 0)
+            .collect();
+        sorted.sort_unstable();
+
+        let source = if !sorted.is_empty() {
+            FeeDataSource::Sampled
+        } else if fees.is_empty() {
+            warn!("No recent priority fee data found, using default fallback");
+            FeeDataSource::Fallback
+        } else {
+            info!(
+                "All {} recent fee samples are zero, using default fallback",
+                fees.len()
+            );
+            FeeDataSource::AllZeroFiltered
+        };
+        (sorted, source)
+    }
+
+    /// Fetch the raw `getRecentPrioritizationFees` samples for the last 150
+    /// slots, scoped to this estimator's accounts.
+    ///
+    /// Entries are returned as the RPC node reports them, including slots
+    /// whose minimum fee was zero; the only filtering is dropping slots newer
+    /// than the [commitment](Self::with_commitment) slot.
+    /// [`estimate`](Self::estimate) drops the zero-fee slots and computes
+    /// percentiles on top of this data.
+    pub fn fetch_samples(&self) -> Result<Vec<PrioritizationFeeEntry>> {
+        let mut entries = self.rpc.recent_fees(&self.scoped_accounts)?;
+        let max_slot = self.rpc.slot(self.commitment)?;
+        let received = entries.len();
+        entries.retain(|e| e.slot <= max_slot);
+        debug!(
+            "Received {received} fee samples, {} at or below {:?} slot {max_slot}",
+            entries.len(),
+            self.commitment.commitment
+        );
+        Ok(entries)
+    }
+
+    /// Fetch recent prioritization fees from the RPC node, merged into the
+    /// rolling buffer if there is one.
+    ///
+    /// Zero fees (slots with no priority transactions) are kept so callers
+    /// can tell an idle network from missing data; [`Self::sorted_samples`]
+    /// drops them.
+    fn fetch_recent_fees(&self) -> Result<Vec<u64>> {
+        let samples = self.fetch_samples()?;
+        let fees: Vec<u64> = match &self.rolling {
+            Some(buffer) => buffer.lock().unwrap().merge(samples),
+            None => samples.into_iter().map(|e| e.prioritization_fee).collect(),
+        };
+
+        info!(
+            "Collected {} non-zero fee samples",
+            fees.iter().filter(|&&fee| fee > 0).count()
+        );
+        Ok(fees)
+    }
+
+    /// Compute percentile value from a sorted list of fees.
+    ///
+    /// Uses the sample at rank `floor(pct * n / 100)`, so p90 of ten samples is
+    /// the largest one rather than an interpolated value.
+    fn percentile(sorted_fees: &[u64], pct: usize) -> u64 {
+        if sorted_fees.is_empty() {
+            return 0;
+        }
+        let index = pct * sorted_fees.len() / 100;
+        sorted_fees[index.min(sorted_fees.len() - 1)]
+    }
+
+    /// Estimate the optimal priority fee for the given strategy.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use solana_tx_optimizer::priority_fee::{PriorityFeeEstimator, FeeStrategy};
+    ///
+    /// let estimator = PriorityFeeEstimator::new("https://api.mainnet-beta.solana.com");
+    /// let estimate = estimator.estimate(FeeStrategy::Fast).unwrap();
+    /// println!("Recommended fee: {} microlamports/CU", estimate.recommended_fee);
+    /// ```
+    pub fn estimate(&self, strategy: FeeStrategy) -> Result<FeeEstimate> {
+        let quicknode = match self.backend {
+            FeeBackend::QuickNode => self.estimate_quicknode(strategy)?,
+            FeeBackend::RecentFees => None,
+        };
+        let mut estimate = match quicknode {
+            Some(mut estimate) => {
+                self.apply_sanity_bounds(&mut estimate);
+                estimate
+            }
+            None => {
+                let fees = self.fetch_recent_fees()?;
+                let mut estimate =
+                    self.estimate_from_fees(&fees, strategy, || self.fetch_unscoped_fees());
+                estimate.window_slots = self.window_slots();
+                estimate
+            }
+        };
+        self.record_scope(&mut estimate);
+
+        info!(
+            strategy = %strategy,
+            recommended_fee = estimate.recommended_fee,
+            slots_sampled = estimate.slots_sampled,
+            data_source = ?estimate.data_source,
+            "Fee estimation complete"
+        );
+
+        Ok(estimate)
+    }
+
+    /// Estimate from `qn_estimatePriorityFees`, or `None` to fall back to
+    /// samples.
+    fn estimate_quicknode(&self, strategy: FeeStrategy) -> Result<Option<FeeEstimate>> {
+        if self.scoped_accounts.len() > 1 {
+            debug!("qn_estimatePriorityFees takes a single account, using recent fee samples");
+            return Ok(None);
+        }
+        let account = self.scoped_accounts.first();
+        let Some(levels) = self.rpc.quicknode_fee_levels(account)? else {
+            info!("RPC node does not offer qn_estimatePriorityFees, using recent fee samples");
+            return Ok(None);
+        };
+
+        Ok(Some(FeeEstimate {
+            recommended_fee: levels.for_strategy(strategy),
+            strategy,
+            slots_sampled: QUICKNODE_LAST_N_BLOCKS,
+            percentiles: FeePercentiles {
+                p10: None,
+                p25: levels.low,
+                p50: levels.medium,
+                p75: levels.high,
+                p90: levels.extreme,
+                p95: None,
+                p99: None,
+                max: levels.extreme,
+            },
+            commitment: self.commitment(),
+            sanity_clamped: false,
+            effective_percentile: None,
+            window_slots: None,
+            data_source: FeeDataSource::QuickNode,
+            scoped: false,
+            scoped_accounts: Vec::new(),
+            fallback_used: None,
+        }))
+    }
+
+    /// Estimate a fee from caller-provided samples without any RPC request,
+    /// e.g. for backtesting against historical fee data.
+    ///
+    /// [`estimate`](Self::estimate) runs this same computation on the fees
+    /// it fetches, so the estimator's settings apply: zero fees are dropped
+    /// unless [included](Self::with_include_zero_fees), the percentile comes
+    /// from the [strategy map](Self::with_strategy_map), landing feedback,
+    /// the [fallback chain](Self::with_fallback_chain) and sanity bounds are
+    /// applied, and the result reports the estimator's commitment. With no
+    /// RPC to query, an [`Unscoped`](FallbackStep::Unscoped) fallback step
+    /// finds no samples, and `scoped` is left unset.
+    ///
+    /// # Example
+    /// ```
+    /// use solana_tx_optimizer::priority_fee::{FeeStrategy, PriorityFeeEstimator};
+    ///
+    /// let estimator = PriorityFeeEstimator::new("https://api.mainnet-beta.solana.com");
+    /// let fees = [300, 0, 100, 200];
+    /// let estimate = estimator.estimate_from_samples(&fees, FeeStrategy::Standard);
+    /// assert_eq!(estimate.recommended_fee, 200);
+    /// assert_eq!(estimate.slots_sampled, 3);
+    /// ```
+    pub fn estimate_from_samples(&self, fees: &[u64], strategy: FeeStrategy) -> FeeEstimate {
+        self.estimate_from_fees(fees, strategy, Vec::new)
+    }
+
+    /// The computation behind [`estimate`](Self::estimate) and
+    /// [`estimate_from_samples`](Self::estimate_from_samples), with
+    /// network-wide samples for the fallback chain taken from `unscoped`.
+    fn estimate_from_fees(
+        &self,
+        fees: &[u64],
+        strategy: FeeStrategy,
+        unscoped: impl FnMut() -> Vec<u64>,
+    ) -> FeeEstimate {
+        let samples = self.samples_from(fees, unscoped);
+        let mut estimate = Self::estimate_with_map(
+            &samples.sorted,
+            strategy,
+            &self.strategy_map,
+            self.commitment(),
+        );
+        self.apply_landing_feedback(&samples.sorted, &mut estimate);
+        samples.apply(&mut estimate);
+        self.apply_sanity_bounds(&mut estimate);
+        estimate
+    }
+
+    /// Estimate fees for every preset [`FeeStrategy`] from a single RPC call.
+    ///
+    /// All returned estimates share the same sample set, so `slots_sampled`
+    /// and `percentiles` are identical across strategies.
+    pub fn estimate_all(&self) -> Result<HashMap<FeeStrategy, FeeEstimate>> {
+        self.estimate_all_with(&[])
+    }
+
+    /// [`estimate_all`](Self::estimate_all), with `extra` strategies such as
+    /// [`Custom`](FeeStrategy::Custom) percentiles estimated from the same
+    /// samples alongside the presets.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use solana_tx_optimizer::priority_fee::{FeeStrategy, PriorityFeeEstimator};
+    ///
+    /// let estimator = PriorityFeeEstimator::new("https://api.mainnet-beta.solana.com");
+    /// let estimates = estimator.estimate_all_with(&[FeeStrategy::Custom(99)])?;
+    /// println!("p99: {}", estimates[&FeeStrategy::Custom(99)].recommended_fee);
+    /// # Ok::<(), solana_tx_optimizer::error::TxOptimizerError>(())
+    /// ```
+    pub fn estimate_all_with(
+        &self,
+        extra: &[FeeStrategy],
+    ) -> Result<HashMap<FeeStrategy, FeeEstimate>> {
+        let samples = self.fetch_fee_samples()?;
+        let strategies: Vec<FeeStrategy> = FeeStrategy::ALL.iter().chain(extra).copied().collect();
+        let mut estimates = Self::estimates_from_sorted(
+            &samples.sorted,
+            &strategies,
+            &self.strategy_map,
+            self.commitment(),
+        );
+        let window_slots = self.window_slots();
+        for estimate in estimates.values_mut() {
+            estimate.window_slots = window_slots;
+            samples.apply(estimate);
+            self.record_scope(estimate);
+            self.apply_sanity_bounds(estimate);
+        }
+        Ok(estimates)
+    }
+
+    /// Estimate a fee for each of `accounts` on its own, as if each were
+    /// passed alone to [`with_scoped_accounts`](Self::with_scoped_accounts).
+    ///
+    /// Up to the [configured](Self::with_per_account_concurrency) number of
+    /// accounts are fetched at once, each on a blocking thread. An account
+    /// whose estimate fails is listed in
+    /// [`errors`](PerAccountEstimates::errors) while the others still
+    /// complete. The rolling buffer, if any, is not used, so one account's
+    /// samples never mix into another's.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use solana_sdk::pubkey::Pubkey;
+    /// use solana_tx_optimizer::priority_fee::{FeeStrategy, PriorityFeeEstimator};
+    ///
+    /// # async fn run() {
+    /// let estimator = PriorityFeeEstimator::new("https://api.mainnet-beta.solana.com");
+    /// let programs = [Pubkey::new_unique(), Pubkey::new_unique()];
+    /// let result = estimator
+    ///     .estimate_per_account(&programs, FeeStrategy::Fast)
+    ///     .await;
+    /// for (program, estimate) in &result.estimates {
+    ///     println!("{program}: {} microlamports/CU", estimate.recommended_fee);
+    /// }
+    /// for (program, error) in &result.errors {
+    ///     eprintln!("{program}: {error}");
+    /// }
+    /// # }
+    /// ```
+    pub async fn estimate_per_account(
+        &self,
+        accounts: &[Pubkey],
+        strategy: FeeStrategy,
+    ) -> PerAccountEstimates {
+        let mut results: Vec<Option<std::result::Result<FeeEstimate, String>>> =
+            vec![None; accounts.len()];
+        let mut tasks = tokio::task::JoinSet::new();
+        let mut collect = |(index, result)| results[index] = Some(result);
+
+        for (index, &account) in accounts.iter().enumerate() {
+            if tasks.len() >= self.per_account_concurrency {
+                if let Some(Ok(done)) = tasks.join_next().await {
+                    collect(done);
+                }
+            }
+
+            let mut estimator = self.clone().with_scoped_accounts(vec![account]);
+            estimator.rolling = None;
+            tasks.spawn(async move {
+                let estimated =
+                    tokio::task::spawn_blocking(move || estimator.estimate(strategy)).await;
+                let result = match estimated {
+                    Ok(result) => result.map_err(|e| e.to_string()),
+                    Err(e) => Err(format!("estimate task failed: {e}")),
+                };
+                (index, result)
+            });
+        }
+        while let Some(joined) = tasks.join_next().await {
+            if let Ok(done) = joined {
+                collect(done);
+            }
+        }
+
+        let mut estimates = PerAccountEstimates::default();
+        for (&account, result) in accounts.iter().zip(results) {
+            match result {
+                Some(Ok(estimate)) => estimates.estimates.push((account, estimate)),
+                Some(Err(e)) => {
+                    warn!("Fee estimate for {account} failed: {e}");
+                    estimates.errors.push((account, e));
+                }
+                None => estimates
+                    .errors
+                    .push((account, "estimate task was cancelled".to_string())),
+            }
+        }
+        estimates
+    }
+
+    /// Estimate a fee between two strategies from a single sample set.
+    ///
+    /// Returns `a_fee * (1 - weight_b) + b_fee * weight_b`, rounded, so a
+    /// `weight_b` of 0.7 between Standard and Fast lands 70% of the way
+    /// toward Fast. `strategy` is whichever side carries more weight, and
+    /// `effective_percentile` records the blended percentile. `weight_b`
+    /// must be within `[0, 1]`.
+    pub fn estimate_blend(
+        &self,
+        a: FeeStrategy,
+        b: FeeStrategy,
+        weight_b: f64,
+    ) -> Result<FeeEstimate> {
+        if !(0.0..=1.0).contains(&weight_b) {
+            return Err(TxOptimizerError::Config(format!(
+                "blend weight must be within [0, 1], got {weight_b}"
+            )));
+        }
+
+        let samples = self.fetch_fee_samples()?;
+        let map = &self.strategy_map;
+        let mut a_estimate = Self::estimate_with_map(&samples.sorted, a, map, self.commitment());
+        let mut b_estimate = Self::estimate_with_map(&samples.sorted, b, map, self.commitment());
+        samples.apply(&mut a_estimate);
+        samples.apply(&mut b_estimate);
+
+        let weight_a = 1.0 - weight_b;
+        let blended = a_estimate.recommended_fee as f64 * weight_a
+            + b_estimate.recommended_fee as f64 * weight_b;
+        let mut estimate = if weight_b >= 0.5 {
+            b_estimate
+        } else {
+            a_estimate
+        };
+        estimate.recommended_fee = blended.round() as u64;
+        estimate.effective_percentile =
+            Some(map.percentile(a) as f64 * weight_a + map.percentile(b) as f64 * weight_b);
+        estimate.window_slots = self.window_slots();
+        self.record_scope(&mut estimate);
+        self.apply_sanity_bounds(&mut estimate);
+
+        info!(
+            a = %a,
+            b = %b,
+            weight_b,
+            recommended_fee = estimate.recommended_fee,
+            slots_sampled = estimate.slots_sampled,
+            "Blended fee estimation complete"
+        );
+
+        Ok(estimate)
+    }
+
+    /// Clamp `estimate` into the sanity bounds, if any are set.
+    fn apply_sanity_bounds(&self, estimate: &mut FeeEstimate) {
+        let Some((min, max)) = self.sanity_bounds else {
+            return;
+        };
+        let clamped = estimate.recommended_fee.clamp(min, max);
+        if clamped != estimate.recommended_fee {
+            warn!(
+                "Recommended fee {} microlamports/CU is outside sanity bounds [{min}, {max}], clamping to {clamped}",
+                estimate.recommended_fee
+            );
+            estimate.recommended_fee = clamped;
+            estimate.sanity_clamped = true;
+        }
+    }
+
+    fn estimates_from_sorted(
+        sorted_fees: &[u64],
+        strategies: &[FeeStrategy],
+        map: &StrategyMap,
+        commitment: CommitmentLevel,
+    ) -> HashMap<FeeStrategy, FeeEstimate> {
+        strategies
+            .iter()
+            .map(|&strategy| {
+                let estimate = Self::estimate_with_map(sorted_fees, strategy, map, commitment);
+                (strategy, estimate)
+            })
+            .collect()
+    }
+
+    /// Build an estimate from already-sorted fee samples, falling back to
+    /// the default fee when there are none.
+    ///
+    /// An empty sample set is reported as [`FeeDataSource::Fallback`]; callers
+    /// that dropped zero fees first correct that to `AllZeroFiltered`.
+    #[cfg(test)]
+    fn estimate_from_sorted(
+        sorted_fees: &[u64],
+        strategy: FeeStrategy,
+        commitment: CommitmentLevel,
+    ) -> FeeEstimate {
+        Self::estimate_with_map(sorted_fees, strategy, &StrategyMap::default(), commitment)
+    }
+
+    /// [`estimate_from_sorted`](Self::estimate_from_sorted) with the
+    /// strategy's percentile taken from `map`.
+    fn estimate_with_map(
+        sorted_fees: &[u64],
+        strategy: FeeStrategy,
+        map: &StrategyMap,
+        commitment: CommitmentLevel,
+    ) -> FeeEstimate {
+        if sorted_fees.is_empty() {
+            return FeeEstimate {
+                recommended_fee: crate::config::DEFAULT_PRIORITY_FEE_MICROLAMPORTS,
+                strategy,
+                slots_sampled: 0,
+                percentiles: FeePercentiles {
+                    p10: None,
+                    p25: 0,
+                    p50: 0,
+                    p75: 0,
+                    p90: 0,
+                    p95: None,
+                    p99: None,
+                    max: 0,
+                },
+                commitment,
+                sanity_clamped: false,
+                effective_percentile: None,
+                window_slots: None,
+                data_source: FeeDataSource::Fallback,
+                scoped: false,
+                scoped_accounts: Vec::new(),
+                fallback_used: None,
+            };
+        }
+
+        let percentiles = FeePercentiles {
+            p10: Some(Self::percentile(sorted_fees, 10)),
+            p25: Self::percentile(sorted_fees, 25),
+            p50: Self::percentile(sorted_fees, 50),
+            p75: Self::percentile(sorted_fees, 75),
+            p90: Self::percentile(sorted_fees, 90),
+            p95: Some(Self::percentile(sorted_fees, 95)),
+            p99: Some(Self::percentile(sorted_fees, 99)),
+            max: *sorted_fees.last().unwrap_or(&0),
+        };
+
+        FeeEstimate {
+            recommended_fee: Self::percentile(sorted_fees, map.percentile(strategy)),
+            strategy,
+            slots_sampled: sorted_fees.len(),
+            percentiles,
+            commitment,
+            sanity_clamped: false,
+            effective_percentile: None,
+            window_slots: None,
+            data_source: FeeDataSource::Sampled,
+            scoped: false,
+            scoped_accounts: Vec::new(),
+            fallback_used: None,
+        }
+    }
+
+    /// Estimate fee and apply a multiplier for extra safety margin.
+    /// Useful during network congestion where fees spike rapidly.
+    ///
+    /// A multiplier below 1.0 discounts the fee. The result is capped at the
+    /// [max priority fee](Self::with_max_priority_fee). Fails with
+    /// [`TxOptimizerError::Config`] if the multiplier is NaN, infinite or
+    /// negative.
+    pub fn estimate_with_buffer(
+        &self,
+        strategy: FeeStrategy,
+        buffer_multiplier: f64,
+    ) -> Result<FeeEstimate> {
+        if !buffer_multiplier.is_finite() || buffer_multiplier < 0.0 {
+            return Err(TxOptimizerError::Config(format!(
+                "Invalid fee buffer multiplier {buffer_multiplier} (expected a finite value >= 0)"
+            )));
+        }
+
+        let mut estimate = self.estimate(strategy)?;
+        let base_fee = estimate.recommended_fee;
+        estimate.recommended_fee = self.buffered_fee(base_fee, buffer_multiplier);
+        debug!(
+            "Applied {}x buffer: {} -> {} microlamports/CU",
+            buffer_multiplier, base_fee, estimate.recommended_fee
+        );
+        // The buffer can push a fee back out of bounds
+        self.apply_sanity_bounds(&mut estimate);
+        Ok(estimate)
+    }
+
+    /// Highest per-CU price, in microlamports, that keeps the total priority
+    /// fee of a transaction with `cu_limit` compute units within
+    /// `max_total_lamports`.
+    ///
+    /// The runtime charges `ceil(price * cu_limit / 1_000_000)` lamports, so
+    /// the price is rounded down. The result is also capped at the
+    /// [max priority fee](Self::with_max_priority_fee); a `cu_limit` of zero
+    /// costs nothing and returns that cap. No RPC request is made.
+    ///
+    /// # Example
+    /// ```
+    /// use solana_tx_optimizer::priority_fee::PriorityFeeEstimator;
+    ///
+    /// let estimator = PriorityFeeEstimator::new("https://api.mainnet-beta.solana.com");
+    /// // At most 1_000 lamports on priority for a 200k CU transaction
+    /// assert_eq!(estimator.fee_for_budget(1_000, 200_000), 5_000);
+    /// ```
+    pub fn fee_for_budget(&self, max_total_lamports: u64, cu_limit: u32) -> u64 {
+        if cu_limit == 0 {
+            return self.max_priority_fee;
+        }
+        let price = max_total_lamports as u128 * 1_000_000 / cu_limit as u128;
+        price.min(self.max_priority_fee as u128) as u64
+    }
+
+    /// Estimate the fee for `strategy`, lowered if needed so the total
+    /// priority fee for `cu_limit` compute units stays within
+    /// `max_total_lamports`.
+    ///
+    /// `recommended_fee` on the result is the effective per-CU price; see
+    /// [`fee_for_budget`](Self::fee_for_budget).
+    pub fn estimate_within_budget(
+        &self,
+        strategy: FeeStrategy,
+        max_total_lamports: u64,
+        cu_limit: u32,
+    ) -> Result<FeeEstimate> {
+        let mut estimate = self.estimate(strategy)?;
+        let budget_fee = self.fee_for_budget(max_total_lamports, cu_limit);
+        if estimate.recommended_fee > budget_fee {
+            debug!(
+                "Capping {} microlamports/CU to {budget_fee} to stay within {max_total_lamports} lamports for {cu_limit} CU",
+                estimate.recommended_fee
+            );
+            estimate.recommended_fee = budget_fee;
+        }
+        Ok(estimate)
+    }
+
+    /// `fee * multiplier`, truncated, computed in fixed point with millionth
+    /// precision so huge multipliers saturate instead of wrapping, then
+    /// capped at the max priority fee.
+    fn buffered_fee(&self, fee: u64, multiplier: f64) -> u64 {
+        const SCALE: u128 = 1_000_000;
+        let scaled_multiplier = (multiplier * SCALE as f64).round() as u128;
+        let buffered = (fee as u128).saturating_mul(scaled_multiplier) / SCALE;
+        buffered.min(self.max_priority_fee as u128) as u64
+    }
+
+    /// Fee to bid on retry `attempt` of a transaction that hasn't landed.
+    ///
+    /// Multiplies `base.recommended_fee` by `factor^attempt`, so attempt 0 is
+    /// the base fee itself, and clamps the result to the estimator's max
+    /// priority fee. No RPC request is made.
+    ///
+    /// Fails with [`TxOptimizerError::Config`] if `factor` is not finite or
+    /// is below 1.0, which would lower the fee on each retry.
+    pub fn escalated_fee(&self, base: &FeeEstimate, attempt: u8, factor: f64) -> Result<u64> {
+        if !factor.is_finite() || factor < 1.0 {
+            return Err(TxOptimizerError::Config(format!(
+                "Invalid fee escalation factor {factor} (expected a finite value >= 1)"
+            )));
+        }
+
+        let fee = base.recommended_fee as f64 * factor.powi(attempt as i32);
+        let fee = (fee as u64).min(self.max_priority_fee);
+        debug!(
+            "Escalated fee for attempt {}: {} -> {} microlamports/CU",
+            attempt, base.recommended_fee, fee
+        );
+        Ok(fee)
+    }
+}
+
+/// JSON-RPC error code for a method the node does not expose.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Turn a JSON-RPC `error` member into [`TxOptimizerError::Rpc`].
+fn check_rpc_error(method: &str, response: &serde_json::Value) -> Result<()> {
+    let Some(error) = response.get("error") else {
+        return Ok(());
+    };
+    let code = error.get("code").and_then(|c| c.as_i64());
+    let message = error
+        .get("message")
+        .and_then(|m| m.as_str())
+        .unwrap_or("Unknown error");
+
+    let mut detail = match code {
+        Some(code) => format!("{method} failed (code {code}): {message}"),
+        None => format!("{method} failed: {message}"),
+    };
+    if code == Some(METHOD_NOT_FOUND) {
+        detail.push_str(&format!(
+            " (this RPC node does not support {method}; use a different RPC provider)"
+        ));
+    }
+    Err(TxOptimizerError::Rpc(detail))
+}
+
+/// Extract the per-CU levels from a `qn_estimatePriorityFees` response, or
+/// `None` if the node doesn't offer the method.
+fn parse_quicknode_response(response: serde_json::Value) -> Result<Option<QuickNodeFeeLevels>> {
+    const METHOD: &str = "qn_estimatePriorityFees";
+    let code = response.pointer("/error/code").and_then(|c| c.as_i64());
+    if code == Some(METHOD_NOT_FOUND) {
+        return Ok(None);
+    }
+    check_rpc_error(METHOD, &response)?;
+
+    // Levels are documented as integers but fractional values have been seen
+    let level = |name: &str| {
+        response
+            .pointer(&format!("/result/per_compute_unit/{name}"))
+            .and_then(|v| v.as_f64())
+            .map(|fee| fee.round() as u64)
+            .ok_or_else(|| TxOptimizerError::Rpc(format!("{METHOD} returned no {name} level")))
+    };
+    Ok(Some(QuickNodeFeeLevels {
+        low: level("low")?,
+        medium: level("medium")?,
+        high: level("high")?,
+        extreme: level("extreme")?,
+    }))
+}
+
+/// Extract the slot from a `getSlot` response.
+fn parse_slot_response(response: serde_json::Value) -> Result<u64> {
+    check_rpc_error("getSlot", &response)?;
+    response
+        .get("result")
+        .and_then(|r| r.as_u64())
+        .ok_or_else(|| TxOptimizerError::Rpc("getSlot returned no slot".to_string()))
+}
+
+/// Extract fee entries from a `getRecentPrioritizationFees` response,
+/// turning a JSON-RPC `error` member into [`TxOptimizerError::Rpc`].
+fn parse_fee_response(mut response: serde_json::Value) -> Result<Vec<PrioritizationFeeEntry>> {
+    check_rpc_error("getRecentPrioritizationFees", &response)?;
+
+    match response.get_mut("result") {
+        Some(result) if !result.is_null() => Ok(serde_json::from_value(result.take())?),
+        _ => Err(TxOptimizerError::NoFeeData),
+    }
+}
+
+/// Build a `SetComputeUnitPrice` instruction for the given fee.
+///
+/// This is the instruction you prepend to your transaction to set priority fees.
+pub fn build_priority_fee_instruction(microlamports_per_cu: u64) -> solana_sdk::instruction::Instruction {
+    solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(
+        microlamports_per_cu,
+    )
+}
+
+/// Build a `SetComputeUnitLimit` instruction.
+///
+/// Setting an accurate CU limit reduces wasted fees since priority fee = CU_price * CU_consumed.
+pub fn build_compute_unit_limit_instruction(units: u32) -> solana_sdk::instruction::Instruction {
+    solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(units)
+}
+
+/// Build a `RequestHeapFrame` instruction for programs that need more than the
+/// default 32KB heap.
+///
+/// `bytes` must be a multiple of 1024 between [`MIN_HEAP_FRAME_BYTES`] and
+/// [`MAX_HEAP_FRAME_BYTES`]; the runtime rejects the whole transaction
+/// otherwise. Note that a larger heap is charged extra compute units.
+pub fn build_heap_frame_instruction(bytes: u32) -> Result<solana_sdk::instruction::Instruction> {
+    if !bytes.is_multiple_of(1024) {
+        return Err(TxOptimizerError::Config(format!(
+            "Heap frame size {bytes} is not a multiple of 1024"
+        )));
+    }
+    if !(MIN_HEAP_FRAME_BYTES..=MAX_HEAP_FRAME_BYTES).contains(&bytes) {
+        return Err(TxOptimizerError::Config(format!(
+            "Heap frame size {bytes} is outside {MIN_HEAP_FRAME_BYTES}..={MAX_HEAP_FRAME_BYTES}"
+        )));
+    }
+    Ok(solana_sdk::compute_budget::ComputeBudgetInstruction::request_heap_frame(bytes))
+}
+
+/// Size a compute unit limit for `instructions` by simulating them.
+///
+/// Builds an unsigned transaction paid by `payer`, with any
+/// `SetComputeUnitLimit` in `instructions` replaced by a request for
+/// [`MAX_COMPUTE_UNIT_LIMIT`] so the simulation isn't cut short, and returns
+/// the units it consumed plus [`CU_ESTIMATE_MARGIN_PERCENT`], capped at
+/// [`MAX_COMPUTE_UNIT_LIMIT`]. The estimate covers the compute-budget
+/// instructions too, so it suits a transaction that adds its own limit.
+///
+/// Fails with [`TxOptimizerError::SimulationFailed`] if the instructions
+/// would fail.
+///
+/// # Example
+/// ```no_run
+/// use solana_client::rpc_client::RpcClient;
+/// use solana_sdk::{pubkey::Pubkey, system_instruction};
+/// use solana_tx_optimizer::priority_fee::{
+///     build_compute_unit_limit_instruction, estimate_cu_for_instructions,
+/// };
+///
+/// let client = RpcClient::new("https://api.mainnet-beta.solana.com".to_string());
+/// let payer = Pubkey::new_unique();
+/// let ixs = [system_instruction::transfer(&payer, &Pubkey::new_unique(), 1)];
+/// let units = estimate_cu_for_instructions(&client, &payer, &ixs)?;
+/// let limit_ix = build_compute_unit_limit_instruction(units);
+/// # Ok::<(), solana_tx_optimizer::error::TxOptimizerError>(())
+/// ```
+pub fn estimate_cu_for_instructions(
+    client: &solana_client::rpc_client::RpcClient,
+    payer: &Pubkey,
+    instructions: &[solana_sdk::instruction::Instruction],
+) -> Result<u32> {
+    let mut simulated = vec![build_compute_unit_limit_instruction(MAX_COMPUTE_UNIT_LIMIT)];
+    simulated.extend(
+        instructions
+            .iter()
+            .filter(|ix| !is_compute_unit_limit_instruction(ix))
+            .cloned(),
+    );
+    let tx = solana_sdk::transaction::Transaction::new_with_payer(&simulated, Some(payer));
+
+    let summary = crate::tx::simulate(client, &tx)?;
+    if let Some(reason) = summary.err {
+        return Err(TxOptimizerError::SimulationFailed {
+            reason,
+            logs: summary.logs,
+        });
+    }
+    let consumed = summary.units_consumed.ok_or_else(|| {
+        TxOptimizerError::Rpc("simulateTransaction reported no units consumed".to_string())
+    })?;
+
+    let units = consumed
+        .saturating_mul(100 + CU_ESTIMATE_MARGIN_PERCENT)
+        .div_ceil(100)
+        .min(u64::from(MAX_COMPUTE_UNIT_LIMIT)) as u32;
+    debug!(
+        "{} instruction(s) consumed {consumed} CU, estimating {units}",
+        instructions.len()
+    );
+    Ok(units)
+}
+
+/// Whether `ix` is a compute-budget `SetComputeUnitLimit`.
+fn is_compute_unit_limit_instruction(ix: &solana_sdk::instruction::Instruction) -> bool {
+    use solana_sdk::compute_budget::{self, ComputeBudgetInstruction};
+
+    ix.program_id == compute_budget::id()
+        && matches!(
+            solana_sdk::borsh1::try_from_slice_unchecked(&ix.data),
+            Ok(ComputeBudgetInstruction::SetComputeUnitLimit(_))
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// [`FeeRpc`] serving canned samples and recording the scoped accounts
+    /// of each `recent_fees` call.
+    struct MockFeeRpc {
+        entries: Mutex<Vec<PrioritizationFeeEntry>>,
+        slot: u64,
+        requested_accounts: Mutex<Vec<Vec<Pubkey>>>,
+    }
+
+    impl MockFeeRpc {
+        /// Serve `(slot, fee)` samples with the commitment slot at `slot`.
+        fn new(samples: &[(u64, u64)], slot: u64) -> Arc<Self> {
+            Arc::new(Self {
+                entries: Mutex::new(Self::entries(samples)),
+                slot,
+                requested_accounts: Mutex::new(Vec::new()),
+            })
+        }
+
+        /// Serve `samples` from the next `recent_fees` call on.
+        fn set_samples(&self, samples: &[(u64, u64)]) {
+            *self.entries.lock().unwrap() = Self::entries(samples);
+        }
+
+        fn entries(samples: &[(u64, u64)]) -> Vec<PrioritizationFeeEntry> {
+            samples
+                .iter()
+                .map(|&(slot, prioritization_fee)| PrioritizationFeeEntry {
+                    slot,
+                    prioritization_fee,
+                })
+                .collect()
+        }
+    }
+
+    impl FeeRpc for MockFeeRpc {
+        fn recent_fees(&self, accounts: &[Pubkey]) -> Result<Vec<PrioritizationFeeEntry>> {
+            self.requested_accounts
+                .lock()
+                .unwrap()
+                .push(accounts.to_vec());
+            Ok(self.entries.lock().unwrap().clone())
+        }
+
+        fn slot(&self, _commitment: CommitmentConfig) -> Result<u64> {
+            Ok(self.slot)
+        }
+    }
+
+    fn mock_estimator(rpc: &Arc<MockFeeRpc>) -> PriorityFeeEstimator {
+        PriorityFeeEstimator::new("http://localhost:8899").with_rpc(rpc.clone())
+    }
+
+    #[test]
+    fn test_percentile_calculation() {
+        let fees = vec![100, 200, 300, 400, 500, 600, 700, 800, 900, 1000];
+        assert_eq!(PriorityFeeEstimator::percentile(&fees, 50), 600);
+        assert_eq!(PriorityFeeEstimator::percentile(&fees, 90), 1000);
+        assert_eq!(PriorityFeeEstimator::percentile(&fees, 0), 100);
+    }
+
+    #[test]
+    fn test_percentile_uses_floor_rank() {
+        // Rank floor(pct * n / 100), never interpolated between samples
+        let fees = vec![10, 20, 30, 40];
+        assert_eq!(PriorityFeeEstimator::percentile(&fees, 25), 20);
+        assert_eq!(PriorityFeeEstimator::percentile(&fees, 50), 30);
+        assert_eq!(PriorityFeeEstimator::percentile(&fees, 74), 30);
+        assert_eq!(PriorityFeeEstimator::percentile(&fees, 75), 40);
+        assert_eq!(PriorityFeeEstimator::percentile(&fees, 100), 40);
+        assert_eq!(PriorityFeeEstimator::percentile(&[7], 90), 7);
+    }
+
+    #[test]
+    fn test_percentile_empty() {
+        let fees: Vec<u64> = vec![];
+        assert_eq!(PriorityFeeEstimator::percentile(&fees, 50), 0);
+    }
+
+    #[test]
+    fn test_percentile_breakdown() {
+        let fees: Vec<u64> = (1..=100).map(|i| i * 10).collect();
+        let estimate = PriorityFeeEstimator::estimate_from_sorted(
+            &fees,
+            FeeStrategy::Turbo,
+            CommitmentLevel::Confirmed,
+        );
+        let percentiles = &estimate.percentiles;
+        assert_eq!(percentiles.get(10), Some(110));
+        assert_eq!(percentiles.get(90), Some(percentiles.p90));
+        assert_eq!(percentiles.get(95), Some(960));
+        assert_eq!(percentiles.get(99), Some(1000));
+        assert_eq!(percentiles.get(100), Some(1000));
+        assert_eq!(percentiles.get(60), None);
+
+        let empty = PriorityFeeEstimator::estimate_from_sorted(
+            &[],
+            FeeStrategy::Turbo,
+            CommitmentLevel::Confirmed,
+        );
+        assert_eq!(empty.percentiles.get(95), None);
+        assert_eq!(empty.percentiles.get(50), Some(0));
+
+        // Older serialized breakdowns have no tail fields
+        let raw = serde_json::json!({ "p25": 1, "p50": 2, "p75": 3, "p90": 4, "max": 5 });
+        let old: FeePercentiles = serde_json::from_value(raw.clone()).unwrap();
+        assert_eq!(old.p99, None);
+        assert_eq!(serde_json::to_value(&old).unwrap(), raw);
+    }
+
+    #[test]
+    fn test_estimate_diff() {
+        let a_fees: Vec<u64> = (1..=100).map(|i| i * 10).collect();
+        let b_fees: Vec<u64> = (1..=50).map(|i| i * 30).collect();
+        let commitment = CommitmentLevel::Confirmed;
+        let a = PriorityFeeEstimator::estimate_from_sorted(&a_fees, FeeStrategy::Fast, commitment);
+        let b = PriorityFeeEstimator::estimate_from_sorted(&b_fees, FeeStrategy::Fast, commitment);
+
+        let diff = a.diff(&b);
+        let recommended = diff.recommended_fee;
+        assert_eq!(
+            (recommended.from, recommended.to),
+            (a.recommended_fee, b.recommended_fee)
+        );
+        assert_eq!(
+            recommended.absolute,
+            i128::from(b.recommended_fee) - i128::from(a.recommended_fee)
+        );
+        assert!(recommended.absolute > 0);
+        assert_eq!(diff.more_samples, Some(DiffSide::This));
+        let pcts: Vec<u8> = diff.percentiles.iter().map(|(pct, _)| *pct).collect();
+        assert_eq!(pcts, DIFF_PERCENTILES);
+        let (_, max) = diff.percentiles[7];
+        assert_eq!((max.from, max.to, max.absolute), (1000, 1500, 500));
+        assert_eq!(max.percent, Some(50.0));
+
+        // The reverse diff flips every sign
+        let reverse = b.diff(&a);
+        assert_eq!(reverse.recommended_fee.absolute, -recommended.absolute);
+        assert_eq!(reverse.more_samples, Some(DiffSide::Other));
+
+        // Percentiles only one side has are skipped, and a zero base has no
+        // percentage
+        let empty = PriorityFeeEstimator::estimate_from_sorted(&[], FeeStrategy::Fast, commitment);
+        let diff = empty.diff(&a);
+        let pcts: Vec<u8> = diff.percentiles.iter().map(|(pct, _)| *pct).collect();
+        assert_eq!(pcts, [25, 50, 75, 90, 100]);
+        assert_eq!(diff.percentiles[0].1.percent, None);
+        assert_eq!(empty.diff(&empty).more_samples, None);
+    }
+
+    #[test]
+    fn test_build_heap_frame_instruction() {
+        assert!(build_heap_frame_instruction(MIN_HEAP_FRAME_BYTES).is_ok());
+        assert!(build_heap_frame_instruction(MAX_HEAP_FRAME_BYTES).is_ok());
+        assert!(build_heap_frame_instruction(64 * 1024).is_ok());
+
+        assert!(build_heap_frame_instruction(0).is_err());
+        assert!(build_heap_frame_instruction(MIN_HEAP_FRAME_BYTES - 1024).is_err());
+        assert!(build_heap_frame_instruction(MAX_HEAP_FRAME_BYTES + 1024).is_err());
+        assert!(build_heap_frame_instruction(64 * 1024 + 1).is_err());
+
+        let ix = build_heap_frame_instruction(MAX_HEAP_FRAME_BYTES).unwrap();
+        assert_eq!(
+            ix,
+            solana_sdk::compute_budget::ComputeBudgetInstruction::request_heap_frame(
+                MAX_HEAP_FRAME_BYTES
+            )
+        );
+    }
+
+    #[test]
+    fn test_estimate_cu_for_instructions() {
+        use solana_client::{rpc_client::RpcClient, rpc_request::RpcRequest};
+
+        let simulating = |value: serde_json::Value| {
+            let mut mocks = HashMap::new();
+            mocks.insert(
+                RpcRequest::SimulateTransaction,
+                serde_json::json!({ "context": { "slot": 1 }, "value": value }),
+            );
+            RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks)
+        };
+        let payer = Pubkey::new_unique();
+        let transfer = solana_sdk::system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+        let ixs = [
+            build_compute_unit_limit_instruction(200),
+            build_priority_fee_instruction(1_000),
+            transfer,
+        ];
+        assert!(is_compute_unit_limit_instruction(&ixs[0]));
+        assert!(!is_compute_unit_limit_instruction(&ixs[1]));
+        assert!(!is_compute_unit_limit_instruction(&ixs[2]));
+
+        // Each mock client answers a single simulation
+        let consumed = serde_json::json!({ "err": null, "unitsConsumed": 450 });
+        let client = simulating(consumed.clone());
+        assert_eq!(estimate_cu_for_instructions(&client, &payer, &ixs).unwrap(), 495);
+        // Without any compute-budget instruction
+        let client = simulating(consumed);
+        let units = estimate_cu_for_instructions(&client, &payer, &ixs[2..]).unwrap();
+        assert_eq!(units, 495);
+
+        let client = simulating(serde_json::json!({ "err": null, "unitsConsumed": 1_390_000 }));
+        let units = estimate_cu_for_instructions(&client, &payer, &ixs).unwrap();
+        assert_eq!(units, MAX_COMPUTE_UNIT_LIMIT);
+
+        let client = simulating(serde_json::json!({
+            "err": "InsufficientFundsForFee",
+            "logs": [],
+            "unitsConsumed": 0,
+        }));
+        assert!(matches!(
+            estimate_cu_for_instructions(&client, &payer, &ixs),
+            Err(TxOptimizerError::SimulationFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_estimates_share_samples() {
+        let fees = vec![100, 200, 300, 400, 500, 600, 700, 800, 900, 1000];
+        let estimates = PriorityFeeEstimator::estimates_from_sorted(
+            &fees,
+            &FeeStrategy::ALL,
+            &StrategyMap::default(),
+            CommitmentLevel::Confirmed,
+        );
+
+        assert_eq!(estimates.len(), FeeStrategy::ALL.len());
+        assert_eq!(estimates[&FeeStrategy::Economy].recommended_fee, 300);
+        assert_eq!(estimates[&FeeStrategy::Turbo].recommended_fee, 1000);
+        for estimate in estimates.values() {
+            assert_eq!(estimate.slots_sampled, 10);
+            assert_eq!(estimate.percentiles.p50, 600);
+            assert_eq!(estimate.commitment, CommitmentLevel::Confirmed);
+        }
+    }
+
+    #[test]
+    fn test_with_commitment_validation() {
+        let estimator = PriorityFeeEstimator::new("http://localhost:8899");
+        assert_eq!(estimator.commitment(), CommitmentLevel::Confirmed);
+
+        let estimator = estimator.with_commitment("finalized").unwrap();
+        assert_eq!(estimator.commitment(), CommitmentLevel::Finalized);
+
+        assert!(matches!(
+            estimator.with_commitment("recent"),
+            Err(TxOptimizerError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_sample_entry_roundtrip() {
+        let raw = serde_json::json!([
+            { "slot": 250_000_000u64, "prioritizationFee": 0 },
+            { "slot": 250_000_001u64, "prioritizationFee": 5000 },
+        ]);
+        let entries: Vec<PrioritizationFeeEntry> = serde_json::from_value(raw.clone()).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].prioritization_fee, 0);
+        assert_eq!(serde_json::to_value(&entries).unwrap(), raw);
+    }
+
+    #[test]
+    fn test_strategy_serde_roundtrip() {
+        let custom = serde_json::to_string(&FeeStrategy::Custom(95)).unwrap();
+        assert_eq!(custom, r#""custom:95""#);
+        let strategies = FeeStrategy::ALL.into_iter().chain([
+            FeeStrategy::Custom(0),
+            FeeStrategy::Custom(95),
+            FeeStrategy::Custom(100),
+        ]);
+        for strategy in strategies {
+            let json = serde_json::to_string(&strategy).unwrap();
+            let name: String = serde_json::from_str(&json).unwrap();
+            assert_eq!(name.parse::<FeeStrategy>().unwrap(), strategy);
+            let parsed: FeeStrategy = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, strategy);
+        }
+
+        #[cfg(not(feature = "serialize_v2"))]
+        {
+            let json = serde_json::to_string(&FeeStrategy::Fast).unwrap();
+            assert_eq!(json, r#""Fast""#);
+        }
+        assert!(serde_json::from_str::<FeeStrategy>(r#""custom:101""#).is_err());
+        assert!(serde_json::from_str::<FeeStrategy>(r#""slow""#).is_err());
+    }
+
+    #[cfg(feature = "serialize_v2")]
+    #[test]
+    fn test_strategy_v2_wire_format() {
+        let json = serde_json::to_string(&FeeStrategy::Turbo).unwrap();
+        assert_eq!(json, r#""turbo""#);
+        let strategy: FeeStrategy = serde_json::from_str(r#""economy""#).unwrap();
+        assert_eq!(strategy, FeeStrategy::Economy);
+        // History logs written before serialize_v2 still load
+        let strategy: FeeStrategy = serde_json::from_str(r#""Fast""#).unwrap();
+        assert_eq!(strategy, FeeStrategy::Fast);
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": 100 }))
+                    .set_delay(Duration::from_millis(500)),
+            )
+            .mount(&server)
+            .await;
+
+        let estimator = PriorityFeeEstimator::new(&server.uri())
+            .with_request_timeout(Duration::from_millis(50));
+        let started = std::time::Instant::now();
+        // The client must be created and dropped off the async runtime
+        let result = tokio::task::spawn_blocking(move || {
+            estimator
+                .with_http_client(reqwest::blocking::Client::new())
+                .estimate(FeeStrategy::Fast)
+        })
+        .await
+        .unwrap();
+        match result {
+            Err(TxOptimizerError::Timeout { method, timeout }) => {
+                assert_eq!(method, "getRecentPrioritizationFees");
+                assert_eq!(timeout, Duration::from_millis(50));
+            }
+            other => panic!("expected a timeout, got {other:?}"),
+        }
+        assert!(started.elapsed() < Duration::from_millis(500));
+    }
+
+    /// JSON-RPC node serving fee samples, and QuickNode fee levels if
+    /// `quicknode` is set.
+    async fn fee_server(quicknode: bool) -> wiremock::MockServer {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(move |request: &wiremock::Request| {
+                let body: serde_json::Value = request.body_json().unwrap();
+                let reply = match body["method"].as_str() {
+                    Some("qn_estimatePriorityFees") if quicknode => serde_json::json!({
+                        "result": {
+                            "per_compute_unit": {
+                                "low": 1_000, "medium": 2_000.4, "high": 3_000, "extreme": 9_000,
+                            },
+                        },
+                    }),
+                    Some("qn_estimatePriorityFees") => serde_json::json!({
+                        "error": { "code": METHOD_NOT_FOUND, "message": "Method not found" },
+                    }),
+                    Some("getSlot") => serde_json::json!({ "result": 100 }),
+                    _ => serde_json::json!({
+                        "result": [{ "slot": 99, "prioritizationFee": 500 }],
+                    }),
+                };
+                wiremock::ResponseTemplate::new(200).set_body_json(reply)
+            })
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn test_quicknode_backend() {
+        for (quicknode, expected) in [
+            (true, (2_000, FeeDataSource::QuickNode)),
+            (false, (500, FeeDataSource::Sampled)),
+        ] {
+            let server = fee_server(quicknode).await;
+            let estimator =
+                PriorityFeeEstimator::new(&server.uri()).with_backend(FeeBackend::QuickNode);
+            // The client must be created and dropped off the async runtime
+            let estimate = tokio::task::spawn_blocking(move || {
+                estimator
+                    .with_http_client(reqwest::blocking::Client::new())
+                    .estimate(FeeStrategy::Standard)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+            assert_eq!((estimate.recommended_fee, estimate.data_source), expected);
+        }
+
+        let levels = QuickNodeFeeLevels {
+            low: 1,
+            medium: 2,
+            high: 3,
+            extreme: 4,
+        };
+        let fees: Vec<u64> = FeeStrategy::ALL
+            .iter()
+            .map(|&s| levels.for_strategy(s))
+            .collect();
+        assert_eq!(fees, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_rpc_error_response() {
+        let response = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": { "code": -32601, "message": "Method not found" },
+        });
+        match parse_fee_response(response) {
+            Err(TxOptimizerError::Rpc(detail)) => {
+                assert!(detail.contains("code -32601"), "{detail}");
+                assert!(detail.contains("Method not found"), "{detail}");
+                assert!(detail.contains("different RPC provider"), "{detail}");
+            }
+            other => panic!("expected Rpc error, got {other:?}"),
+        }
+
+        let response = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": { "code": 429, "message": "Too many requests" },
+        });
+        match parse_fee_response(response) {
+            Err(TxOptimizerError::Rpc(detail)) => {
+                assert_eq!(detail, "getRecentPrioritizationFees failed (code 429): Too many requests");
+            }
+            other => panic!("expected Rpc error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_slot_response() {
+        let response = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": 250_000_000u64 });
+        assert_eq!(parse_slot_response(response).unwrap(), 250_000_000);
+
+        let response = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": { "code": 401, "message": "Unauthorized" },
+        });
+        match parse_slot_response(response) {
+            Err(TxOptimizerError::Rpc(detail)) => {
+                assert_eq!(detail, "getSlot failed (code 401): Unauthorized");
+            }
+            other => panic!("expected Rpc error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_missing_result_is_no_fee_data() {
+        let response = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": null });
+        assert!(matches!(parse_fee_response(response), Err(TxOptimizerError::NoFeeData)));
+    }
+
+    #[test]
+    fn test_escalated_fee() {
+        let estimator =
+            PriorityFeeEstimator::new("http://localhost:8899").with_max_priority_fee(5000);
+        let base = PriorityFeeEstimator::estimate_from_sorted(
+            &[1000],
+            FeeStrategy::Standard,
+            CommitmentLevel::Confirmed,
+        );
+
+        assert_eq!(estimator.escalated_fee(&base, 0, 1.5).unwrap(), 1000);
+        assert_eq!(estimator.escalated_fee(&base, 1, 1.5).unwrap(), 1500);
+        assert_eq!(estimator.escalated_fee(&base, 2, 1.5).unwrap(), 2250);
+        assert_eq!(estimator.escalated_fee(&base, 5, 1.5).unwrap(), 5000);
+        assert_eq!(estimator.escalated_fee(&base, u8::MAX, 2.0).unwrap(), 5000);
+        assert_eq!(estimator.escalated_fee(&base, 3, 1.0).unwrap(), 1000);
+    }
+
+    #[test]
+    fn test_escalated_fee_rejects_shrinking_factors() {
+        let estimator = PriorityFeeEstimator::new("http://localhost:8899");
+        let base = PriorityFeeEstimator::estimate_from_sorted(
+            &[1000],
+            FeeStrategy::Standard,
+            CommitmentLevel::Confirmed,
+        );
+
+        for factor in [0.5, 0.0, -2.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                estimator.escalated_fee(&base, 1, factor),
+                Err(TxOptimizerError::Config(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_buffered_fee() {
+        let estimator = PriorityFeeEstimator::new("http://localhost:8899");
+        assert_eq!(estimator.buffered_fee(10_000, 1.0), 10_000);
+        assert_eq!(estimator.buffered_fee(10_000, 1.2), 12_000);
+        assert_eq!(estimator.buffered_fee(10_000, 0.5), 5_000);
+        assert_eq!(estimator.buffered_fee(10_000, 0.0), 0);
+        assert_eq!(estimator.buffered_fee(3, 1.5), 4);
+        let cap = crate::config::DEFAULT_MAX_PRIORITY_FEE_MICROLAMPORTS;
+        assert_eq!(estimator.buffered_fee(10_000, 1e300), cap);
+        assert_eq!(estimator.buffered_fee(u64::MAX, f64::MAX), cap);
+
+        let uncapped = estimator.with_max_priority_fee(u64::MAX);
+        assert_eq!(uncapped.buffered_fee(u64::MAX, 2.0), u64::MAX);
+        assert_eq!(uncapped.buffered_fee(u64::MAX, 1.0), u64::MAX);
+        assert_eq!(uncapped.buffered_fee(1 << 60, 16.0), u64::MAX);
+    }
+
+    #[test]
+    fn test_fee_for_budget() {
+        let estimator = PriorityFeeEstimator::new("http://localhost:8899");
+        assert_eq!(estimator.fee_for_budget(1_000, 200_000), 5_000);
+        // Rounded down so the charged fee never exceeds the budget
+        let price = estimator.fee_for_budget(1_000, 300_000);
+        assert_eq!(price, 3_333);
+        assert!((price * 300_000).div_ceil(1_000_000) <= 1_000);
+        assert_eq!(estimator.fee_for_budget(0, 200_000), 0);
+        // Capped at the max priority fee
+        assert_eq!(estimator.fee_for_budget(u64::MAX, 1), 500_000);
+        assert_eq!(estimator.fee_for_budget(1_000, 0), 500_000);
+
+        let rpc = MockFeeRpc::new(&[(1, 4_000), (2, 8_000)], 100);
+        let estimator = mock_estimator(&rpc);
+        let capped = estimator
+            .estimate_within_budget(FeeStrategy::Turbo, 1_000, 200_000)
+            .unwrap();
+        assert_eq!(capped.recommended_fee, 5_000);
+        let within = estimator
+            .estimate_within_budget(FeeStrategy::Economy, 1_000, 200_000)
+            .unwrap();
+        assert_eq!(within.recommended_fee, 4_000);
+    }
+
+    #[test]
+    fn test_invalid_buffer_multiplier() {
+        let rpc = MockFeeRpc::new(&[(1, 1000)], 100);
+        let estimator = mock_estimator(&rpc);
+        for multiplier in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, -0.5] {
+            assert!(matches!(
+                estimator.estimate_with_buffer(FeeStrategy::Fast, multiplier),
+                Err(TxOptimizerError::Config(_))
+            ));
+        }
+        // Rejected before any RPC request
+        assert!(rpc.requested_accounts.lock().unwrap().is_empty());
+
+        let estimate = estimator
+            .estimate_with_buffer(FeeStrategy::Fast, 1.5)
+            .unwrap();
+        assert_eq!(estimate.recommended_fee, 1500);
+    }
+
+    #[test]
+    fn test_sanity_bounds() {
+        let estimator = PriorityFeeEstimator::new("http://localhost:8899");
+        assert!(matches!(
+            estimator.clone().with_sanity_bounds(500, 100),
+            Err(TxOptimizerError::Config(_))
+        ));
+        let estimator = estimator.with_sanity_bounds(1000, 50_000).unwrap();
+
+        // One gigantic sample dominates p90
+        let mut fees = vec![4000; 9];
+        fees.push(900_000_000);
+        let mut turbo = PriorityFeeEstimator::estimate_from_sorted(
+            &fees,
+            FeeStrategy::Turbo,
+            CommitmentLevel::Confirmed,
+        );
+        estimator.apply_sanity_bounds(&mut turbo);
+        assert_eq!(turbo.recommended_fee, 50_000);
+        assert!(turbo.sanity_clamped);
+
+        let mut standard = PriorityFeeEstimator::estimate_from_sorted(
+            &fees,
+            FeeStrategy::Standard,
+            CommitmentLevel::Confirmed,
+        );
+        estimator.apply_sanity_bounds(&mut standard);
+        assert_eq!(standard.recommended_fee, 4000);
+        assert!(!standard.sanity_clamped);
+
+        let mut low = PriorityFeeEstimator::estimate_from_sorted(
+            &[1],
+            FeeStrategy::Economy,
+            CommitmentLevel::Confirmed,
+        );
+        estimator.apply_sanity_bounds(&mut low);
+        assert_eq!(low.recommended_fee, 1000);
+        assert!(low.sanity_clamped);
+    }
+
+    #[test]
+    fn test_empty_samples_fall_back_to_default() {
+        let rpc = MockFeeRpc::new(&[], 100);
+        let estimate = mock_estimator(&rpc).estimate(FeeStrategy::Fast).unwrap();
+        assert_eq!(
+            estimate.recommended_fee,
+            crate::config::DEFAULT_PRIORITY_FEE_MICROLAMPORTS
+        );
+        assert_eq!(estimate.slots_sampled, 0);
+        assert_eq!(estimate.percentiles.max, 0);
+        assert_eq!(estimate.data_source, FeeDataSource::Fallback);
+        assert_eq!(
+            estimate.fallback_used,
+            Some(FallbackStep::Static(
+                crate::config::DEFAULT_PRIORITY_FEE_MICROLAMPORTS
+            ))
+        );
+    }
+
+    /// Serves `unscoped` samples network-wide and none for any scope.
+    struct ScopeRpc {
+        unscoped: Vec<PrioritizationFeeEntry>,
+        requests: Mutex<usize>,
+    }
+
+    impl FeeRpc for ScopeRpc {
+        fn recent_fees(&self, accounts: &[Pubkey]) -> Result<Vec<PrioritizationFeeEntry>> {
+            *self.requests.lock().unwrap() += 1;
+            if accounts.is_empty() {
+                Ok(self.unscoped.clone())
+            } else {
+                Ok(Vec::new())
+            }
+        }
+
+        fn slot(&self, _commitment: CommitmentConfig) -> Result<u64> {
+            Ok(100)
+        }
+    }
+
+    #[test]
+    fn test_landing_feedback() {
+        let samples: Vec<(u64, u64)> = (1..=100).map(|i| (i, i * 10)).collect();
+        let rpc = MockFeeRpc::new(&samples, 1_000);
+        let estimator = mock_estimator(&rpc).with_landing_target(0.9).unwrap();
+        let standard = || estimator.estimate(FeeStrategy::Standard).unwrap();
+
+        // Too few outcomes to act on
+        for _ in 0..MIN_LANDING_OUTCOMES - 1 {
+            estimator.record_outcome(510, false);
+        }
+        assert_eq!(estimator.landing_rate(), None);
+        assert_eq!(standard().recommended_fee, 510);
+        assert_eq!(standard().effective_percentile, None);
+
+        // 1 of 5 landed, 70 points short of the target: p50 moves up to p99
+        estimator.record_outcome(510, true);
+        assert_eq!(estimator.landing_rate(), Some(0.2));
+        let estimate = standard();
+        assert_eq!(estimate.effective_percentile, Some(99.0));
+        assert_eq!(estimate.recommended_fee, 1_000);
+
+        // 6 of 10 landed, 30 points short: p80
+        for _ in 0..5 {
+            estimator.record_outcome(1_000, true);
+        }
+        let estimate = standard();
+        assert_eq!(estimate.effective_percentile.map(f64::round), Some(80.0));
+        assert_eq!(estimate.recommended_fee, 810);
+
+        // Every recent outcome landed: p50 moves down to p40
+        let clone = estimator.clone();
+        for _ in 0..LANDING_FEEDBACK_WINDOW {
+            clone.record_outcome(1_000, true);
+        }
+        assert_eq!(estimator.landing_rate(), Some(1.0));
+        let estimate = standard();
+        assert_eq!(estimate.effective_percentile, Some(40.0));
+        assert_eq!(estimate.recommended_fee, 410);
+
+        // Off by default
+        let estimator = mock_estimator(&rpc);
+        estimator.record_outcome(510, false);
+        assert_eq!(estimator.landing_rate(), None);
+        assert!(mock_estimator(&rpc).with_landing_target(0.0).is_err());
+        assert!(mock_estimator(&rpc).with_landing_target(1.5).is_err());
+    }
+
+    #[test]
+    fn test_fallback_chain() {
+        let estimate = |unscoped: &[(u64, u64)], scoped: bool, chain: Vec<FallbackStep>| {
+            let rpc = Arc::new(ScopeRpc {
+                unscoped: MockFeeRpc::entries(unscoped),
+                requests: Mutex::new(0),
+            });
+            let accounts = if scoped {
+                vec![Pubkey::new_unique()]
+            } else {
+                Vec::new()
+            };
+            let estimate = PriorityFeeEstimator::new("http://localhost:8899")
+                .with_rpc(rpc.clone())
+                .with_scoped_accounts(accounts)
+                .with_fallback_chain(chain)
+                .estimate(FeeStrategy::Standard)
+                .unwrap();
+            let requests = *rpc.requests.lock().unwrap();
+            (estimate, requests)
+        };
+        let network = [(1, 100), (2, 200), (3, 300)];
+
+        let chain = vec![FallbackStep::Unscoped, FallbackStep::Static(1)];
+        let (unscoped, requests) = estimate(&network, true, chain.clone());
+        assert_eq!(unscoped.recommended_fee, 200);
+        assert_eq!(unscoped.slots_sampled, 3);
+        assert_eq!(unscoped.fallback_used, Some(FallbackStep::Unscoped));
+        assert_eq!(unscoped.data_source, FeeDataSource::Fallback);
+        assert!(unscoped.scoped);
+        assert_eq!(requests, 2);
+
+        // An unscoped estimator has nothing broader to fall back to
+        let (fixed, requests) = estimate(&[], false, chain);
+        assert_eq!(fixed.recommended_fee, 1);
+        assert_eq!(fixed.fallback_used, Some(FallbackStep::Static(1)));
+        assert_eq!(requests, 1);
+
+        let (raised, _) = estimate(&network, true, vec![FallbackStep::MaxOf(5_000)]);
+        assert_eq!(raised.recommended_fee, 5_000);
+        let (kept, _) = estimate(&network, true, vec![FallbackStep::MaxOf(50)]);
+        assert_eq!(kept.recommended_fee, 200);
+        let (floor, _) = estimate(&[], true, vec![FallbackStep::MaxOf(50)]);
+        assert_eq!(floor.recommended_fee, 50);
+        assert_eq!(floor.fallback_used, Some(FallbackStep::MaxOf(50)));
+
+        let (exhausted, _) = estimate(&[], true, vec![FallbackStep::Unscoped]);
+        assert_eq!(
+            exhausted.recommended_fee,
+            crate::config::DEFAULT_PRIORITY_FEE_MICROLAMPORTS
+        );
+        assert_eq!(exhausted.fallback_used, None);
+    }
+
+    #[test]
+    fn test_include_zero_fees() {
+        let rpc = MockFeeRpc::new(&[(1, 0), (2, 0), (3, 400), (4, 200), (5, 0)], 100);
+        let estimator = mock_estimator(&rpc).with_include_zero_fees(true);
+
+        let economy = estimator.estimate(FeeStrategy::Economy).unwrap();
+        assert_eq!(economy.recommended_fee, 0);
+        assert_eq!(economy.slots_sampled, 5);
+        assert_eq!(economy.data_source, FeeDataSource::Sampled);
+        let fast = estimator.estimate(FeeStrategy::Fast).unwrap();
+        assert_eq!(fast.recommended_fee, 200);
+
+        // Filtered by default
+        let economy = mock_estimator(&rpc).estimate(FeeStrategy::Economy).unwrap();
+        assert_eq!(economy.recommended_fee, 200);
+        assert_eq!(economy.slots_sampled, 2);
+    }
+
+    #[test]
+    fn test_all_zero_samples_are_not_missing_data() {
+        let rpc = MockFeeRpc::new(&[(1, 0), (2, 0), (3, 0)], 100);
+        let estimator = mock_estimator(&rpc);
+        let estimate = estimator.estimate(FeeStrategy::Fast).unwrap();
+        assert_eq!(
+            estimate.recommended_fee,
+            crate::config::DEFAULT_PRIORITY_FEE_MICROLAMPORTS
+        );
+        assert_eq!(estimate.data_source, FeeDataSource::AllZeroFiltered);
+
+        let all = estimator.estimate_all().unwrap();
+        assert!(all
+            .values()
+            .all(|e| e.data_source == FeeDataSource::AllZeroFiltered));
+
+        rpc.set_samples(&[(1, 0), (2, 300)]);
+        let estimate = estimator.estimate(FeeStrategy::Fast).unwrap();
+        assert_eq!(estimate.recommended_fee, 300);
+        assert_eq!(estimate.data_source, FeeDataSource::Sampled);
+    }
+
+    #[test]
+    fn test_estimate_from_samples_matches_estimate() {
+        let samples = [(1, 500), (2, 0), (3, 100), (4, 300), (5, 200), (6, 400)];
+        let rpc = MockFeeRpc::new(&samples, 100);
+        let estimator = mock_estimator(&rpc).with_commitment("finalized").unwrap();
+        let fees: Vec<u64> = samples.iter().map(|&(_, fee)| fee).collect();
+
+        for strategy in FeeStrategy::ALL {
+            let fetched = estimator.estimate(strategy).unwrap();
+            let offline = estimator.estimate_from_samples(&fees, strategy);
+            assert_eq!(
+                offline.recommended_fee, fetched.recommended_fee,
+                "{strategy}"
+            );
+            assert_eq!(offline.slots_sampled, 5);
+            assert_eq!(offline.percentiles.max, fetched.percentiles.max);
+            assert_eq!(offline.commitment, CommitmentLevel::Finalized);
+            assert_eq!(fetched.commitment, CommitmentLevel::Finalized);
+        }
+
+        let empty = estimator.estimate_from_samples(&[0, 0], FeeStrategy::Fast);
+        assert_eq!(
+            empty.recommended_fee,
+            crate::config::DEFAULT_PRIORITY_FEE_MICROLAMPORTS
+        );
+        assert_eq!(empty.slots_sampled, 0);
+
+        // The estimator's settings apply to caller-provided samples too.
+        let tuned = estimator
+            .with_include_zero_fees(true)
+            .with_strategy_map("0, 50, 75, 90".parse().unwrap())
+            .unwrap()
+            .with_fallback_chain(vec![FallbackStep::Unscoped, FallbackStep::Static(42)]);
+        let economy = tuned.estimate_from_samples(&fees, FeeStrategy::Economy);
+        assert_eq!(economy.recommended_fee, 0);
+        assert_eq!(economy.slots_sampled, 6);
+        let fetched = tuned.estimate(FeeStrategy::Economy).unwrap();
+        assert_eq!(economy.recommended_fee, fetched.recommended_fee);
+        let none = tuned.estimate_from_samples(&[], FeeStrategy::Fast);
+        assert_eq!(none.recommended_fee, 42);
+        assert_eq!(none.fallback_used, Some(FallbackStep::Static(42)));
+    }
+
+    #[test]
+    fn test_zero_fee_slots_are_ignored() {
+        let rpc = MockFeeRpc::new(&[(1, 0), (2, 400), (3, 0), (4, 200), (5, 0)], 100);
+        let estimator = mock_estimator(&rpc);
+        assert_eq!(estimator.fetch_samples().unwrap().len(), 5);
+
+        let estimate = estimator.estimate(FeeStrategy::Economy).unwrap();
+        assert_eq!(estimate.slots_sampled, 2);
+        assert_eq!(estimate.recommended_fee, 200);
+        assert_eq!(estimate.percentiles.max, 400);
+    }
+
+    #[test]
+    fn test_rolling_buffer_merges_by_slot() {
+        let rpc = MockFeeRpc::new(&[(1, 100), (2, 200), (3, 0)], 100);
+        let estimator = mock_estimator(&rpc).with_rolling_buffer(4).unwrap();
+        assert!(mock_estimator(&rpc).with_rolling_buffer(0).is_err());
+
+        let first = estimator.estimate(FeeStrategy::Standard).unwrap();
+        assert_eq!(first.window_slots, Some(3));
+        assert_eq!(first.slots_sampled, 2);
+
+        // Same slots again: nothing is double-counted
+        let repeat = estimator.estimate(FeeStrategy::Standard).unwrap();
+        assert_eq!(repeat.window_slots, Some(3));
+        assert_eq!(repeat.slots_sampled, 2);
+
+        // Slot 3 is overwritten, slots 4-5 push slot 1 out of the window
+        rpc.set_samples(&[(3, 300), (4, 400), (5, 500)]);
+        let merged = estimator.clone().estimate(FeeStrategy::Economy).unwrap();
+        assert_eq!(merged.window_slots, Some(4));
+        assert_eq!(merged.slots_sampled, 4);
+        assert_eq!(merged.recommended_fee, 300);
+        assert_eq!(merged.percentiles.max, 500);
+
+        let unbuffered = mock_estimator(&rpc).estimate(FeeStrategy::Fast).unwrap();
+        assert_eq!(unbuffered.window_slots, None);
+    }
+
+    #[test]
+    fn test_estimate_blend() {
+        let samples: Vec<(u64, u64)> = (1..=100).map(|i| (i, i * 10)).collect();
+        let rpc = MockFeeRpc::new(&samples, 1000);
+        let estimator = mock_estimator(&rpc);
+
+        // Standard is 510, Fast is 760
+        let blend = estimator
+            .estimate_blend(FeeStrategy::Standard, FeeStrategy::Fast, 0.7)
+            .unwrap();
+        assert_eq!(blend.recommended_fee, 685);
+        assert_eq!(blend.strategy, FeeStrategy::Fast);
+        assert_eq!(blend.slots_sampled, 100);
+        assert!((blend.effective_percentile.unwrap() - 67.5).abs() < 1e-9);
+
+        let all_a = estimator
+            .estimate_blend(FeeStrategy::Standard, FeeStrategy::Fast, 0.0)
+            .unwrap();
+        assert_eq!(all_a.recommended_fee, 510);
+        assert_eq!(all_a.strategy, FeeStrategy::Standard);
+
+        for weight in [-0.1, 1.5, f64::NAN] {
+            assert!(matches!(
+                estimator.estimate_blend(FeeStrategy::Standard, FeeStrategy::Fast, weight),
+                Err(TxOptimizerError::Config(_))
+            ));
+        }
+        assert_eq!(rpc.requested_accounts.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_samples_capped_at_commitment_slot() {
+        let rpc = MockFeeRpc::new(&[(8, 100), (9, 200), (10, 300), (11, 900_000)], 10);
+        let estimator = mock_estimator(&rpc);
+
+        let slots: Vec<u64> = estimator
+            .fetch_samples()
+            .unwrap()
+            .iter()
+            .map(|e| e.slot)
+            .collect();
+        assert_eq!(slots, vec![8, 9, 10]);
+        let estimate = estimator.estimate(FeeStrategy::Turbo).unwrap();
+        assert_eq!(estimate.recommended_fee, 300);
+    }
+
+    #[test]
+    fn test_estimate_all_with_custom_strategies() {
+        let samples: Vec<(u64, u64)> = (1..=100).map(|fee| (fee, fee)).collect();
+        let rpc = MockFeeRpc::new(&samples, 100);
+        let estimator = mock_estimator(&rpc);
+
+        let extra = [FeeStrategy::Custom(99), FeeStrategy::Custom(40)];
+        let estimates = estimator.estimate_all_with(&extra).unwrap();
+        assert_eq!(estimates.len(), FeeStrategy::ALL.len() + extra.len());
+        assert_eq!(estimates[&FeeStrategy::Custom(99)].recommended_fee, 100);
+        assert_eq!(estimates[&FeeStrategy::Custom(40)].recommended_fee, 41);
+        assert_eq!(estimates[&FeeStrategy::Fast].recommended_fee, 76);
+        for estimate in estimates.values() {
+            assert_eq!(estimate.slots_sampled, 100);
+        }
+        assert_eq!(rpc.requested_accounts.lock().unwrap().len(), 1);
+
+        // A preset passed again is estimated once
+        let estimates = estimator.estimate_all_with(&[FeeStrategy::Fast]).unwrap();
+        assert_eq!(estimates.len(), FeeStrategy::ALL.len());
+    }
+
+    #[test]
+    fn test_scoped_accounts_are_requested() {
+        let program = Pubkey::new_unique();
+        let rpc = MockFeeRpc::new(&[(1, 100)], 100);
+
+        let unscoped = mock_estimator(&rpc).estimate_all().unwrap();
+        assert!(!unscoped[&FeeStrategy::Standard].scoped);
+        assert!(unscoped[&FeeStrategy::Standard].scoped_accounts.is_empty());
+        let scoped = mock_estimator(&rpc)
+            .with_scoped_accounts(vec![program])
+            .estimate(FeeStrategy::Standard)
+            .unwrap();
+        assert!(scoped.scoped);
+        assert_eq!(scoped.scoped_accounts, vec![program.to_string()]);
+        assert_eq!(
+            *rpc.requested_accounts.lock().unwrap(),
+            vec![vec![], vec![program]]
+        );
+
+        assert_eq!(fee_request_params(&[]), serde_json::json!([]));
+        assert_eq!(
+            fee_request_params(&[program]),
+            serde_json::json!([[program.to_string()]])
+        );
+    }
+
+    /// Serves a fee of `100 * account[0]` per account, fails for `failing`,
+    /// and records the most `recent_fees` calls in flight at once.
+    struct PerAccountRpc {
+        failing: Pubkey,
+        in_flight: std::sync::atomic::AtomicUsize,
+        max_in_flight: std::sync::atomic::AtomicUsize,
+    }
+
+    impl FeeRpc for PerAccountRpc {
+        fn recent_fees(&self, accounts: &[Pubkey]) -> Result<Vec<PrioritizationFeeEntry>> {
+            use std::sync::atomic::Ordering;
+
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(20));
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            let account = accounts[0];
+            if account == self.failing {
+                return Err(TxOptimizerError::Rpc("account is rate limited".to_string()));
+            }
+            Ok(vec![PrioritizationFeeEntry {
+                slot: 1,
+                prioritization_fee: 100 * account.to_bytes()[0] as u64,
+            }])
+        }
+
+        fn slot(&self, _commitment: CommitmentConfig) -> Result<u64> {
+            Ok(100)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_estimate_per_account() {
+        let accounts: Vec<Pubkey> = (1..=5).map(|i| Pubkey::new_from_array([i; 32])).collect();
+        let rpc = Arc::new(PerAccountRpc {
+            failing: accounts[2],
+            in_flight: Default::default(),
+            max_in_flight: Default::default(),
+        });
+        let estimator = PriorityFeeEstimator::new("http://localhost:8899")
+            .with_rpc(rpc.clone())
+            .with_per_account_concurrency(2)
+            .unwrap();
+
+        let result = estimator
+            .estimate_per_account(&accounts, FeeStrategy::Standard)
+            .await;
+        let fees: Vec<(Pubkey, u64)> = result
+            .estimates
+            .iter()
+            .map(|(account, estimate)| (*account, estimate.recommended_fee))
+            .collect();
+        assert_eq!(
+            fees,
+            vec![
+                (accounts[0], 100),
+                (accounts[1], 200),
+                (accounts[3], 400),
+                (accounts[4], 500),
+            ]
+        );
+        let scope = &result.estimates[0].1.scoped_accounts;
+        assert_eq!(*scope, vec![accounts[0].to_string()]);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].0, accounts[2]);
+        assert!(result.errors[0].1.contains("rate limited"));
+        let max_in_flight = rpc.max_in_flight.load(std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(max_in_flight, 2);
+
+        assert!(matches!(
+            estimator.with_per_account_concurrency(0),
+            Err(TxOptimizerError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_fee_strategy_display() {
+        assert_eq!(FeeStrategy::Turbo.to_string(), "Turbo (p90)");
+        assert_eq!(FeeStrategy::Economy.percentile(), 25);
+    }
+
+    #[test]
+    fn test_strategy_map() {
+        let default = StrategyMap::default();
+        for strategy in FeeStrategy::ALL {
+            assert_eq!(default.percentile(strategy), strategy.percentile());
+        }
+
+        let map: StrategyMap = "25, 50, 85, 95".parse().unwrap();
+        assert_eq!(map.percentile(FeeStrategy::Fast), 85);
+        let fees: Vec<u64> = (1..=100).collect();
+        let estimates = PriorityFeeEstimator::estimates_from_sorted(
+            &fees,
+            &FeeStrategy::ALL,
+            &map,
+            CommitmentLevel::Confirmed,
+        );
+        assert_eq!(estimates[&FeeStrategy::Fast].recommended_fee, 86);
+        assert_eq!(estimates[&FeeStrategy::Turbo].recommended_fee, 96);
+
+        assert!("25,50,75".parse::<StrategyMap>().is_err());
+        assert!("25,50,75,fast".parse::<StrategyMap>().is_err());
+        assert!("25,50,75,101".parse::<StrategyMap>().is_err());
+        assert!("25,75,50,90".parse::<StrategyMap>().is_err());
+        assert!("0,0,100,100".parse::<StrategyMap>().is_ok());
+        let estimator = PriorityFeeEstimator::new("http://localhost:8899");
+        assert!(matches!(
+            estimator.with_strategy_map(StrategyMap {
+                turbo: 20,
+                ..StrategyMap::default()
+            }),
+            Err(TxOptimizerError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_fee_strategy_from_str() {
+        assert_eq!("fast".parse::<FeeStrategy>().unwrap(), FeeStrategy::Fast);
+        assert_eq!("TURBO".parse::<FeeStrategy>().unwrap(), FeeStrategy::Turbo);
+        assert_eq!("Economy".parse::<FeeStrategy>().unwrap(), FeeStrategy::Economy);
+        for strategy in FeeStrategy::ALL {
+            let name = format!("{strategy:?}");
+            assert_eq!(name.parse::<FeeStrategy>().unwrap(), strategy);
+        }
+
+        let err = "fastest".parse::<FeeStrategy>().unwrap_err();
+        assert!(matches!(err, TxOptimizerError::Config(_)));
+        assert!(err.to_string().contains("economy, standard, fast, turbo"));
+    }
+
+    #[test]
+    fn test_fee_strategy_from_str_custom() {
+        let strategy: FeeStrategy = "custom:95".parse().unwrap();
+        assert_eq!(strategy, FeeStrategy::Custom(95));
+        assert_eq!(strategy.percentile(), 95);
+        assert_eq!(
+            "Custom:0".parse::<FeeStrategy>().unwrap(),
+            FeeStrategy::Custom(0)
+        );
+        for invalid in ["custom:101", "custom:", "custom:-5", "custom:p90"] {
+            let err = invalid.parse::<FeeStrategy>().unwrap_err();
+            assert!(matches!(err, TxOptimizerError::Config(_)), "{invalid}");
+        }
+
+        // A strategy map remaps the presets but not a custom percentile.
+        let map: StrategyMap = "10, 20, 30, 40".parse().unwrap();
+        assert_eq!(map.percentile(strategy), 95);
+        let fees: Vec<u64> = (1..=100).collect();
+        let estimator = PriorityFeeEstimator::new("http://localhost:8899")
+            .with_strategy_map(map)
+            .unwrap();
+        let estimate = estimator.estimate_from_samples(&fees, strategy);
+        assert_eq!(estimate.recommended_fee, 96);
     }
 }
@@ -0,0 +1,662 @@
+use serde::{Deserialize, Serialize};
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_client::RpcClient,
+    rpc_config::{RpcSendTransactionConfig, RpcSimulateTransactionConfig},
+};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::{signers::Signers, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use crate::config::{Config, DEFAULT_COMPUTE_UNIT_LIMIT, DEFAULT_PRIORITY_FEE_MICROLAMPORTS};
+use crate::error::{Result, TxOptimizerError};
+use crate::priority_fee::{
+    build_compute_unit_limit_instruction, build_heap_frame_instruction,
+    build_priority_fee_instruction, FeeEstimate, FeeStrategy, PriorityFeeEstimator,
+};
+
+/// How often to poll the signature status while waiting for confirmation.
+const CONFIRM_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Compute unit limit for [`prioritized_transfer`]: the system transfer and
+/// both compute-budget instructions use 150 CU each, plus headroom.
+const TRANSFER_COMPUTE_UNIT_LIMIT: u32 = 1_000;
+
+/// Assembles a signed transaction with compute-budget instructions prepended
+/// to the payload.
+///
+/// The priority fee defaults to [`DEFAULT_PRIORITY_FEE_MICROLAMPORTS`] and the
+/// compute unit limit to [`DEFAULT_COMPUTE_UNIT_LIMIT`] unless overridden.
+/// Heavy transactions can also request a larger heap with
+/// [`with_heap_frame`](Self::with_heap_frame).
+///
+/// # Example
+/// ```no_run
+/// use solana_client::rpc_client::RpcClient;
+/// use solana_sdk::{signature::Keypair, signer::Signer, system_instruction};
+/// use solana_tx_optimizer::priority_fee::{FeeStrategy, PriorityFeeEstimator};
+/// use solana_tx_optimizer::tx::PriorityTransactionBuilder;
+///
+/// let client = RpcClient::new("https://api.mainnet-beta.solana.com".to_string());
+/// let estimator = PriorityFeeEstimator::new("https://api.mainnet-beta.solana.com");
+/// let payer = Keypair::new();
+/// let ix = system_instruction::transfer(&payer.pubkey(), &payer.pubkey(), 1);
+///
+/// let tx = PriorityTransactionBuilder::new(&client, payer.pubkey())
+///     .with_instructions(vec![ix])
+///     .with_estimated_fee(&estimator, FeeStrategy::Fast)
+///     .unwrap()
+///     .with_compute_unit_limit(50_000)
+///     .build(&[&payer])
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct PriorityTransactionBuilder<'a> {
+    client: &'a RpcClient,
+    payer: Pubkey,
+    instructions: Vec<Instruction>,
+    microlamports_per_cu: u64,
+    compute_unit_limit: u32,
+    heap_frame: Option<Instruction>,
+}
+
+impl<'a> PriorityTransactionBuilder<'a> {
+    /// Start a transaction paid by `payer`; `client` supplies the blockhash.
+    pub fn new(client: &'a RpcClient, payer: Pubkey) -> Self {
+        Self {
+            client,
+            payer,
+            instructions: Vec::new(),
+            microlamports_per_cu: DEFAULT_PRIORITY_FEE_MICROLAMPORTS,
+            compute_unit_limit: DEFAULT_COMPUTE_UNIT_LIMIT,
+            heap_frame: None,
+        }
+    }
+
+    /// Set the payload instructions, executed in order after the
+    /// compute-budget instructions.
+    pub fn with_instructions(mut self, instructions: Vec<Instruction>) -> Self {
+        self.instructions = instructions;
+        self
+    }
+
+    /// Use an explicit priority fee in microlamports per compute unit.
+    pub fn with_priority_fee(mut self, microlamports_per_cu: u64) -> Self {
+        self.microlamports_per_cu = microlamports_per_cu;
+        self
+    }
+
+    /// Use the recommended fee from an existing estimate.
+    pub fn with_fee_estimate(self, estimate: &FeeEstimate) -> Self {
+        self.with_priority_fee(estimate.recommended_fee)
+    }
+
+    /// Estimate the fee now with `estimator` and use its recommendation.
+    pub fn with_estimated_fee(
+        self,
+        estimator: &PriorityFeeEstimator,
+        strategy: FeeStrategy,
+    ) -> Result<Self> {
+        let estimate = estimator.estimate(strategy)?;
+        Ok(self.with_fee_estimate(&estimate))
+    }
+
+    /// Set the compute unit limit.
+    pub fn with_compute_unit_limit(mut self, units: u32) -> Self {
+        self.compute_unit_limit = units;
+        self
+    }
+
+    /// Request a heap frame of `bytes`; see
+    /// [`build_heap_frame_instruction`] for the accepted sizes.
+    pub fn with_heap_frame(mut self, bytes: u32) -> Result<Self> {
+        self.heap_frame = Some(build_heap_frame_instruction(bytes)?);
+        Ok(self)
+    }
+
+    /// The full instruction list: CU limit, CU price, the heap frame request
+    /// if any, then the payload.
+    pub fn instructions(&self) -> Vec<Instruction> {
+        let mut instructions = Vec::with_capacity(self.instructions.len() + 3);
+        instructions.push(build_compute_unit_limit_instruction(self.compute_unit_limit));
+        instructions.push(build_priority_fee_instruction(self.microlamports_per_cu));
+        instructions.extend(self.heap_frame.iter().cloned());
+        instructions.extend(self.instructions.iter().cloned());
+        instructions
+    }
+
+    /// Fetch a fresh blockhash and sign the assembled transaction.
+    ///
+    /// `signers` must include the payer and any other required signers.
+    pub fn build<T: Signers + ?Sized>(&self, signers: &T) -> Result<Transaction> {
+        let blockhash = self.client.get_latest_blockhash()?;
+        let mut tx = Transaction::new_with_payer(&self.instructions(), Some(&self.payer));
+        tx.try_sign(signers, blockhash)?;
+        debug!(
+            "Built priority transaction: {} payload instruction(s), {} microlamports/CU, {} CU limit",
+            self.instructions.len(),
+            self.microlamports_per_cu,
+            self.compute_unit_limit
+        );
+        Ok(tx)
+    }
+}
+
+/// Outcome of simulating a transaction with [`simulate`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulationSummary {
+    pub success: bool,
+    /// Compute units the simulation consumed, if the node reported it
+    pub units_consumed: Option<u64>,
+    pub logs: Vec<String>,
+    /// Why the simulation failed; `None` on success
+    pub err: Option<String>,
+}
+
+/// Simulate `tx` at the client's commitment without sending it.
+///
+/// Signatures are not verified and the node substitutes its latest
+/// blockhash, so `tx` may be unsigned. A transaction that would fail is
+/// still `Ok`, with `success` unset; only RPC errors are returned as `Err`.
+///
+/// # Example
+/// ```no_run
+/// use solana_client::rpc_client::RpcClient;
+/// use solana_sdk::{signature::Keypair, signer::Signer, system_instruction, transaction::Transaction};
+/// use solana_tx_optimizer::tx;
+///
+/// let client = RpcClient::new("https://api.mainnet-beta.solana.com".to_string());
+/// let payer = Keypair::new();
+/// let ix = system_instruction::transfer(&payer.pubkey(), &payer.pubkey(), 1);
+/// let tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+/// let summary = tx::simulate(&client, &tx).unwrap();
+/// println!("{} ({:?} CU)", summary.success, summary.units_consumed);
+/// ```
+pub fn simulate(client: &RpcClient, tx: &Transaction) -> Result<SimulationSummary> {
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        commitment: Some(client.commitment()),
+        ..RpcSimulateTransactionConfig::default()
+    };
+    let result = client.simulate_transaction_with_config(tx, config)?.value;
+    debug!(
+        "Simulation consumed {:?} CU: {:?}",
+        result.units_consumed, result.err
+    );
+
+    Ok(SimulationSummary {
+        success: result.err.is_none(),
+        units_consumed: result.units_consumed,
+        logs: result.logs.unwrap_or_default(),
+        err: result.err.map(|e| e.to_string()),
+    })
+}
+
+/// Send a transaction and wait until it reaches the config's commitment.
+///
+/// Retries up to `cfg.max_retries` times with exponential backoff when the
+/// blockhash expires or the RPC request times out. Each attempt resends the
+/// same transaction while its blockhash is still valid, and otherwise fetches
+/// a fresh blockhash and re-signs with `signers`, which must cover every
+/// required signature of `tx` (so `tx` may be passed unsigned). Any other
+/// send error, or the transaction failing on-chain, is returned immediately;
+/// running out of attempts yields [`TxOptimizerError::RetriesExhausted`].
+///
+/// With `cfg.simulate_first`, `tx` is [simulated](simulate) once before the
+/// first attempt and a failing simulation aborts with
+/// [`TxOptimizerError::SimulationFailed`], so nothing is sent.
+///
+/// # Example
+/// ```no_run
+/// use solana_client::rpc_client::RpcClient;
+/// use solana_sdk::{signature::Keypair, signer::Signer, system_instruction, transaction::Transaction};
+/// use solana_tx_optimizer::{config::Config, tx};
+///
+/// let config = Config::from_env();
+/// let client = RpcClient::new(config.rpc_url.clone());
+/// let payer = Keypair::new();
+/// let ix = system_instruction::transfer(&payer.pubkey(), &payer.pubkey(), 1);
+/// let tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+/// let signature = tx::send_and_confirm(&client, &tx, &[&payer], &config).unwrap();
+/// println!("Confirmed: {signature}");
+/// ```
+pub fn send_and_confirm<T: Signers + ?Sized>(
+    client: &RpcClient,
+    tx: &Transaction,
+    signers: &T,
+    cfg: &Config,
+) -> Result<Signature> {
+    let commitment: CommitmentConfig = cfg.commitment.parse().map_err(|_| {
+        TxOptimizerError::Config(format!("Invalid commitment level: {}", cfg.commitment))
+    })?;
+    let send_config = RpcSendTransactionConfig {
+        preflight_commitment: Some(commitment.commitment),
+        ..RpcSendTransactionConfig::default()
+    };
+
+    if cfg.simulate_first {
+        let summary = simulate(client, tx)?;
+        if let Some(reason) = summary.err {
+            warn!("Simulation failed, not sending: {reason}");
+            return Err(TxOptimizerError::SimulationFailed {
+                reason,
+                logs: summary.logs,
+            });
+        }
+    }
+
+    let mut tx = tx.clone();
+    let mut last_signature = None;
+    let mut last_error = String::from("no attempts made");
+
+    for attempt in 1..=cfg.max_retries {
+        // A timed-out send may still have landed; never re-sign a
+        // transaction that could already be on-chain.
+        if let (true, Some(&previous)) = (attempt > 1, tx.signatures.first()) {
+            if let Ok(Some(Ok(()))) =
+                client.get_signature_status_with_commitment(&previous, commitment)
+            {
+                info!("Transaction {previous} confirmed");
+                return Ok(previous);
+            }
+        }
+
+        let blockhash_valid = client
+            .is_blockhash_valid(&tx.message.recent_blockhash, commitment)
+            .unwrap_or(false);
+        if !blockhash_valid {
+            let blockhash = client.get_latest_blockhash_with_commitment(commitment)?.0;
+            tx.try_sign(signers, blockhash)?;
+            debug!("Re-signed transaction with blockhash {blockhash}");
+        }
+
+        info!("Sending transaction (attempt {}/{})", attempt, cfg.max_retries);
+        match client.send_transaction_with_config(&tx, send_config) {
+            Ok(signature) => {
+                last_signature = Some(signature);
+                if wait_for_confirmation(client, &tx, &signature, commitment)? {
+                    info!("Transaction {signature} confirmed");
+                    return Ok(signature);
+                }
+                warn!("Blockhash expired before {signature} confirmed");
+                last_error = "blockhash expired before confirmation".to_string();
+            }
+            Err(e) if is_retryable(&e) => {
+                warn!("Send failed (retryable): {e}");
+                last_error = e.to_string();
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        // Exponential backoff: 100ms, 200ms, 400ms, ...
+        if attempt < cfg.max_retries {
+            let backoff = Duration::from_millis(100 * 2u64.pow((attempt - 1) as u32));
+            debug!("Retrying in {}ms", backoff.as_millis());
+            std::thread::sleep(backoff);
+        }
+    }
+
+    Err(TxOptimizerError::RetriesExhausted {
+        last_signature,
+        attempts: cfg.max_retries,
+        last_error,
+    })
+}
+
+/// Send a priority transaction, raising its fee on every retry until it lands.
+///
+/// Attempt `n` (counting from 0) bids
+/// [`estimator.escalated_fee(base, n, factor)`](PriorityFeeEstimator::escalated_fee),
+/// so the fee grows by `factor` each time and never exceeds the estimator's
+/// max priority fee. Each attempt is built from `builder` with a fresh
+/// blockhash and signed with `signers`.
+///
+/// A new, higher-fee transaction is only sent once the previous one can no
+/// longer land (its blockhash has expired), so at most one of them is ever
+/// executed. Up to `cfg.max_retries` attempts are made; errors are handled as
+/// in [`send_and_confirm`]. Fails before sending anything if `factor` is
+/// rejected by `escalated_fee`.
+///
+/// # Example
+/// ```no_run
+/// use solana_client::rpc_client::RpcClient;
+/// use solana_sdk::{signature::Keypair, signer::Signer, system_instruction};
+/// use solana_tx_optimizer::{config::Config, tx};
+/// use solana_tx_optimizer::priority_fee::{FeeStrategy, PriorityFeeEstimator};
+/// use solana_tx_optimizer::tx::PriorityTransactionBuilder;
+///
+/// let config = Config::from_env();
+/// let client = RpcClient::new(config.rpc_url.clone());
+/// let estimator = PriorityFeeEstimator::new(&config.rpc_url)
+///     .with_max_priority_fee(config.max_priority_fee);
+/// let payer = Keypair::new();
+/// let ix = system_instruction::transfer(&payer.pubkey(), &payer.pubkey(), 1);
+///
+/// let base = estimator.estimate(FeeStrategy::Standard).unwrap();
+/// let builder =
+///     PriorityTransactionBuilder::new(&client, payer.pubkey()).with_instructions(vec![ix]);
+/// let signature =
+///     tx::send_with_escalation(&builder, &estimator, &base, 1.5, &[&payer], &config).unwrap();
+/// println!("Confirmed: {signature}");
+/// ```
+pub fn send_with_escalation<T: Signers + ?Sized>(
+    builder: &PriorityTransactionBuilder<'_>,
+    estimator: &PriorityFeeEstimator,
+    base: &FeeEstimate,
+    factor: f64,
+    signers: &T,
+    cfg: &Config,
+) -> Result<Signature> {
+    let commitment: CommitmentConfig = cfg.commitment.parse().map_err(|_| {
+        TxOptimizerError::Config(format!("Invalid commitment level: {}", cfg.commitment))
+    })?;
+    let send_config = RpcSendTransactionConfig {
+        preflight_commitment: Some(commitment.commitment),
+        ..RpcSendTransactionConfig::default()
+    };
+    let client = builder.client;
+
+    let mut last_signature = None;
+    let mut last_error = String::from("no attempts made");
+
+    for attempt in 0..cfg.max_retries {
+        let fee = estimator.escalated_fee(base, attempt, factor)?;
+        let tx = builder.clone().with_priority_fee(fee).build(signers)?;
+
+        info!(
+            "Sending transaction at {} microlamports/CU (attempt {}/{})",
+            fee,
+            attempt + 1,
+            cfg.max_retries
+        );
+        let signature = match client.send_transaction_with_config(&tx, send_config) {
+            Ok(signature) => signature,
+            // The node never accepted it, so it cannot land later
+            Err(e) if e.get_transaction_error() == Some(TransactionError::BlockhashNotFound) => {
+                warn!("Send failed (retryable): {e}");
+                last_error = e.to_string();
+                continue;
+            }
+            // A timed-out send may still land; wait it out before bidding higher
+            Err(e) if is_retryable(&e) => {
+                warn!("Send failed (retryable): {e}");
+                last_error = e.to_string();
+                match tx.signatures.first() {
+                    Some(&signature) => signature,
+                    None => continue,
+                }
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        last_signature = Some(signature);
+        if wait_for_confirmation(client, &tx, &signature, commitment)? {
+            info!("Transaction {signature} confirmed at {fee} microlamports/CU");
+            return Ok(signature);
+        }
+        warn!("Blockhash expired before {signature} confirmed");
+        last_error = "blockhash expired before confirmation".to_string();
+    }
+
+    Err(TxOptimizerError::RetriesExhausted {
+        last_signature,
+        attempts: cfg.max_retries,
+        last_error,
+    })
+}
+
+/// Send `lamports` SOL from `payer` to `to` at an estimated priority fee and
+/// wait for confirmation.
+///
+/// The fee is estimated for `strategy` from the node behind `client`, at the
+/// client's commitment, and the compute unit limit is sized for a plain
+/// transfer. Sending and retrying work as in [`send_and_confirm`] with the
+/// default [`Config`] retry settings; build the transaction with
+/// [`PriorityTransactionBuilder`] for more control.
+///
+/// # Example
+/// ```no_run
+/// use solana_client::rpc_client::RpcClient;
+/// use solana_sdk::{pubkey::Pubkey, signature::Keypair};
+/// use solana_tx_optimizer::{priority_fee::FeeStrategy, tx};
+///
+/// let client = RpcClient::new("https://api.mainnet-beta.solana.com".to_string());
+/// let payer = Keypair::new();
+/// let to = Pubkey::new_unique();
+/// let signature =
+///     tx::prioritized_transfer(&client, &payer, &to, 1_000_000, FeeStrategy::Fast).unwrap();
+/// println!("Confirmed: {signature}");
+/// ```
+pub fn prioritized_transfer(
+    client: &RpcClient,
+    payer: &Keypair,
+    to: &Pubkey,
+    lamports: u64,
+    strategy: FeeStrategy,
+) -> Result<Signature> {
+    let commitment = client.commitment().commitment.to_string();
+    let estimator = PriorityFeeEstimator::new(&client.url()).with_commitment(&commitment)?;
+    let cfg = Config {
+        commitment,
+        ..Config::default()
+    };
+    transfer_with_estimator(client, &estimator, payer, to, lamports, strategy, &cfg)
+}
+
+fn transfer_with_estimator(
+    client: &RpcClient,
+    estimator: &PriorityFeeEstimator,
+    payer: &Keypair,
+    to: &Pubkey,
+    lamports: u64,
+    strategy: FeeStrategy,
+    cfg: &Config,
+) -> Result<Signature> {
+    let transfer = system_instruction::transfer(&payer.pubkey(), to, lamports);
+    let tx = PriorityTransactionBuilder::new(client, payer.pubkey())
+        .with_instructions(vec![transfer])
+        .with_estimated_fee(estimator, strategy)?
+        .with_compute_unit_limit(TRANSFER_COMPUTE_UNIT_LIMIT)
+        .build(&[payer])?;
+    send_and_confirm(client, &tx, &[payer], cfg)
+}
+
+/// Poll until `signature` reaches `commitment`.
+///
+/// Returns `Ok(false)` once the transaction's blockhash is no longer valid,
+/// since it can then never land and must be re-signed.
+fn wait_for_confirmation(
+    client: &RpcClient,
+    tx: &Transaction,
+    signature: &Signature,
+    commitment: CommitmentConfig,
+) -> Result<bool> {
+    loop {
+        match client.get_signature_status_with_commitment(signature, commitment) {
+            Ok(Some(Ok(()))) => return Ok(true),
+            Ok(Some(Err(e))) => {
+                return Err(TxOptimizerError::TransactionFailed {
+                    signature: *signature,
+                    reason: e.to_string(),
+                })
+            }
+            Ok(None) => {}
+            Err(e) if is_retryable(&e) => debug!("Status check failed: {e}"),
+            Err(e) => return Err(e.into()),
+        }
+
+        match client.is_blockhash_valid(&tx.message.recent_blockhash, commitment) {
+            Ok(false) => return Ok(false),
+            Ok(true) => {}
+            Err(e) => debug!("Blockhash validity check failed: {e}"),
+        }
+
+        std::thread::sleep(CONFIRM_POLL_INTERVAL);
+    }
+}
+
+/// Whether a send error is worth retrying with a fresh blockhash.
+fn is_retryable(error: &ClientError) -> bool {
+    if error.get_transaction_error() == Some(TransactionError::BlockhashNotFound) {
+        return true;
+    }
+    match error.kind() {
+        ClientErrorKind::Reqwest(e) => e.is_timeout() || e.is_connect(),
+        ClientErrorKind::Io(_) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::priority_fee::{FeeRpc, PrioritizationFeeEntry};
+
+    #[test]
+    fn test_priority_transaction_builder() {
+        let client = RpcClient::new_mock("succeeds".to_string());
+        let payer = Keypair::new();
+        let transfer = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+
+        let builder = PriorityTransactionBuilder::new(&client, payer.pubkey())
+            .with_instructions(vec![transfer.clone()])
+            .with_priority_fee(25_000)
+            .with_compute_unit_limit(50_000);
+
+        let instructions = builder.instructions();
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(instructions[0], build_compute_unit_limit_instruction(50_000));
+        assert_eq!(instructions[1], build_priority_fee_instruction(25_000));
+        assert_eq!(instructions[2], transfer);
+
+        let tx = builder.build(&[&payer]).unwrap();
+        assert_eq!(tx.message.account_keys[0], payer.pubkey());
+        assert!(tx.is_signed());
+        tx.verify().unwrap();
+
+        let instructions = builder.with_heap_frame(64 * 1024).unwrap().instructions();
+        assert_eq!(instructions.len(), 4);
+        assert_eq!(instructions[2], build_heap_frame_instruction(64 * 1024).unwrap());
+        assert_eq!(instructions[3], transfer);
+    }
+
+    #[test]
+    fn test_simulate_first_aborts_on_failure() {
+        let payer = Keypair::new();
+        let transfer = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        let tx = Transaction::new_with_payer(&[transfer], Some(&payer.pubkey()));
+
+        let client = RpcClient::new_mock("succeeds".to_string());
+        let summary = simulate(&client, &tx).unwrap();
+        assert!(summary.success);
+        assert_eq!(summary.err, None);
+
+        let mut mocks = std::collections::HashMap::new();
+        mocks.insert(
+            solana_client::rpc_request::RpcRequest::SimulateTransaction,
+            serde_json::json!({
+                "context": { "slot": 1 },
+                "value": {
+                    "err": "InsufficientFundsForFee",
+                    "logs": ["Program 11111111111111111111111111111111 failed"],
+                    "unitsConsumed": 150,
+                },
+            }),
+        );
+        // The send would succeed, so an error can only come from the simulation
+        let client = RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks);
+        let cfg = Config {
+            simulate_first: true,
+            ..Config::default()
+        };
+        match send_and_confirm(&client, &tx, &[&payer], &cfg).unwrap_err() {
+            TxOptimizerError::SimulationFailed { reason, logs } => {
+                assert_eq!(
+                    reason,
+                    TransactionError::InsufficientFundsForFee.to_string()
+                );
+                assert_eq!(logs.len(), 1);
+            }
+            other => panic!("expected a simulation failure, got {other:?}"),
+        }
+    }
+
+    /// Serves the same fee in every recent slot.
+    struct FlatFeeRpc(u64);
+
+    impl FeeRpc for FlatFeeRpc {
+        fn recent_fees(&self, _accounts: &[Pubkey]) -> Result<Vec<PrioritizationFeeEntry>> {
+            Ok((1..=20)
+                .map(|slot| PrioritizationFeeEntry {
+                    slot,
+                    prioritization_fee: self.0,
+                })
+                .collect())
+        }
+
+        fn slot(&self, _commitment: CommitmentConfig) -> Result<u64> {
+            Ok(20)
+        }
+    }
+
+    #[test]
+    fn test_prioritized_transfer() {
+        let client = RpcClient::new_mock("succeeds".to_string());
+        let estimator = PriorityFeeEstimator::new("http://localhost:8899")
+            .with_rpc(std::sync::Arc::new(FlatFeeRpc(4_000)));
+        let payer = Keypair::new();
+        let to = Pubkey::new_unique();
+
+        let signature = transfer_with_estimator(
+            &client,
+            &estimator,
+            &payer,
+            &to,
+            5_000,
+            FeeStrategy::Fast,
+            &Config::default(),
+        )
+        .unwrap();
+        assert_ne!(signature, Signature::default());
+
+        let expected = PriorityTransactionBuilder::new(&client, payer.pubkey())
+            .with_instructions(vec![system_instruction::transfer(
+                &payer.pubkey(),
+                &to,
+                5_000,
+            )])
+            .with_priority_fee(4_000)
+            .with_compute_unit_limit(TRANSFER_COMPUTE_UNIT_LIMIT)
+            .build(&[&payer])
+            .unwrap();
+        assert_eq!(signature, expected.signatures[0]);
+    }
+
+    #[test]
+    fn test_blockhash_not_found_is_retryable() {
+        let error = ClientError::from(ClientErrorKind::TransactionError(
+            TransactionError::BlockhashNotFound,
+        ));
+        assert!(is_retryable(&error));
+    }
+
+    #[test]
+    fn test_other_errors_not_retryable() {
+        let error = ClientError::from(ClientErrorKind::TransactionError(
+            TransactionError::InsufficientFundsForFee,
+        ));
+        assert!(!is_retryable(&error));
+        assert!(!is_retryable(&ClientError::from(ClientErrorKind::Custom(
+            "bad request".to_string()
+        ))));
+    }
+}
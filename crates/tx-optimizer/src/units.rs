@@ -0,0 +1,84 @@
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+/// Convert lamports to SOL.
+///
+/// Amounts above 2^53 lamports lose precision in the `f64`; use [`Lamports`]
+/// for exact display.
+pub fn lamports_to_sol(lamports: u64) -> f64 {
+    lamports as f64 / LAMPORTS_PER_SOL as f64
+}
+
+/// Convert SOL to lamports, rounding to the nearest lamport.
+///
+/// Negative and NaN inputs become 0; values too large for a `u64` saturate
+/// at `u64::MAX`.
+pub fn sol_to_lamports(sol: f64) -> u64 {
+    (sol * LAMPORTS_PER_SOL as f64).round() as u64
+}
+
+/// A lamport amount that displays as both SOL and lamports,
+/// e.g. `0.00001 SOL (10000 lamports)`.
+///
+/// The SOL part is computed with integer math, so it is exact for any `u64`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Lamports(pub u64);
+
+impl From<u64> for Lamports {
+    fn from(lamports: u64) -> Self {
+        Self(lamports)
+    }
+}
+
+impl std::fmt::Display for Lamports {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let whole = self.0 / LAMPORTS_PER_SOL;
+        let frac = self.0 % LAMPORTS_PER_SOL;
+        if frac == 0 {
+            write!(f, "{whole} SOL ({} lamports)", self.0)
+        } else {
+            let frac = format!("{frac:09}");
+            write!(f, "{whole}.{} SOL ({} lamports)", frac.trim_end_matches('0'), self.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lamports_display() {
+        assert_eq!(Lamports(10_000).to_string(), "0.00001 SOL (10000 lamports)");
+        assert_eq!(Lamports(1).to_string(), "0.000000001 SOL (1 lamports)");
+        assert_eq!(Lamports(0).to_string(), "0 SOL (0 lamports)");
+        assert_eq!(
+            Lamports(2 * LAMPORTS_PER_SOL).to_string(),
+            "2 SOL (2000000000 lamports)"
+        );
+        assert_eq!(
+            Lamports(u64::MAX).to_string(),
+            "18446744073.709551615 SOL (18446744073709551615 lamports)"
+        );
+    }
+
+    #[test]
+    fn test_sol_to_lamports_rounding() {
+        assert_eq!(sol_to_lamports(0.000000001), 1);
+        assert_eq!(sol_to_lamports(0.0000000004), 0);
+        assert_eq!(sol_to_lamports(0.0000000006), 1);
+        // 0.1 is not exactly representable; rounding keeps it at 1e8
+        assert_eq!(sol_to_lamports(0.1), 100_000_000);
+        assert_eq!(sol_to_lamports(-1.0), 0);
+        assert_eq!(sol_to_lamports(f64::NAN), 0);
+        assert_eq!(sol_to_lamports(1e12), u64::MAX);
+    }
+
+    #[test]
+    fn test_lamports_to_sol() {
+        assert_eq!(lamports_to_sol(10_000), 0.00001);
+        assert_eq!(lamports_to_sol(1), 1e-9);
+        assert_eq!(lamports_to_sol(LAMPORTS_PER_SOL), 1.0);
+        assert_eq!(sol_to_lamports(lamports_to_sol(123_456_789)), 123_456_789);
+        assert!((lamports_to_sol(u64::MAX) - 18_446_744_073.709_55).abs() < 1e-3);
+    }
+}
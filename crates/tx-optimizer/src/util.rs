@@ -0,0 +1,158 @@
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+use crate::error::{Result, TxOptimizerError};
+
+/// Call `f` every `interval` until it returns `Ok(Some(value))`.
+///
+/// `f` runs once immediately and then after each interval. Returns
+/// `Ok(None)` once `timeout` has elapsed without a value; an `Err` from `f`
+/// stops polling and is returned as is, so callers that want to ride out
+/// transient failures should log them and return `Ok(None)` instead.
+///
+/// # Example
+/// ```no_run
+/// use std::time::Duration;
+/// use solana_tx_optimizer::util::poll_until;
+///
+/// # async fn run() -> solana_tx_optimizer::error::Result<()> {
+/// let mut polls = 0;
+/// let value = poll_until(Duration::from_millis(500), Duration::from_secs(10), || {
+///     polls += 1;
+///     let done = polls == 3;
+///     async move { Ok(done.then_some(polls)) }
+/// })
+/// .await?;
+/// assert_eq!(value, Some(3));
+/// # Ok(())
+/// # }
+/// ```
+pub async fn poll_until<F, Fut, T>(interval: Duration, timeout: Duration, f: F) -> Result<Option<T>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Option<T>>>,
+{
+    poll_until_cancelled(interval, timeout, &CancellationToken::new(), f).await
+}
+
+/// [`poll_until`] that also stops when `cancel` is cancelled.
+///
+/// Cancellation interrupts both the wait between polls and a poll in
+/// progress, and returns [`TxOptimizerError::Cancelled`].
+pub async fn poll_until_cancelled<F, Fut, T>(
+    interval: Duration,
+    timeout: Duration,
+    cancel: &CancellationToken,
+    mut f: F,
+) -> Result<Option<T>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Option<T>>>,
+{
+    let start = Instant::now();
+    loop {
+        let polled = tokio::select! {
+            biased;
+            _ = cancel.cancelled() => return Err(TxOptimizerError::Cancelled),
+            polled = f() => polled?,
+        };
+        if polled.is_some() {
+            return Ok(polled);
+        }
+
+        let remaining = timeout.saturating_sub(start.elapsed());
+        if remaining.is_zero() {
+            return Ok(None);
+        }
+        tokio::select! {
+            biased;
+            _ = cancel.cancelled() => return Err(TxOptimizerError::Cancelled),
+            _ = tokio::time::sleep(interval.min(remaining)) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    const INTERVAL: Duration = Duration::from_millis(1);
+
+    #[tokio::test]
+    async fn test_poll_until_stops_on_value() {
+        let calls = AtomicU32::new(0);
+        let value = poll_until(INTERVAL, Duration::from_secs(5), || async {
+            let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok((call == 3).then_some(call))
+        })
+        .await
+        .unwrap();
+        assert_eq!(value, Some(3));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_poll_until_times_out() {
+        let calls = AtomicU32::new(0);
+        let value: Option<()> = poll_until(INTERVAL, Duration::from_millis(20), || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Ok(None)
+        })
+        .await
+        .unwrap();
+        assert_eq!(value, None);
+        assert!(calls.load(Ordering::SeqCst) >= 2);
+
+        // A zero timeout still polls once
+        calls.store(0, Ordering::SeqCst);
+        let value: Option<()> = poll_until(INTERVAL, Duration::ZERO, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Ok(None)
+        })
+        .await
+        .unwrap();
+        assert_eq!(value, None);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_poll_until_returns_errors() {
+        let calls = AtomicU32::new(0);
+        let result: Result<Option<()>> = poll_until(INTERVAL, Duration::from_secs(5), || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(TxOptimizerError::NoFeeData)
+        })
+        .await;
+        assert!(matches!(result, Err(TxOptimizerError::NoFeeData)));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_poll_until_cancelled() {
+        let cancel = CancellationToken::new();
+        let calls = AtomicU32::new(0);
+        let result: Result<Option<()>> =
+            poll_until_cancelled(INTERVAL, Duration::from_secs(60), &cancel, || async {
+                if calls.fetch_add(1, Ordering::SeqCst) == 2 {
+                    cancel.cancel();
+                }
+                Ok(None)
+            })
+            .await;
+        assert!(matches!(result, Err(TxOptimizerError::Cancelled)));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // An already cancelled token never polls
+        calls.store(0, Ordering::SeqCst);
+        let result: Result<Option<()>> =
+            poll_until_cancelled(INTERVAL, Duration::from_secs(60), &cancel, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok(Some(()))
+            })
+            .await;
+        assert!(matches!(result, Err(TxOptimizerError::Cancelled)));
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,896 @@
+use serde::{Deserialize, Serialize};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::signers::Signers,
+    system_program,
+    transaction::Transaction,
+};
+use tracing::{debug, warn};
+
+use crate::config::Config;
+use crate::error::{Result, TxOptimizerError};
+use crate::tx::PriorityTransactionBuilder;
+
+/// Byte offset of `Vault::owner`, right after the 8-byte Anchor discriminator.
+const OWNER_OFFSET: usize = 8;
+
+/// Most recipients `execute_batch` accepts in one instruction, mirroring the
+/// program's `MAX_BATCH_SIZE`.
+pub const MAX_BATCH_SIZE: usize = 10;
+
+/// Longest vault name, in bytes, `initialize_vault` accepts.
+pub const MAX_VAULT_NAME_LEN: usize = 32;
+
+/// Off-chain view of a `tx-vault` program `Vault` account.
+///
+/// Mirrors the on-chain field order; see `Vault::SPACE` in the program for
+/// the byte layout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VaultInfo {
+    /// Address of the vault PDA
+    pub address: Pubkey,
+    /// Lamports held by the account, including its rent-exempt reserve
+    pub lamports: u64,
+    pub owner: Pubkey,
+    pub name: String,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub tx_count: u64,
+    pub bump: u8,
+    /// Unix timestamp the vault was created at
+    pub created_at: i64,
+    pub paused: bool,
+    /// `0` means unlimited
+    pub daily_withdraw_limit: u64,
+    pub window_start: i64,
+    pub withdrawn_in_window: u64,
+    /// `0` means no minimum
+    pub min_deposit: u64,
+    /// Lamports kept back on top of the rent-exempt minimum
+    pub rent_reserve: u64,
+}
+
+impl VaultInfo {
+    /// Decode a `Vault` account's data.
+    ///
+    /// Fails if the Anchor discriminator is not `Vault`'s or the data ends
+    /// before the last field.
+    pub fn from_account_data(address: Pubkey, lamports: u64, data: &[u8]) -> Result<Self> {
+        let mut reader = AccountReader::new(data);
+        if reader.take(8)? != account_discriminator("Vault") {
+            return Err(TxOptimizerError::Serialization(format!(
+                "account {address} is not a vault"
+            )));
+        }
+
+        Ok(Self {
+            address,
+            lamports,
+            owner: reader.pubkey()?,
+            name: reader.string()?,
+            total_deposited: reader.u64()?,
+            total_withdrawn: reader.u64()?,
+            tx_count: reader.u64()?,
+            bump: reader.u8()?,
+            created_at: reader.i64()?,
+            paused: reader.u8()? != 0,
+            daily_withdraw_limit: reader.u64()?,
+            window_start: reader.i64()?,
+            withdrawn_in_window: reader.u64()?,
+            min_deposit: reader.u64()?,
+            rent_reserve: reader.u64()?,
+        })
+    }
+}
+
+/// List every vault `owner` has created under `program_id`.
+///
+/// Uses `getProgramAccounts` filtered on the `Vault` discriminator and the
+/// `owner` field, so only the owner's vaults are transferred. Accounts that
+/// match but fail to decode are skipped with a warning.
+///
+/// # Example
+/// ```no_run
+/// use solana_client::rpc_client::RpcClient;
+/// use solana_sdk::pubkey::Pubkey;
+/// use solana_tx_optimizer::vault_client::list_vaults;
+///
+/// let client = RpcClient::new("https://api.mainnet-beta.solana.com".to_string());
+/// let program_id: Pubkey = "VauLT1111111111111111111111111111111111111".parse().unwrap();
+/// for vault in list_vaults(&client, &program_id, &Pubkey::new_unique()).unwrap() {
+///     println!("{} {}", vault.name, vault.address);
+/// }
+/// ```
+pub fn list_vaults(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    owner: &Pubkey,
+) -> Result<Vec<VaultInfo>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(owner_filters(owner)),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    let accounts = client.get_program_accounts_with_config(program_id, config)?;
+    debug!("Found {} vault accounts for {owner}", accounts.len());
+
+    Ok(accounts
+        .into_iter()
+        .filter_map(|(address, account)| {
+            VaultInfo::from_account_data(address, account.lamports, &account.data)
+                .map_err(|e| warn!("Skipping vault {address}: {e}"))
+                .ok()
+        })
+        .collect())
+}
+
+/// Derive the address and bump of `owner`'s vault named `name`.
+///
+/// Uses the program's seeds, `["vault", owner, name]`, with `name` taken
+/// verbatim: no trimming or other normalization, so existing vaults keep
+/// resolving to the address they were created at.
+///
+/// # Example
+/// ```
+/// use solana_sdk::pubkey::Pubkey;
+/// use solana_tx_optimizer::vault_client::derive_vault_address;
+///
+/// let program_id = Pubkey::new_unique();
+/// let (vault, _bump) = derive_vault_address(&program_id, &Pubkey::new_unique(), "savings");
+/// assert!(!vault.is_on_curve());
+/// ```
+pub fn derive_vault_address(program_id: &Pubkey, owner: &Pubkey, name: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vault", owner.as_ref(), name.as_bytes()], program_id)
+}
+
+/// Like [`derive_vault_address`], but fails with [`TxOptimizerError::Config`]
+/// if `name` is longer than [`MAX_VAULT_NAME_LEN`] bytes, since no such vault
+/// can exist.
+pub fn derive_vault_address_checked(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    name: &str,
+) -> Result<(Pubkey, u8)> {
+    if name.len() > MAX_VAULT_NAME_LEN {
+        return Err(TxOptimizerError::Config(format!(
+            "vault name is {} bytes, the maximum is {MAX_VAULT_NAME_LEN}",
+            name.len()
+        )));
+    }
+    Ok(derive_vault_address(program_id, owner, name))
+}
+
+/// Derive the address of the record tracking `depositor`'s deposits into
+/// `vault`, from the program's seeds `["depositor", vault, depositor]`.
+pub fn derive_depositor_record_address(
+    program_id: &Pubkey,
+    vault: &Pubkey,
+    depositor: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"depositor", vault.as_ref(), depositor.as_ref()],
+        program_id,
+    )
+}
+
+/// Build an `initialize_vault` instruction creating `owner`'s vault named
+/// `name`, funded by `owner`.
+///
+/// Fails with [`TxOptimizerError::Config`] if `name` is longer than
+/// [`MAX_VAULT_NAME_LEN`] bytes. The name is sent verbatim; the program
+/// rejects names with surrounding whitespace or control characters, so
+/// trim it first.
+pub fn initialize_vault_instruction(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    name: &str,
+) -> Result<Instruction> {
+    let (vault, _) = derive_vault_address_checked(program_id, owner, name)?;
+    let mut data = instruction_discriminator("initialize_vault").to_vec();
+    data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+    data.extend_from_slice(name.as_bytes());
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(vault, false),
+            AccountMeta::new(*owner, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data,
+    })
+}
+
+/// Build a `deposit` instruction moving `amount` lamports from `depositor`
+/// into `vault`.
+///
+/// `depositor` also pays for its deposit record on the first deposit.
+pub fn deposit_instruction(
+    program_id: &Pubkey,
+    vault: &Pubkey,
+    depositor: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (depositor_record, _) = derive_depositor_record_address(program_id, vault, depositor);
+    let mut data = instruction_discriminator("deposit").to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(*depositor, true),
+            AccountMeta::new(depositor_record, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data,
+    }
+}
+
+/// Build a `close_vault` instruction returning `vault`'s lamports to
+/// `owner`.
+///
+/// Without `force`, the program refuses to close a vault holding more than
+/// its rent-exempt minimum and rent reserve. It refuses paused vaults even
+/// with `force`, and a forced close must fit in the vault's withdrawal limit.
+pub fn close_vault_instruction(
+    program_id: &Pubkey,
+    vault: &Pubkey,
+    owner: &Pubkey,
+    force: bool,
+) -> Instruction {
+    let mut data = instruction_discriminator("close_vault").to_vec();
+    data.push(force as u8);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(*owner, true),
+        ],
+        data,
+    }
+}
+
+/// Build a `migrate_vault` instruction growing `owner`'s vault named `name`
+/// to the program's current account size, with `owner` paying the extra
+/// rent.
+///
+/// Vaults created by an older version of the program fail every other
+/// instruction until they are migrated. Fails with
+/// [`TxOptimizerError::Config`] if `name` is longer than
+/// [`MAX_VAULT_NAME_LEN`] bytes.
+pub fn migrate_vault_instruction(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    name: &str,
+) -> Result<Instruction> {
+    let (vault, _) = derive_vault_address_checked(program_id, owner, name)?;
+    let mut data = instruction_discriminator("migrate_vault").to_vec();
+    data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+    data.extend_from_slice(name.as_bytes());
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(vault, false),
+            AccountMeta::new(*owner, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data,
+    })
+}
+
+/// Send `instructions` in one transaction paid by `payer` and wait for
+/// confirmation, via [`send_and_confirm`](crate::tx::send_and_confirm).
+///
+/// `signers` must include `payer` and every signer the instructions require,
+/// such as the vault owner or depositor.
+///
+/// # Example
+/// ```no_run
+/// use solana_client::rpc_client::RpcClient;
+/// use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+/// use solana_tx_optimizer::{config::Config, vault_client};
+///
+/// let config = Config::from_env();
+/// let client = RpcClient::new(config.rpc_url.clone());
+/// let owner = Keypair::new();
+/// let program_id: Pubkey = "VauLT1111111111111111111111111111111111111".parse().unwrap();
+/// let ix = vault_client::initialize_vault_instruction(&program_id, &owner.pubkey(), "savings")
+///     .unwrap();
+/// let signature = vault_client::send(&client, &owner.pubkey(), &[ix], &[&owner], &config).unwrap();
+/// println!("Vault created: {signature}");
+/// ```
+pub fn send<T: Signers + ?Sized>(
+    client: &RpcClient,
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    signers: &T,
+    cfg: &Config,
+) -> Result<Signature> {
+    let tx = Transaction::new_with_payer(instructions, Some(payer));
+    crate::tx::send_and_confirm(client, &tx, signers, cfg)
+}
+
+/// Split a payout into groups of at most `chunk_size` recipients, keeping
+/// their order.
+///
+/// Fails with [`TxOptimizerError::Config`] if `recipients` and `amounts`
+/// differ in length or `chunk_size` is not within `1..=MAX_BATCH_SIZE`. An
+/// empty payout yields no chunks.
+///
+/// # Example
+/// ```
+/// use solana_sdk::pubkey::Pubkey;
+/// use solana_tx_optimizer::vault_client::chunk_batch;
+///
+/// let recipients: Vec<Pubkey> = (0..25).map(|_| Pubkey::new_unique()).collect();
+/// let chunks = chunk_batch(&recipients, &[1_000; 25], 10).unwrap();
+/// assert_eq!(chunks.iter().map(|(r, _)| r.len()).collect::<Vec<_>>(), [10, 10, 5]);
+/// ```
+pub fn chunk_batch(
+    recipients: &[Pubkey],
+    amounts: &[u64],
+    chunk_size: usize,
+) -> Result<Vec<(Vec<Pubkey>, Vec<u64>)>> {
+    if recipients.len() != amounts.len() {
+        return Err(TxOptimizerError::Config(format!(
+            "batch has {} recipients but {} amounts",
+            recipients.len(),
+            amounts.len()
+        )));
+    }
+    if !(1..=MAX_BATCH_SIZE).contains(&chunk_size) {
+        return Err(TxOptimizerError::Config(format!(
+            "batch chunk size must be within 1..={MAX_BATCH_SIZE}, got {chunk_size}"
+        )));
+    }
+
+    Ok(recipients
+        .chunks(chunk_size)
+        .zip(amounts.chunks(chunk_size))
+        .map(|(recipients, amounts)| (recipients.to_vec(), amounts.to_vec()))
+        .collect())
+}
+
+/// Build an `execute_batch` instruction paying `amounts[i]` lamports from
+/// `vault` to `recipients[i]`.
+///
+/// Recipients are appended as writable remaining accounts, in order. The
+/// program rejects more than [`MAX_BATCH_SIZE`] recipients; use
+/// [`chunk_batch`] or [`BatchPayoutBuilder`] for larger payouts.
+///
+/// With `allow_partial`, the program skips recipients it cannot pay instead
+/// of failing the whole batch, and reports them in a
+/// `BatchPartiallyExecuted` event.
+pub fn execute_batch_instruction(
+    program_id: &Pubkey,
+    vault: &Pubkey,
+    owner: &Pubkey,
+    recipients: &[Pubkey],
+    amounts: &[u64],
+    allow_partial: bool,
+) -> Instruction {
+    let mut data = instruction_discriminator("execute_batch").to_vec();
+    data.extend_from_slice(&(recipients.len() as u32).to_le_bytes());
+    for recipient in recipients {
+        data.extend_from_slice(recipient.as_ref());
+    }
+    data.extend_from_slice(&(amounts.len() as u32).to_le_bytes());
+    for amount in amounts {
+        data.extend_from_slice(&amount.to_le_bytes());
+    }
+    data.push(allow_partial as u8);
+
+    let mut accounts = vec![
+        AccountMeta::new(*vault, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend(recipients.iter().map(|r| AccountMeta::new(*r, false)));
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
+
+/// Builds one `execute_batch` transaction per chunk of a payout too large
+/// for a single instruction.
+///
+/// Each transaction is assembled from a clone of the `template`, so priority
+/// fee and compute unit limit set on it apply to every chunk. Chunks are
+/// independent transactions: if one fails, the ones before it have still
+/// paid out.
+///
+/// # Example
+/// ```no_run
+/// use solana_client::rpc_client::RpcClient;
+/// use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+/// use solana_tx_optimizer::tx::PriorityTransactionBuilder;
+/// use solana_tx_optimizer::vault_client::BatchPayoutBuilder;
+///
+/// let client = RpcClient::new("https://api.mainnet-beta.solana.com".to_string());
+/// let owner = Keypair::new();
+/// let program_id: Pubkey = "VauLT1111111111111111111111111111111111111".parse().unwrap();
+/// let vault = Pubkey::new_unique();
+/// let recipients: Vec<Pubkey> = (0..25).map(|_| Pubkey::new_unique()).collect();
+///
+/// let template = PriorityTransactionBuilder::new(&client, owner.pubkey()).with_priority_fee(5_000);
+/// let txs = BatchPayoutBuilder::new(template, program_id, vault, owner.pubkey())
+///     .build(&recipients, &[1_000; 25], &[&owner])
+///     .unwrap();
+/// assert_eq!(txs.len(), 3);
+/// ```
+#[derive(Clone)]
+pub struct BatchPayoutBuilder<'a> {
+    template: PriorityTransactionBuilder<'a>,
+    program_id: Pubkey,
+    vault: Pubkey,
+    owner: Pubkey,
+    chunk_size: usize,
+    allow_partial: bool,
+}
+
+impl<'a> BatchPayoutBuilder<'a> {
+    /// Pay out of `vault`, authorized by `owner`, in chunks of
+    /// [`MAX_BATCH_SIZE`] recipients.
+    pub fn new(
+        template: PriorityTransactionBuilder<'a>,
+        program_id: Pubkey,
+        vault: Pubkey,
+        owner: Pubkey,
+    ) -> Self {
+        Self {
+            template,
+            program_id,
+            vault,
+            owner,
+            chunk_size: MAX_BATCH_SIZE,
+            allow_partial: false,
+        }
+    }
+
+    /// Use smaller chunks, e.g. to leave room for other instructions.
+    /// Must be within `1..=MAX_BATCH_SIZE`.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Result<Self> {
+        chunk_batch(&[], &[], chunk_size)?;
+        self.chunk_size = chunk_size;
+        Ok(self)
+    }
+
+    /// Let each chunk skip recipients the program cannot pay instead of
+    /// failing; see [`execute_batch_instruction`]. Off by default.
+    pub fn with_allow_partial(mut self, allow_partial: bool) -> Self {
+        self.allow_partial = allow_partial;
+        self
+    }
+
+    /// One `execute_batch` instruction per chunk, in payout order.
+    pub fn instructions(&self, recipients: &[Pubkey], amounts: &[u64]) -> Result<Vec<Instruction>> {
+        Ok(chunk_batch(recipients, amounts, self.chunk_size)?
+            .iter()
+            .map(|(recipients, amounts)| {
+                execute_batch_instruction(
+                    &self.program_id,
+                    &self.vault,
+                    &self.owner,
+                    recipients,
+                    amounts,
+                    self.allow_partial,
+                )
+            })
+            .collect())
+    }
+
+    /// Sign one transaction per chunk, in payout order.
+    ///
+    /// `signers` must include the template's payer and the vault owner.
+    pub fn build<T: Signers + ?Sized>(
+        &self,
+        recipients: &[Pubkey],
+        amounts: &[u64],
+        signers: &T,
+    ) -> Result<Vec<Transaction>> {
+        let instructions = self.instructions(recipients, amounts)?;
+        debug!(
+            "Splitting {} recipients into {} batch transactions",
+            recipients.len(),
+            instructions.len()
+        );
+        instructions
+            .into_iter()
+            .map(|ix| {
+                self.template
+                    .clone()
+                    .with_instructions(vec![ix])
+                    .build(signers)
+            })
+            .collect()
+    }
+}
+
+/// `getProgramAccounts` filters matching `Vault` accounts owned by `owner`.
+fn owner_filters(owner: &Pubkey) -> Vec<RpcFilterType> {
+    vec![
+        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            0,
+            account_discriminator("Vault").to_vec(),
+        )),
+        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            OWNER_OFFSET,
+            owner.to_bytes().to_vec(),
+        )),
+    ]
+}
+
+/// Anchor account discriminator: the first 8 bytes of
+/// `sha256("account:<Name>")`.
+fn account_discriminator(name: &str) -> [u8; 8] {
+    discriminator("account", name)
+}
+
+/// Anchor instruction discriminator: the first 8 bytes of
+/// `sha256("global:<name>")`.
+fn instruction_discriminator(name: &str) -> [u8; 8] {
+    discriminator("global", name)
+}
+
+fn discriminator(namespace: &str, name: &str) -> [u8; 8] {
+    let hash = solana_sdk::hash::hash(format!("{namespace}:{name}").as_bytes());
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash.to_bytes()[..8]);
+    discriminator
+}
+
+/// Sequential reader for Borsh-encoded account fields.
+struct AccountReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> AccountReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.offset.saturating_add(len);
+        let bytes = self.data.get(self.offset..end).ok_or_else(|| {
+            TxOptimizerError::Serialization(format!(
+                "account data too short: needed {end} bytes, got {}",
+                self.data.len()
+            ))
+        })?;
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn i64(&mut self) -> Result<i64> {
+        Ok(i64::from_le_bytes(self.array()?))
+    }
+
+    fn pubkey(&mut self) -> Result<Pubkey> {
+        Ok(Pubkey::new_from_array(self.array()?))
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let len = u32::from_le_bytes(self.array()?) as usize;
+        String::from_utf8(self.take(len)?.to_vec())
+            .map_err(|e| TxOptimizerError::Serialization(format!("invalid UTF-8 string: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+    use solana_client::rpc_request::RpcRequest;
+
+    /// Borsh-encode a vault the way the program lays it out, padded to
+    /// `Vault::SPACE`.
+    fn vault_data(owner: &Pubkey, name: &str, total_deposited: u64) -> Vec<u8> {
+        let mut data = account_discriminator("Vault").to_vec();
+        data.extend_from_slice(owner.as_ref());
+        data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+        data.extend_from_slice(name.as_bytes());
+        data.extend_from_slice(&total_deposited.to_le_bytes());
+        data.extend_from_slice(&400u64.to_le_bytes()); // total_withdrawn
+        data.extend_from_slice(&3u64.to_le_bytes()); // tx_count
+        data.push(254); // bump
+        data.extend_from_slice(&1_700_000_000i64.to_le_bytes()); // created_at
+        data.push(1); // paused
+        data.extend_from_slice(&5_000u64.to_le_bytes()); // daily_withdraw_limit
+        data.extend_from_slice(&1_700_000_100i64.to_le_bytes()); // window_start
+        data.extend_from_slice(&250u64.to_le_bytes()); // withdrawn_in_window
+        data.extend_from_slice(&10u64.to_le_bytes()); // min_deposit
+        data.extend_from_slice(&50_000u64.to_le_bytes()); // rent_reserve
+        data.resize(150, 0);
+        data
+    }
+
+    #[test]
+    fn test_derive_vault_address() {
+        let program_id = Pubkey::new_from_array([3; 32]);
+        let owner = Pubkey::new_from_array([7; 32]);
+        let (address, bump) = derive_vault_address(&program_id, &owner, "savings");
+        assert_eq!(
+            address.to_string(),
+            "8vVDPdgzkrxoBSXQLbQSWGLQLagwa43h9qXj1ZgKeoPQ"
+        );
+        assert_eq!(bump, 254);
+        assert_eq!(
+            Pubkey::create_program_address(
+                &[b"vault", owner.as_ref(), b"savings", &[bump]],
+                &program_id
+            ),
+            Ok(address)
+        );
+
+        // The name is a seed as-is, so a trailing space is a different vault.
+        assert_ne!(
+            derive_vault_address(&program_id, &owner, "savings ").0,
+            address
+        );
+
+        let name = "a".repeat(MAX_VAULT_NAME_LEN);
+        assert_eq!(
+            derive_vault_address_checked(&program_id, &owner, &name).unwrap(),
+            derive_vault_address(&program_id, &owner, &name)
+        );
+        let err =
+            derive_vault_address_checked(&program_id, &owner, &format!("{name}a")).unwrap_err();
+        assert!(matches!(err, TxOptimizerError::Config(_)));
+    }
+
+    #[test]
+    fn test_decode_vault() {
+        let owner = Pubkey::new_unique();
+        let address = Pubkey::new_unique();
+        let data = vault_data(&owner, "savings", 1_000);
+
+        let vault = VaultInfo::from_account_data(address, 2_000_000, &data).unwrap();
+        assert_eq!(vault.address, address);
+        assert_eq!(vault.owner, owner);
+        assert_eq!(vault.name, "savings");
+        assert_eq!(vault.total_deposited, 1_000);
+        assert_eq!(vault.total_withdrawn, 400);
+        assert_eq!(vault.bump, 254);
+        assert_eq!(vault.created_at, 1_700_000_000);
+        assert!(vault.paused);
+        assert_eq!(vault.withdrawn_in_window, 250);
+        assert_eq!(vault.min_deposit, 10);
+        assert_eq!(vault.rent_reserve, 50_000);
+
+        // Data ending right before `min_deposit`
+        assert!(VaultInfo::from_account_data(address, 0, &data[..109]).is_err());
+
+        let mut other = data.clone();
+        other[0] ^= 1;
+        assert!(matches!(
+            VaultInfo::from_account_data(address, 0, &other),
+            Err(TxOptimizerError::Serialization(_))
+        ));
+    }
+
+    #[test]
+    fn test_list_vaults() {
+        let owner = Pubkey::new_unique();
+        let account = |data: Vec<u8>| {
+            serde_json::json!({
+                "pubkey": Pubkey::new_unique().to_string(),
+                "account": {
+                    "lamports": 2_000_000,
+                    "data": [base64::engine::general_purpose::STANDARD.encode(data), "base64"],
+                    "owner": Pubkey::new_unique().to_string(),
+                    "executable": false,
+                    "rentEpoch": 0,
+                    "space": 150,
+                },
+            })
+        };
+        let mut mocks = std::collections::HashMap::new();
+        mocks.insert(
+            RpcRequest::GetProgramAccounts,
+            serde_json::json!([
+                account(vault_data(&owner, "savings", 1_000)),
+                account(vec![0; 16]),
+                account(vault_data(&owner, "trading", 7)),
+            ]),
+        );
+        let client = RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks);
+
+        let vaults = list_vaults(&client, &Pubkey::new_unique(), &owner).unwrap();
+        let names: Vec<&str> = vaults.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, ["savings", "trading"]);
+
+        let filters = owner_filters(&owner);
+        assert_eq!(filters.len(), 2);
+        assert!(
+            filters[1].allows(&solana_sdk::account::AccountSharedData::from(
+                solana_sdk::account::Account {
+                    data: vault_data(&owner, "x", 0),
+                    ..Default::default()
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_chunk_batch() {
+        let recipients: Vec<Pubkey> = (0..23).map(|_| Pubkey::new_unique()).collect();
+        let amounts: Vec<u64> = (1..=23).collect();
+
+        let chunks = chunk_batch(&recipients, &amounts, MAX_BATCH_SIZE).unwrap();
+        let sizes: Vec<usize> = chunks.iter().map(|(r, _)| r.len()).collect();
+        assert_eq!(sizes, [10, 10, 3]);
+        let flattened: Vec<u64> = chunks.iter().flat_map(|(_, a)| a.clone()).collect();
+        assert_eq!(flattened, amounts);
+        assert_eq!(chunks[2].0, recipients[20..]);
+
+        assert!(chunk_batch(&[], &[], 4).unwrap().is_empty());
+        for (recipients, amounts, size) in [
+            (&recipients[..2], &amounts[..1], 10),
+            (&recipients[..2], &amounts[..2], 0),
+            (&recipients[..2], &amounts[..2], MAX_BATCH_SIZE + 1),
+        ] {
+            assert!(matches!(
+                chunk_batch(recipients, amounts, size),
+                Err(TxOptimizerError::Config(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_instruction_layouts() {
+        let program_id = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let system = AccountMeta::new_readonly(system_program::id(), false);
+
+        // `sha256("global:<name>")[..8]`, as Anchor derives them
+        assert_eq!(
+            instruction_discriminator("initialize_vault"),
+            [48, 191, 163, 44, 71, 129, 63, 164]
+        );
+        assert_eq!(
+            instruction_discriminator("deposit"),
+            [242, 35, 198, 137, 82, 225, 242, 182]
+        );
+        assert_eq!(
+            instruction_discriminator("execute_batch"),
+            [112, 159, 211, 51, 238, 70, 212, 60]
+        );
+        assert_eq!(
+            instruction_discriminator("close_vault"),
+            [141, 103, 17, 126, 72, 75, 29, 29]
+        );
+        assert_eq!(
+            instruction_discriminator("migrate_vault"),
+            [139, 151, 25, 211, 120, 164, 24, 215]
+        );
+
+        // InitializeVault: vault, owner, system_program
+        let ix = initialize_vault_instruction(&program_id, &owner, "savings").unwrap();
+        let (vault, _) = derive_vault_address(&program_id, &owner, "savings");
+        assert_eq!(
+            ix.accounts,
+            [
+                AccountMeta::new(vault, false),
+                AccountMeta::new(owner, true),
+                system.clone()
+            ]
+        );
+        assert_eq!(ix.data[8..12], 7u32.to_le_bytes());
+        assert_eq!(&ix.data[12..], b"savings");
+        assert!(initialize_vault_instruction(&program_id, &owner, &"x".repeat(33)).is_err());
+
+        // Deposit: vault, depositor, depositor_record, system_program
+        let depositor = Pubkey::new_unique();
+        let ix = deposit_instruction(&program_id, &vault, &depositor, 1_000);
+        let (record, _) = derive_depositor_record_address(&program_id, &vault, &depositor);
+        assert_eq!(
+            ix.accounts,
+            [
+                AccountMeta::new(vault, false),
+                AccountMeta::new(depositor, true),
+                AccountMeta::new(record, false),
+                system,
+            ]
+        );
+        assert_eq!(ix.data[8..], 1_000u64.to_le_bytes());
+
+        // CloseVault: vault, owner
+        let ix = close_vault_instruction(&program_id, &vault, &owner, true);
+        assert_eq!(
+            ix.accounts,
+            [
+                AccountMeta::new(vault, false),
+                AccountMeta::new(owner, true)
+            ]
+        );
+        assert_eq!(
+            ix.data,
+            [&instruction_discriminator("close_vault")[..], &[1]].concat()
+        );
+
+        // MigrateVault: vault, owner, system_program; same data as initialize
+        let ix = migrate_vault_instruction(&program_id, &owner, "savings").unwrap();
+        assert_eq!(
+            ix.accounts,
+            [
+                AccountMeta::new(vault, false),
+                AccountMeta::new(owner, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ]
+        );
+        assert_eq!(&ix.data[12..], b"savings");
+    }
+
+    #[test]
+    fn test_batch_payout_builder() {
+        use solana_sdk::{signature::Keypair, signer::Signer};
+
+        let client = RpcClient::new_mock("succeeds".to_string());
+        let owner = Keypair::new();
+        let program_id = Pubkey::new_unique();
+        let vault = Pubkey::new_unique();
+        let recipients: Vec<Pubkey> = (0..7).map(|_| Pubkey::new_unique()).collect();
+        let amounts = [5u64; 7];
+
+        let template = PriorityTransactionBuilder::new(&client, owner.pubkey());
+        let builder = BatchPayoutBuilder::new(template, program_id, vault, owner.pubkey())
+            .with_chunk_size(3)
+            .unwrap();
+        assert!(builder.clone().with_chunk_size(11).is_err());
+
+        let instructions = builder.instructions(&recipients, &amounts).unwrap();
+        assert_eq!(instructions.len(), 3);
+        let last = &instructions[2];
+        assert_eq!(last.program_id, program_id);
+        assert_eq!(last.accounts[0], AccountMeta::new(vault, false));
+        assert_eq!(
+            last.accounts[1],
+            AccountMeta::new_readonly(owner.pubkey(), true)
+        );
+        assert_eq!(last.accounts[3], AccountMeta::new(recipients[6], false));
+        // Discriminator, then one recipient, one amount and `allow_partial`
+        assert_eq!(last.data.len(), 8 + 4 + 32 + 4 + 8 + 1);
+        assert_eq!(last.data[..8], instruction_discriminator("execute_batch"));
+        assert_eq!(last.data.last(), Some(&0));
+        let partial = builder.clone().with_allow_partial(true);
+        let instructions = partial.instructions(&recipients, &amounts).unwrap();
+        assert_eq!(instructions[0].data.last(), Some(&1));
+
+        let txs = builder.build(&recipients, &amounts, &[&owner]).unwrap();
+        assert_eq!(txs.len(), 3);
+        assert!(txs.iter().all(|tx| tx.is_signed()));
+        assert!(txs[1].message.account_keys.contains(&recipients[5]));
+    }
+}
@@ -0,0 +1,28 @@
+//! Live check of the QuickNode fee backend.
+//!
+//! Needs an endpoint with the priority fee add-on enabled:
+//!
+//! ```text
+//! QUICKNODE_RPC_URL=https://... cargo test --features quicknode-tests --test quicknode
+//! ```
+//!
+//! Without `QUICKNODE_RPC_URL` the test passes without making any request.
+#![cfg(feature = "quicknode-tests")]
+
+use solana_tx_optimizer::priority_fee::{
+    FeeBackend, FeeDataSource, FeeStrategy, PriorityFeeEstimator,
+};
+
+#[test]
+fn test_quicknode_estimate() {
+    let Ok(url) = std::env::var("QUICKNODE_RPC_URL") else {
+        eprintln!("QUICKNODE_RPC_URL is not set, skipping");
+        return;
+    };
+
+    let estimator = PriorityFeeEstimator::new(&url).with_backend(FeeBackend::QuickNode);
+    let economy = estimator.estimate(FeeStrategy::Economy).unwrap();
+    let turbo = estimator.estimate(FeeStrategy::Turbo).unwrap();
+    assert_eq!(economy.data_source, FeeDataSource::QuickNode);
+    assert!(economy.recommended_fee <= turbo.recommended_fee);
+}
@@ -18,9 +18,7 @@ use solana_sdk::{
     commitment_config::CommitmentConfig,
     instruction::Instruction,
     message::Message,
-    native_token::LAMPORTS_PER_SOL,
     pubkey::Pubkey,
-    signature::read_keypair_file,
     signer::Signer,
     system_instruction,
     transaction::Transaction,
@@ -33,6 +31,7 @@ use solana_tx_optimizer::{
         build_compute_unit_limit_instruction, build_priority_fee_instruction,
         FeeStrategy, PriorityFeeEstimator,
     },
+    units::Lamports,
 };
 use std::str::FromStr;
 use std::time::Duration;
@@ -55,16 +54,12 @@ async fn main() -> Result<()> {
     );
 
     // Load wallet
-    let wallet_path = config
-        .wallet_path
-        .to_str()
-        .expect("Invalid wallet path");
-    let payer = read_keypair_file(wallet_path)
-        .map_err(|e| anyhow::anyhow!("Failed to read wallet: {e}"))?;
+    let payer = config.load_keypair()?;
 
     println!("Wallet:       {}", payer.pubkey());
     println!("RPC:          {}", config.rpc_url);
-    println!("Jito engine:  {}\n", config.jito_block_engine_url);
+    println!("Jito engine:  {}", config.jito_block_engine_url);
+    println!("Tip:          {}\n", Lamports(config.jito_tip_lamports));
 
     // --- Step 1: Estimate Priority Fee ---
     println!("--- Step 1: Estimating priority fee ---");
@@ -101,6 +96,7 @@ async fn main() -> Result<()> {
         let tip_ix = create_tip_instruction(
             &payer.pubkey(),
             config.jito_tip_lamports,
+            None,
         )?;
 
         let instructions = vec![
@@ -119,7 +115,7 @@ async fn main() -> Result<()> {
     // --- Step 3: Build and Submit Bundle ---
     println!("--- Step 3: Submitting Jito bundle ---");
 
-    let mut builder = JitoBundleBuilder::new(&config);
+    let mut builder = JitoBundleBuilder::new(&config)?;
     builder
         .add_transaction(&tx1)?
         .add_transaction(&tx2)?
@@ -141,7 +137,7 @@ async fn main() -> Result<()> {
         solana_tx_optimizer::BundleStatus::Accepted { bundle_id } => {
             println!("\nBundle {} accepted, waiting for confirmation...", bundle_id);
         }
-        solana_tx_optimizer::BundleStatus::Rejected { reason } => {
+        solana_tx_optimizer::BundleStatus::Rejected { reason, .. } => {
             println!("\nBundle rejected: {}", reason);
         }
         solana_tx_optimizer::BundleStatus::Expired { bundle_id } => {
@@ -17,7 +17,9 @@ pub mod tx_vault {
     ///
     /// The vault is derived from the owner's public key and a human-readable name,
     /// allowing a single user to manage multiple independent vaults. The name must
-    /// not exceed 32 bytes to keep account size predictable.
+    /// not exceed 32 bytes to keep account size predictable. While
+    /// [`NORMALIZE_VAULT_NAMES`] is set, it must also already be normalized; see
+    /// [`validate_vault_name`].
     ///
     /// # Arguments
     ///
@@ -26,9 +28,10 @@ pub mod tx_vault {
     ///
     /// # Errors
     ///
-    /// Returns [`VaultError::NameTooLong`] if `name` exceeds 32 bytes.
+    /// Returns [`VaultError::NameTooLong`] if `name` exceeds 32 bytes, or
+    /// [`VaultError::InvalidName`] if it is not normalized.
     pub fn initialize_vault(ctx: Context<InitializeVault>, name: String) -> Result<()> {
-        require!(name.len() <= 32, VaultError::NameTooLong);
+        validate_vault_name(&name, NORMALIZE_VAULT_NAMES)?;
 
         let vault = &mut ctx.accounts.vault;
         vault.owner = ctx.accounts.owner.key();
@@ -38,6 +41,12 @@ pub mod tx_vault {
         vault.tx_count = 0;
         vault.bump = ctx.bumps.vault;
         vault.created_at = Clock::get()?.unix_timestamp;
+        vault.paused = false;
+        vault.daily_withdraw_limit = 0;
+        vault.window_start = 0;
+        vault.withdrawn_in_window = 0;
+        vault.min_deposit = 0;
+        vault.rent_reserve = 0;
 
         emit!(VaultInitialized {
             vault: vault.key(),
@@ -55,15 +64,20 @@ pub mod tx_vault {
     ///
     /// # Arguments
     ///
+    /// The depositor's [`DepositorRecord`] is created on their first deposit
+    /// (paid for by the depositor) and tracks their cumulative contribution.
+    ///
     /// * `ctx` - The instruction context containing the vault and depositor accounts.
     /// * `amount` - The number of lamports to transfer into the vault.
     ///
     /// # Errors
     ///
     /// Returns [`VaultError::ZeroAmount`] if `amount` is zero.
+    /// Returns [`VaultError::VaultPaused`] if the vault is paused.
+    /// Returns [`VaultError::BelowMinDeposit`] if `amount` is below the vault's `min_deposit`.
     /// Returns [`VaultError::Overflow`] if the running total would overflow.
     pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
-        require!(amount > 0, VaultError::ZeroAmount);
+        ctx.accounts.vault.validate_deposit(amount)?;
 
         // CPI: transfer SOL from depositor to vault PDA via System Program.
         let cpi_context = CpiContext::new(
@@ -85,11 +99,68 @@ pub mod tx_vault {
             .checked_add(1)
             .ok_or(VaultError::Overflow)?;
 
+        let record = &mut ctx.accounts.depositor_record;
+        record.vault = vault.key();
+        record.depositor = ctx.accounts.depositor.key();
+        record.bump = ctx.bumps.depositor_record;
+        record.total_deposited = record
+            .total_deposited
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+
         emit!(DepositMade {
             vault: vault.key(),
             depositor: ctx.accounts.depositor.key(),
             amount,
             total_deposited: vault.total_deposited,
+            depositor_total: record.total_deposited,
+        });
+
+        Ok(())
+    }
+
+    /// Adds SOL to the vault's rent reserve, a cushion that withdrawals will
+    /// not spend.
+    ///
+    /// The lamports are transferred from the owner like a deposit but counted
+    /// in [`Vault::rent_reserve`] rather than `total_deposited`. Batches and
+    /// sweeps only spend what is above the rent-exempt minimum plus the
+    /// reserve, so a vault drawn down to that point stays rent-exempt even if
+    /// the minimum grows. The reserve is returned to the owner when the vault
+    /// is closed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The instruction context containing the vault, owner, and system program.
+    /// * `extra_lamports` - The number of lamports to add to the reserve.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VaultError::ZeroAmount`] if `extra_lamports` is zero.
+    /// Returns [`VaultError::Overflow`] if the reserve would overflow.
+    pub fn fund_rent(ctx: Context<FundRent>, extra_lamports: u64) -> Result<()> {
+        require!(extra_lamports > 0, VaultError::ZeroAmount);
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        system_program::transfer(cpi_context, extra_lamports)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.rent_reserve = vault
+            .rent_reserve
+            .checked_add(extra_lamports)
+            .ok_or(VaultError::Overflow)?;
+
+        emit!(RentFunded {
+            vault: vault.key(),
+            owner: vault.owner,
+            amount: extra_lamports,
+            rent_reserve: vault.rent_reserve,
         });
 
         Ok(())
@@ -99,47 +170,268 @@ pub mod tx_vault {
     ///
     /// Only the vault owner may invoke this instruction. The vault PDA signs each
     /// outbound transfer using its derived seeds, so no private key is required.
-    /// The instruction enforces a maximum of 10 recipients per batch to stay within
-    /// compute-unit limits.
+    /// The instruction enforces a maximum of [`MAX_BATCH_SIZE`] recipients per batch
+    /// to stay within the account limits of a legacy transaction; larger batches
+    /// must go through [`execute_batch_with_lut`].
     ///
     /// # Arguments
     ///
     /// * `ctx` - The instruction context containing the vault, owner, and system program.
     /// * `recipients` - Public keys of the accounts to receive SOL.
     /// * `amounts` - Lamport amounts corresponding to each recipient.
+    /// * `allow_partial` - Skip a recipient whose account is missing from
+    ///   `remaining_accounts` or not writable instead of failing the batch.
+    ///   Only the lamports actually transferred count toward the withdrawal
+    ///   limit, the funds check and `total_withdrawn`, and the outcome is
+    ///   reported in a [`BatchPartiallyExecuted`] event when any recipient was
+    ///   skipped. Without it, the batch is all-or-nothing.
     ///
     /// # Errors
     ///
+    /// Returns [`VaultError::VaultPaused`] if the vault is paused.
     /// Returns [`VaultError::LengthMismatch`] if `recipients` and `amounts` differ in length.
     /// Returns [`VaultError::EmptyBatch`] if both vectors are empty.
-    /// Returns [`VaultError::BatchTooLarge`] if there are more than 10 recipients.
+    /// Returns [`VaultError::DuplicateRecipient`] if a recipient appears more than once.
+    /// Returns [`VaultError::BatchRequiresLookupTable`] if there are more than
+    ///   [`MAX_BATCH_SIZE`] but no more than [`MAX_BATCH_WITH_LUT`] recipients.
+    /// Returns [`VaultError::BatchTooLarge`] if there are more than [`MAX_BATCH_WITH_LUT`] recipients.
     /// Returns [`VaultError::Overflow`] if the total amount overflows.
+    /// Returns [`VaultError::WithdrawLimitExceeded`] if the batch would push the
+    ///   current window's withdrawals past `daily_withdraw_limit`.
     /// Returns [`VaultError::InsufficientFunds`] if the vault lacks enough lamports
-    ///   (after reserving rent-exempt minimum).
-    pub fn execute_batch(
-        ctx: Context<ExecuteBatch>,
+    ///   (after reserving the rent-exempt minimum and `rent_reserve`).
+    /// Returns [`VaultError::RecipientNotWritable`] or
+    ///   `ErrorCode::AccountNotEnoughKeys` for a recipient that cannot be paid,
+    ///   unless `allow_partial` is set.
+    pub fn execute_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteBatch<'info>>,
         recipients: Vec<Pubkey>,
         amounts: Vec<u64>,
+        allow_partial: bool,
     ) -> Result<()> {
-        require!(recipients.len() == amounts.len(), VaultError::LengthMismatch);
-        require!(!recipients.is_empty(), VaultError::EmptyBatch);
-        require!(recipients.len() <= 10, VaultError::BatchTooLarge);
-
-        // Compute the total outbound amount with overflow protection.
-        let total_amount: u64 = amounts
-            .iter()
-            .try_fold(0u64, |acc, &amt| acc.checked_add(amt))
-            .ok_or(VaultError::Overflow)?;
+        process_batch(
+            ctx,
+            recipients,
+            amounts,
+            MAX_BATCH_SIZE,
+            String::new(),
+            allow_partial,
+        )
+    }
+
+    /// Executes a batched transfer and attaches a human-readable memo.
+    ///
+    /// Identical to [`execute_batch`], with the `memo` copied verbatim into the
+    /// emitted [`BatchExecuted`] event so accounting integrations can reconcile
+    /// payouts against their own references. The memo is not stored on-chain
+    /// beyond the event log. Partial execution is not available here: the
+    /// batch is always all-or-nothing, as with `allow_partial = false`. There
+    /// is no lookup-table variant either, so memo batches are capped at
+    /// [`MAX_BATCH_SIZE`] recipients.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The instruction context containing the vault, owner, and system program.
+    /// * `recipients` - Public keys of the accounts to receive SOL.
+    /// * `amounts` - Lamport amounts corresponding to each recipient.
+    /// * `memo` - A reference string of at most [`MAX_MEMO_LEN`] bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VaultError::MemoTooLong`] if `memo` exceeds [`MAX_MEMO_LEN`] bytes.
+    /// Returns [`VaultError::MemoBatchTooLarge`] if there are more than
+    ///   [`MAX_BATCH_SIZE`] recipients.
+    /// Otherwise returns the errors documented on [`execute_batch`].
+    pub fn execute_batch_with_memo<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteBatch<'info>>,
+        recipients: Vec<Pubkey>,
+        amounts: Vec<u64>,
+        memo: String,
+    ) -> Result<()> {
+        validate_memo_batch(&memo, recipients.len())?;
+        process_batch(ctx, recipients, amounts, MAX_BATCH_SIZE, memo, false)
+    }
+
+    /// Executes a batched transfer for transactions that load recipients from
+    /// an Address Lookup Table.
+    ///
+    /// Behaves exactly like [`execute_batch`] but raises the recipient ceiling to
+    /// [`MAX_BATCH_WITH_LUT`]. A v0 transaction referencing a lookup table only
+    /// spends one byte per recipient account instead of 32, which is what makes
+    /// the larger batch fit. The program cannot observe how the transaction
+    /// encoded its account keys, so each recipient is still resolved and checked
+    /// for writability through `remaining_accounts`. Partial execution is not
+    /// available here: the batch is always all-or-nothing, as with
+    /// `allow_partial = false`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The instruction context containing the vault, owner, and system program.
+    /// * `recipients` - Public keys of the accounts to receive SOL.
+    /// * `amounts` - Lamport amounts corresponding to each recipient.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`execute_batch`], except that [`VaultError::BatchTooLarge`] is only
+    /// returned above [`MAX_BATCH_WITH_LUT`] recipients.
+    pub fn execute_batch_with_lut<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteBatch<'info>>,
+        recipients: Vec<Pubkey>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        process_batch(
+            ctx,
+            recipients,
+            amounts,
+            MAX_BATCH_WITH_LUT,
+            String::new(),
+            false,
+        )
+    }
+
+    /// Validates a batch exactly like [`execute_batch`] without moving any
+    /// lamports, and emits a [`BatchPreview`] event with the accounting.
+    ///
+    /// Nothing is written, so clients can check a payout on devnet or mainnet
+    /// by sending the transaction to `simulateTransaction` and decoding the
+    /// event from the logs before committing with [`execute_batch`]. The
+    /// validation is shared with `execute_batch`, including the writability
+    /// check on each recipient passed in `remaining_accounts` and the
+    /// withdrawal limit for the current window.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The instruction context containing the vault and owner accounts.
+    /// * `recipients` - Public keys of the accounts that would receive SOL.
+    /// * `amounts` - Lamport amounts corresponding to each recipient.
+    /// * `use_lut` - Validate against [`MAX_BATCH_WITH_LUT`], the cap of
+    ///   [`execute_batch_with_lut`], instead of [`MAX_BATCH_SIZE`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`execute_batch`] or, with `use_lut`, [`execute_batch_with_lut`].
+    pub fn preview_batch(
+        ctx: Context<PreviewBatch>,
+        recipients: Vec<Pubkey>,
+        amounts: Vec<u64>,
+        use_lut: bool,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let vault = &ctx.accounts.vault;
+        let total_amount = validate_batch(
+            vault,
+            &vault.to_account_info(),
+            ctx.remaining_accounts,
+            &recipients,
+            &amounts,
+            if use_lut {
+                MAX_BATCH_WITH_LUT
+            } else {
+                MAX_BATCH_SIZE
+            },
+            false,
+        )?
+        .total_amount;
+        vault.check_withdrawal(now, total_amount)?;
+
+        emit!(BatchPreview {
+            vault: vault.key(),
+            owner: vault.owner,
+            recipients,
+            amounts,
+            total_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Pauses or resumes deposits and withdrawals for a vault.
+    ///
+    /// Intended as an emergency brake for an owner who suspects key compromise:
+    /// while paused, [`deposit`], [`sweep`], [`close_vault`] (forced or not)
+    /// and the `execute_batch*` instructions are rejected. Funds stay in the
+    /// vault until the owner resumes it.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The instruction context containing the vault and owner accounts.
+    /// * `paused` - `true` to freeze the vault, `false` to resume normal operation.
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.paused = paused;
+
+        emit!(PauseToggled {
+            vault: vault.key(),
+            paused,
+        });
+
+        Ok(())
+    }
+
+    /// Sets the maximum number of lamports that may leave the vault per
+    /// [`WITHDRAW_WINDOW_SECS`] window.
+    ///
+    /// The window is a fixed 24-hour period that starts with the first
+    /// withdrawal after the previous window elapsed; it is not a sliding window.
+    /// A `limit` of zero disables the check, which is also the default for new
+    /// and pre-existing vaults. Lowering the limit mid-window takes effect
+    /// immediately against what has already been withdrawn in that window.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The instruction context containing the vault and owner accounts.
+    /// * `limit` - Maximum lamports per window, or `0` for unlimited.
+    pub fn set_withdraw_limit(ctx: Context<SetWithdrawLimit>, limit: u64) -> Result<()> {
+        ctx.accounts.vault.daily_withdraw_limit = limit;
+        Ok(())
+    }
+
+    /// Sets the smallest deposit the vault will accept, in lamports.
+    ///
+    /// Filters out dust deposits that would otherwise inflate `tx_count`. A
+    /// `min` of zero accepts any non-zero deposit, matching the behavior of
+    /// vaults that never set a minimum.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The instruction context containing the vault and owner accounts.
+    /// * `min` - Minimum lamports per deposit, or `0` for no minimum.
+    pub fn set_min_deposit(ctx: Context<SetMinDeposit>, min: u64) -> Result<()> {
+        ctx.accounts.vault.min_deposit = min;
+        Ok(())
+    }
+
+    /// Transfers everything above the rent-exempt minimum and `rent_reserve` to
+    /// a single recipient.
+    ///
+    /// Empties the vault without closing it, so the account (and its history)
+    /// stays alive for future deposits. Subject to the same pause and
+    /// withdrawal-limit checks as batch transfers.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The instruction context containing the vault, owner, recipient, and system program.
+    /// * `recipient` - The account receiving the swept lamports; must match `ctx.accounts.recipient_account`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VaultError::VaultPaused`] if the vault is paused.
+    /// Returns [`VaultError::InsufficientFunds`] if nothing is available above the
+    ///   rent-exempt minimum and `rent_reserve`.
+    /// Returns [`VaultError::WithdrawLimitExceeded`] if the sweep would exceed the
+    ///   current window's withdrawal limit.
+    /// Returns [`VaultError::Overflow`] if the running totals would overflow.
+    pub fn sweep(ctx: Context<Sweep>, recipient: Pubkey) -> Result<()> {
+        require!(!ctx.accounts.vault.paused, VaultError::VaultPaused);
 
-        // Ensure the vault retains enough lamports for rent exemption.
         let vault_info = ctx.accounts.vault.to_account_info();
-        let rent = Rent::get()?;
-        let rent_exempt_min = rent.minimum_balance(vault_info.data_len());
-        let available = vault_info
-            .lamports()
-            .checked_sub(rent_exempt_min)
-            .ok_or(VaultError::InsufficientFunds)?;
-        require!(available >= total_amount, VaultError::InsufficientFunds);
+        let rent_exempt_min = Rent::get()?.minimum_balance(vault_info.data_len());
+        let reserved = ctx.accounts.vault.reserved_lamports(rent_exempt_min);
+        let amount = vault_info.lamports().saturating_sub(reserved);
+        require!(amount > 0, VaultError::InsufficientFunds);
+
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.vault.record_withdrawal(now, amount)?;
 
         // Build the PDA signer seeds for the vault.
         let vault = &ctx.accounts.vault;
@@ -147,51 +439,91 @@ pub mod tx_vault {
         let name_bytes = vault.name.as_bytes();
         let bump = &[vault.bump];
         let signer_seeds: &[&[u8]] = &[b"vault", owner_key.as_ref(), name_bytes, bump];
+        let signer = &[signer_seeds];
 
-        // Transfer SOL to each recipient via CPI, signed by the vault PDA.
-        let recipient_count = recipients.len() as u8;
-        for (i, recipient) in recipients.iter().enumerate() {
-            let amount = amounts[i];
-            if amount == 0 {
-                continue;
-            }
-
-            // Locate the matching remaining account for this recipient.
-            let recipient_info = ctx
-                .remaining_accounts
-                .iter()
-                .find(|a| a.key == recipient)
-                .ok_or(ErrorCode::AccountNotEnoughKeys)?;
-
-            require!(recipient_info.is_writable, VaultError::RecipientNotWritable);
-
-            let cpi_context = CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.vault.to_account_info(),
-                    to: recipient_info.clone(),
-                },
-                &[signer_seeds],
-            );
-            system_program::transfer(cpi_context, amount)?;
-        }
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.recipient_account.to_account_info(),
+            },
+            signer,
+        );
+        system_program::transfer(cpi_context, amount)?;
 
-        // Update vault accounting.
         let vault = &mut ctx.accounts.vault;
         vault.total_withdrawn = vault
             .total_withdrawn
-            .checked_add(total_amount)
+            .checked_add(amount)
             .ok_or(VaultError::Overflow)?;
         vault.tx_count = vault
             .tx_count
             .checked_add(1)
             .ok_or(VaultError::Overflow)?;
 
-        emit!(BatchExecuted {
+        emit!(SweepExecuted {
             vault: vault.key(),
-            owner: owner_key,
-            recipient_count,
-            total_amount,
+            recipient,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Returns a [`VaultSummary`] of the vault's state as return data.
+    ///
+    /// The instruction writes nothing, so clients can read the summary without
+    /// paying fees by simulating a transaction that calls it: send the
+    /// transaction to `simulateTransaction`, take `returnData.data[0]` from the
+    /// result, base64-decode it, and borsh-deserialize it as a `VaultSummary`.
+    /// The summary layout is kept stable even if [`Vault`] changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The instruction context containing the vault account.
+    pub fn get_vault_summary(ctx: Context<GetVaultSummary>) -> Result<VaultSummary> {
+        Ok(VaultSummary::new(
+            &ctx.accounts.vault,
+            ctx.accounts.vault.to_account_info().lamports(),
+        ))
+    }
+
+    /// Grows a vault created by an older version of the program to
+    /// [`Vault::SPACE`].
+    ///
+    /// Vaults created before fields were appended to [`Vault`] are too short
+    /// to deserialize, so every other instruction, including
+    /// [`close_vault`], fails on them. The `realloc` constraint tops the
+    /// account up to the new rent-exempt minimum from the owner and zeroes
+    /// the added bytes, which decode as the defaults of the new fields
+    /// (unpaused, no withdrawal limit, no minimum deposit, no rent reserve).
+    /// Calling it on a vault that is already the current size does nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The instruction context containing the vault, owner, and system program.
+    /// * `name` - The vault's name, used to re-derive its address.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ErrorCode::AccountDiscriminatorMismatch` if the account is not
+    /// a vault, or `ErrorCode::ConstraintHasOne` if it belongs to another owner.
+    pub fn migrate_vault(ctx: Context<MigrateVault>, name: String) -> Result<()> {
+        let owner = ctx.accounts.owner.key();
+        {
+            let data = ctx.accounts.vault.try_borrow_data()?;
+            if data.get(..8) != Some(&Vault::DISCRIMINATOR[..]) {
+                return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+            }
+            if data.get(8..40) != Some(&owner.to_bytes()[..]) {
+                return Err(ErrorCode::ConstraintHasOne.into());
+            }
+        }
+
+        emit!(VaultMigrated {
+            vault: ctx.accounts.vault.key(),
+            owner,
+            name,
         });
 
         Ok(())
@@ -202,20 +534,347 @@ pub mod tx_vault {
     /// This instruction uses Anchor's `close` constraint, which zeroes the
     /// account data, transfers lamports, and assigns ownership back to the
     /// System Program — making the account eligible for garbage collection.
+    /// The `close` constraint only runs after the handler returns, so the final
+    /// accounting is read here and preserved in the [`VaultClosed`] event.
+    ///
+    /// A vault holding more than its rent-exempt minimum and `rent_reserve` is
+    /// refused with
+    /// [`VaultError::VaultNotEmpty`] unless `force` is set, so funds are not
+    /// swept to the owner by accident. Even when forced, that excess counts as
+    /// a withdrawal and must fit in the current window's
+    /// `daily_withdraw_limit`, so a compromised owner key cannot drain a
+    /// limited vault by closing it.
     ///
     /// # Arguments
     ///
     /// * `ctx` - The instruction context containing the vault and owner accounts.
-    pub fn close_vault(ctx: Context<CloseVault>) -> Result<()> {
+    /// * `force` - Close even if the vault still holds funds beyond rent.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VaultError::VaultPaused`] if the vault is paused, even with
+    ///   `force`.
+    /// Returns [`VaultError::VaultNotEmpty`] if the vault holds funds beyond
+    ///   rent and `force` is not set.
+    /// Returns [`VaultError::WithdrawLimitExceeded`] if those funds would
+    ///   exceed the current window's withdrawal limit.
+    pub fn close_vault(ctx: Context<CloseVault>, force: bool) -> Result<()> {
+        let vault = &ctx.accounts.vault;
+        let vault_info = vault.to_account_info();
+        let rent_exempt_min = Rent::get()?.minimum_balance(vault_info.data_len());
+        let reserved = vault.reserved_lamports(rent_exempt_min);
+        let now = Clock::get()?.unix_timestamp;
+        ensure_closable(vault, vault_info.lamports(), reserved, force, now)?;
+
         emit!(VaultClosed {
-            vault: ctx.accounts.vault.key(),
+            vault: vault.key(),
             owner: ctx.accounts.owner.key(),
+            total_deposited: vault.total_deposited,
+            total_withdrawn: vault.total_withdrawn,
+            tx_count: vault.tx_count,
+            reclaimed_lamports: vault_info.lamports(),
         });
 
         Ok(())
     }
 }
 
+// ---------------------------------------------------------------------------
+// Batch execution
+// ---------------------------------------------------------------------------
+
+/// Maximum number of recipients per batch for a legacy transaction, where every
+/// recipient account key is listed inline.
+pub const MAX_BATCH_SIZE: usize = 10;
+
+/// Maximum number of recipients per batch when the transaction resolves
+/// recipient accounts through an Address Lookup Table.
+pub const MAX_BATCH_WITH_LUT: usize = 30;
+
+/// Maximum length in bytes of the memo accepted by
+/// [`tx_vault::execute_batch_with_memo`].
+pub const MAX_MEMO_LEN: usize = 64;
+
+/// Length of a withdrawal rate-limit window, in seconds (24 hours).
+pub const WITHDRAW_WINDOW_SECS: i64 = 86_400;
+
+/// Checks the memo and recipient count of
+/// [`tx_vault::execute_batch_with_memo`], which has no lookup-table variant.
+fn validate_memo_batch(memo: &str, recipient_count: usize) -> Result<()> {
+    require!(memo.len() <= MAX_MEMO_LEN, VaultError::MemoTooLong);
+    require!(
+        recipient_count <= MAX_BATCH_SIZE,
+        VaultError::MemoBatchTooLarge
+    );
+    Ok(())
+}
+
+/// Shared implementation of the `execute_batch*` instructions, parameterized
+/// by the recipient cap, the memo to emit and whether recipients that cannot
+/// be paid are skipped.
+fn process_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteBatch<'info>>,
+    recipients: Vec<Pubkey>,
+    amounts: Vec<u64>,
+    max_recipients: usize,
+    memo: String,
+    allow_partial: bool,
+) -> Result<()> {
+    // Dry pass: resolve every recipient account before anything moves, so
+    // the transfer loop below cannot fail partway on a missing account.
+    let batch = validate_batch(
+        &ctx.accounts.vault,
+        &ctx.accounts.vault.to_account_info(),
+        ctx.remaining_accounts,
+        &recipients,
+        &amounts,
+        max_recipients,
+        allow_partial,
+    )?;
+    let total_amount = batch.total_amount;
+
+    // Pre-compute the new accounting totals so an overflow also fails
+    // before any transfer.
+    let vault = &ctx.accounts.vault;
+    let total_withdrawn = vault
+        .total_withdrawn
+        .checked_add(total_amount)
+        .ok_or(VaultError::Overflow)?;
+    let tx_count = vault
+        .tx_count
+        .checked_add(1)
+        .ok_or(VaultError::Overflow)?;
+
+    // Enforce the per-window withdrawal limit before any lamports move.
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.vault.record_withdrawal(now, total_amount)?;
+
+    // Build the PDA signer seeds for the vault.
+    let vault = &ctx.accounts.vault;
+    let owner_key = vault.owner;
+    let name_bytes = vault.name.as_bytes();
+    let bump = &[vault.bump];
+    let signer_seeds: &[&[u8]] = &[b"vault", owner_key.as_ref(), name_bytes, bump];
+    let signer = &[signer_seeds];
+
+    // Transfer SOL to each recipient via CPI, signed by the vault PDA.
+    let recipient_count = recipients.len() as u8;
+    let succeeded: Vec<Pubkey> = batch.transfers.iter().map(|(info, _)| *info.key).collect();
+    for (recipient_info, amount) in batch.transfers {
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: recipient_info.clone(),
+            },
+            signer,
+        );
+        system_program::transfer(cpi_context, amount)?;
+    }
+
+    // Update vault accounting.
+    let vault = &mut ctx.accounts.vault;
+    vault.total_withdrawn = total_withdrawn;
+    vault.tx_count = tx_count;
+
+    if batch.skipped.is_empty() {
+        emit!(BatchExecuted {
+            vault: vault.key(),
+            owner: owner_key,
+            recipient_count,
+            total_amount,
+            memo,
+        });
+    } else {
+        emit!(BatchPartiallyExecuted {
+            vault: vault.key(),
+            owner: owner_key,
+            succeeded,
+            skipped: batch.skipped,
+            total_amount,
+            memo,
+        });
+    }
+
+    Ok(())
+}
+
+/// A batch that passed [`validate_batch`].
+struct ValidatedBatch<'a, 'info> {
+    /// Total outbound lamports, counting only `transfers`.
+    total_amount: u64,
+    /// Account and amount of every recipient that is actually paid, in batch
+    /// order.
+    transfers: Vec<(&'a AccountInfo<'info>, u64)>,
+    /// Recipients left out because they could not be paid; always empty
+    /// unless partial execution was allowed.
+    skipped: Vec<Pubkey>,
+}
+
+/// Recipients of a batch paired with their accounts by [`resolve_transfers`].
+#[derive(Debug)]
+struct ResolvedTransfers<'a, 'info> {
+    /// Account and amount of every recipient to pay, in batch order.
+    transfers: Vec<(&'a AccountInfo<'info>, u64)>,
+    /// Recipients with a non-zero amount that could not be paid.
+    skipped: Vec<Pubkey>,
+}
+
+/// Checks everything about a batch that does not move lamports, shared by
+/// `execute_batch*` and [`tx_vault::preview_batch`] so the two cannot drift.
+///
+/// Returns the total outbound amount along with each paid recipient's
+/// account. The withdrawal limit is left to the caller, which either records
+/// or only checks it. With `allow_partial`, recipients that cannot be paid
+/// are skipped and left out of the total; see [`resolve_transfers`].
+fn validate_batch<'a, 'info>(
+    vault: &Vault,
+    vault_info: &AccountInfo,
+    remaining_accounts: &'a [AccountInfo<'info>],
+    recipients: &[Pubkey],
+    amounts: &[u64],
+    max_recipients: usize,
+    allow_partial: bool,
+) -> Result<ValidatedBatch<'a, 'info>> {
+    require!(!vault.paused, VaultError::VaultPaused);
+    require!(recipients.len() == amounts.len(), VaultError::LengthMismatch);
+    require!(!recipients.is_empty(), VaultError::EmptyBatch);
+    if recipients.len() > max_recipients {
+        if recipients.len() <= MAX_BATCH_WITH_LUT {
+            return err!(VaultError::BatchRequiresLookupTable);
+        }
+        return err!(VaultError::BatchTooLarge);
+    }
+    ensure_unique_recipients(recipients)?;
+
+    // Reject a batch whose full total overflows, even if skipping recipients
+    // would bring it back in range.
+    amounts
+        .iter()
+        .try_fold(0u64, |acc, &amt| acc.checked_add(amt))
+        .ok_or(VaultError::Overflow)?;
+
+    // Only lamports that will actually move count toward the total.
+    let resolved = resolve_transfers(remaining_accounts, recipients, amounts, allow_partial)?;
+    let total_amount: u64 = resolved.transfers.iter().map(|(_, amount)| amount).sum();
+
+    // Ensure the vault retains enough lamports for rent exemption and the
+    // owner's rent reserve.
+    let rent = Rent::get()?;
+    let rent_exempt_min = rent.minimum_balance(vault_info.data_len());
+    let available = vault_info
+        .lamports()
+        .checked_sub(vault.reserved_lamports(rent_exempt_min))
+        .ok_or(VaultError::InsufficientFunds)?;
+    require!(available >= total_amount, VaultError::InsufficientFunds);
+
+    Ok(ValidatedBatch {
+        total_amount,
+        transfers: resolved.transfers,
+        skipped: resolved.skipped,
+    })
+}
+
+/// Pairs every recipient with a non-zero amount with its account from
+/// `remaining_accounts`, in batch order.
+///
+/// Each paid recipient must be passed in writable. Zero amounts need no
+/// account and are neither paid nor skipped. A recipient that is missing or
+/// not writable fails the batch, or with `allow_partial` is listed as
+/// skipped.
+fn resolve_transfers<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    recipients: &[Pubkey],
+    amounts: &[u64],
+    allow_partial: bool,
+) -> Result<ResolvedTransfers<'a, 'info>> {
+    let mut resolved = ResolvedTransfers {
+        transfers: Vec::with_capacity(recipients.len()),
+        skipped: Vec::new(),
+    };
+    for (recipient, &amount) in recipients.iter().zip(amounts) {
+        if amount == 0 {
+            continue;
+        }
+        let recipient_info = match remaining_accounts.iter().find(|a| a.key == recipient) {
+            Some(info) if info.is_writable => info,
+            _ if allow_partial => {
+                resolved.skipped.push(*recipient);
+                continue;
+            }
+            Some(_) => return err!(VaultError::RecipientNotWritable),
+            None => return Err(ErrorCode::AccountNotEnoughKeys.into()),
+        };
+        resolved.transfers.push((recipient_info, amount));
+    }
+    Ok(resolved)
+}
+
+/// Rejects batches that list the same recipient more than once.
+///
+/// Runs before any transfer so a duplicate never results in a partially
+/// executed batch. Batches are capped at [`MAX_BATCH_WITH_LUT`] entries, so the
+/// quadratic scan is cheaper than allocating a sorted copy.
+fn ensure_unique_recipients(recipients: &[Pubkey]) -> Result<()> {
+    for (i, recipient) in recipients.iter().enumerate() {
+        require!(
+            !recipients[i + 1..].contains(recipient),
+            VaultError::DuplicateRecipient
+        );
+    }
+    Ok(())
+}
+
+/// Rejects closing a paused vault, a vault whose balance exceeds `reserved`
+/// (see [`Vault::reserved_lamports`]) unless `force` is set, and a forced
+/// close whose excess would break the withdrawal limit at `now`.
+///
+/// The vault is closed right after, so the withdrawal is only checked, not
+/// recorded.
+fn ensure_closable(
+    vault: &Vault,
+    lamports: u64,
+    reserved: u64,
+    force: bool,
+    now: i64,
+) -> Result<()> {
+    require!(!vault.paused, VaultError::VaultPaused);
+    let excess = lamports.saturating_sub(reserved);
+    if excess > 0 {
+        require!(force, VaultError::VaultNotEmpty);
+        vault.check_withdrawal(now, excess)?;
+    }
+    Ok(())
+}
+
+/// Whether [`tx_vault::initialize_vault`] rejects names that are not
+/// normalized.
+///
+/// The name is used verbatim as a PDA seed, so "Savings" and "Savings " would
+/// otherwise be two distinct vaults that look identical to users. Changing
+/// this only affects vaults created afterwards: existing vaults stay derived
+/// from the exact bytes they were created with and must keep being addressed
+/// that way, so never normalize a name before deriving an existing vault.
+pub const NORMALIZE_VAULT_NAMES: bool = true;
+
+/// Checks a vault name before it is used as a PDA seed.
+///
+/// The name must fit in 32 bytes. With `normalize`, it must also have no
+/// leading or trailing whitespace and no control characters. Names are
+/// rejected rather than rewritten, because the seeds are derived from the
+/// instruction argument before the handler runs; clients trim the name
+/// themselves.
+fn validate_vault_name(name: &str, normalize: bool) -> Result<()> {
+    require!(name.len() <= 32, VaultError::NameTooLong);
+    if normalize {
+        require!(
+            name.trim() == name && !name.chars().any(char::is_control),
+            VaultError::InvalidName
+        );
+    }
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Account structures
 // ---------------------------------------------------------------------------
@@ -240,22 +899,49 @@ pub struct Vault {
     pub bump: u8,
     /// Unix timestamp when the vault was created.
     pub created_at: i64,
+    /// When `true`, deposits, withdrawals and closing are rejected.
+    pub paused: bool,
+    /// Maximum lamports that may be withdrawn per window; `0` means unlimited.
+    pub daily_withdraw_limit: u64,
+    /// Unix timestamp at which the current withdrawal window started.
+    pub window_start: i64,
+    /// Lamports withdrawn since `window_start`.
+    pub withdrawn_in_window: u64,
+    /// Smallest accepted deposit in lamports; `0` means no minimum.
+    pub min_deposit: u64,
+    /// Lamports set aside by [`tx_vault::fund_rent`] on top of the rent-exempt
+    /// minimum; withdrawals never spend them.
+    pub rent_reserve: u64,
 }
 
 impl Vault {
     /// Account space calculation including the 8-byte Anchor discriminator.
     ///
     /// Layout:
-    /// - discriminator:     8
-    /// - owner (Pubkey):   32
-    /// - name (String):     4 (length prefix) + 32 (max chars)
-    /// - total_deposited:   8
-    /// - total_withdrawn:   8
-    /// - tx_count:          8
-    /// - bump:              1
-    /// - created_at:        8
-    /// -------------------------
-    /// Total:             109
+    /// - discriminator:           8
+    /// - owner (Pubkey):         32
+    /// - name (String):           4 (length prefix) + 32 (max chars)
+    /// - total_deposited:         8
+    /// - total_withdrawn:         8
+    /// - tx_count:                8
+    /// - bump:                    1
+    /// - created_at:              8
+    /// - paused:                  1
+    /// - daily_withdraw_limit:    8
+    /// - window_start:            8
+    /// - withdrawn_in_window:     8
+    /// - min_deposit:             8
+    /// - rent_reserve:            8
+    /// -------------------------------
+    /// Total:                   150
+    ///
+    /// Migration notes — vaults created before a size change are too short and
+    /// will fail to deserialize until they are grown with
+    /// [`tx_vault::migrate_vault`]:
+    /// - `paused` grew the account from 109 to 110 bytes.
+    /// - The withdrawal-limit fields grew it from 110 to 134 bytes.
+    /// - `min_deposit` grew it from 134 to 142 bytes.
+    /// - `rent_reserve` grew it from 142 to 150 bytes.
     pub const SPACE: usize = 8  // discriminator
         + 32                    // owner
         + 4 + 32               // name (borsh string: 4-byte len + max content)
@@ -263,7 +949,124 @@ impl Vault {
         + 8                     // total_withdrawn
         + 8                     // tx_count
         + 1                     // bump
-        + 8;                    // created_at
+        + 8                     // created_at
+        + 1                     // paused
+        + 8                     // daily_withdraw_limit
+        + 8                     // window_start
+        + 8                     // withdrawn_in_window
+        + 8                     // min_deposit
+        + 8;                    // rent_reserve
+
+    /// Lamports the vault must keep: the rent-exempt minimum plus
+    /// `rent_reserve`.
+    pub fn reserved_lamports(&self, rent_exempt_min: u64) -> u64 {
+        rent_exempt_min.saturating_add(self.rent_reserve)
+    }
+
+    /// Checks that a deposit of `amount` lamports is currently allowed.
+    pub fn validate_deposit(&self, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::ZeroAmount);
+        require!(!self.paused, VaultError::VaultPaused);
+        require!(amount >= self.min_deposit, VaultError::BelowMinDeposit);
+        Ok(())
+    }
+
+    /// Records an outbound transfer against the current withdrawal window.
+    ///
+    /// Starts a fresh window when [`WITHDRAW_WINDOW_SECS`] have elapsed since
+    /// `window_start`, then rejects the withdrawal if it would exceed
+    /// `daily_withdraw_limit`. A zero limit skips the check but the window is
+    /// still tracked so a limit set later applies to the current window.
+    pub fn record_withdrawal(&mut self, now: i64, amount: u64) -> Result<()> {
+        let (window_start, withdrawn) = self.check_withdrawal(now, amount)?;
+        self.window_start = window_start;
+        self.withdrawn_in_window = withdrawn;
+        Ok(())
+    }
+
+    /// Checks an outbound transfer against the withdrawal window without
+    /// recording it, returning the `(window_start, withdrawn_in_window)` it
+    /// would leave behind.
+    pub fn check_withdrawal(&self, now: i64, amount: u64) -> Result<(i64, u64)> {
+        let (window_start, already_withdrawn) =
+            if now.saturating_sub(self.window_start) >= WITHDRAW_WINDOW_SECS {
+                (now, 0)
+            } else {
+                (self.window_start, self.withdrawn_in_window)
+            };
+
+        let withdrawn = already_withdrawn
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+        if self.daily_withdraw_limit > 0 {
+            require!(
+                withdrawn <= self.daily_withdraw_limit,
+                VaultError::WithdrawLimitExceeded
+            );
+        }
+
+        Ok((window_start, withdrawn))
+    }
+}
+
+/// Cumulative contribution of one depositor to one vault.
+///
+/// Derived from `["depositor", vault, depositor]` and created lazily by
+/// [`tx_vault::deposit`]. The aggregate totals on [`Vault`] are unaffected.
+#[account]
+pub struct DepositorRecord {
+    /// The vault this record belongs to.
+    pub vault: Pubkey,
+    /// The wallet whose deposits are tracked.
+    pub depositor: Pubkey,
+    /// Cumulative lamports this depositor has put into the vault.
+    pub total_deposited: u64,
+    /// The PDA bump seed.
+    pub bump: u8,
+}
+
+impl DepositorRecord {
+    /// Account space: discriminator (8) + vault (32) + depositor (32) +
+    /// total_deposited (8) + bump (1).
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+/// Stable, read-only view of a vault returned by
+/// [`tx_vault::get_vault_summary`].
+///
+/// Fields are only ever appended, so integrators can decode it without
+/// tracking changes to the [`Vault`] account layout.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VaultSummary {
+    /// The authority allowed to execute withdrawals and close the vault.
+    pub owner: Pubkey,
+    /// Human-readable label for this vault.
+    pub name: String,
+    /// Cumulative lamports deposited into the vault.
+    pub total_deposited: u64,
+    /// Cumulative lamports withdrawn from the vault.
+    pub total_withdrawn: u64,
+    /// Number of deposit and batch-execute transactions processed.
+    pub tx_count: u64,
+    /// The vault's current lamport balance, including the rent reserve.
+    pub balance: u64,
+    /// Unix timestamp when the vault was created.
+    pub created_at: i64,
+}
+
+impl VaultSummary {
+    /// Builds the summary from a vault and its current lamport balance.
+    pub fn new(vault: &Vault, balance: u64) -> Self {
+        Self {
+            owner: vault.owner,
+            name: vault.name.clone(),
+            total_deposited: vault.total_deposited,
+            total_withdrawn: vault.total_withdrawn,
+            tx_count: vault.tx_count,
+            balance,
+            created_at: vault.created_at,
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -310,6 +1113,37 @@ pub struct Deposit<'info> {
     #[account(mut)]
     pub depositor: Signer<'info>,
 
+    /// Per-depositor running total, created on the depositor's first deposit.
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        space = DepositorRecord::SPACE,
+        seeds = [b"depositor", vault.key().as_ref(), depositor.key().as_ref()],
+        bump,
+    )]
+    pub depositor_record: Account<'info, DepositorRecord>,
+
+    /// The Solana System Program, required for the SOL transfer CPI.
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts required by [`tx_vault::fund_rent`].
+#[derive(Accounts)]
+pub struct FundRent<'info> {
+    /// The vault whose rent reserve is topped up. Only the recorded owner may
+    /// earmark funds in it.
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [b"vault", vault.owner.as_ref(), vault.name.as_bytes()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// The vault owner funding the reserve.
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
     /// The Solana System Program, required for the SOL transfer CPI.
     pub system_program: Program<'info, System>,
 }
@@ -338,6 +1172,140 @@ pub struct ExecuteBatch<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// Accounts required by [`tx_vault::preview_batch`].
+///
+/// Like [`ExecuteBatch`], but the vault is read-only and no System Program is
+/// needed because nothing is transferred. Recipients are passed via
+/// `ctx.remaining_accounts` so their writability can be checked.
+#[derive(Accounts)]
+pub struct PreviewBatch<'info> {
+    /// The vault the batch would be paid from. Only the recorded owner may
+    /// preview its payouts.
+    #[account(
+        has_one = owner,
+        seeds = [b"vault", vault.owner.as_ref(), vault.name.as_bytes()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// The vault owner who would authorize the batch.
+    pub owner: Signer<'info>,
+}
+
+/// Accounts required by [`tx_vault::set_min_deposit`].
+#[derive(Accounts)]
+pub struct SetMinDeposit<'info> {
+    /// The vault whose deposit minimum is updated. Only the recorded owner may
+    /// change it.
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [b"vault", vault.owner.as_ref(), vault.name.as_bytes()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// The vault owner authorizing the change.
+    pub owner: Signer<'info>,
+}
+
+/// Accounts required by [`tx_vault::sweep`].
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey)]
+pub struct Sweep<'info> {
+    /// The vault to sweep. Only the recorded owner may authorize it.
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [b"vault", vault.owner.as_ref(), vault.name.as_bytes()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// The vault owner authorizing the sweep.
+    pub owner: Signer<'info>,
+
+    /// CHECK: Any system account may receive SOL; the address is pinned to
+    /// the `recipient` instruction argument.
+    #[account(mut, address = recipient)]
+    pub recipient_account: UncheckedAccount<'info>,
+
+    /// The Solana System Program, required for the outbound transfer CPI.
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts required by [`tx_vault::get_vault_summary`].
+#[derive(Accounts)]
+pub struct GetVaultSummary<'info> {
+    /// The vault to summarize. Read-only; anyone may query it.
+    #[account(
+        seeds = [b"vault", vault.owner.as_ref(), vault.name.as_bytes()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+/// Accounts required by [`tx_vault::set_paused`].
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    /// The vault whose paused flag is toggled. Only the recorded owner may
+    /// change it.
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [b"vault", vault.owner.as_ref(), vault.name.as_bytes()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// The vault owner authorizing the change.
+    pub owner: Signer<'info>,
+}
+
+/// Accounts required by [`tx_vault::set_withdraw_limit`].
+#[derive(Accounts)]
+pub struct SetWithdrawLimit<'info> {
+    /// The vault whose withdrawal limit is updated. Only the recorded owner may
+    /// change it.
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [b"vault", vault.owner.as_ref(), vault.name.as_bytes()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// The vault owner authorizing the change.
+    pub owner: Signer<'info>,
+}
+
+/// Accounts required by [`tx_vault::migrate_vault`].
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct MigrateVault<'info> {
+    /// CHECK: A vault laid out for an older version of the program, which
+    /// `Account<Vault>` cannot deserialize. The seeds tie it to `owner` and
+    /// `name`, the owner constraint to this program, and the handler checks
+    /// its discriminator and stored owner.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"vault", owner.key().as_ref(), name.as_bytes()],
+        bump,
+        realloc = Vault::SPACE,
+        realloc::payer = owner,
+        realloc::zero = true,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// The vault owner, paying for the extra rent.
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The Solana System Program, required for the rent top-up.
+    pub system_program: Program<'info, System>,
+}
+
 /// Accounts required by [`tx_vault::close_vault`].
 #[derive(Accounts)]
 pub struct CloseVault<'info> {
@@ -384,6 +1352,21 @@ pub struct DepositMade {
     pub amount: u64,
     /// The vault's cumulative deposit total after this transaction.
     pub total_deposited: u64,
+    /// This depositor's cumulative contribution after this transaction.
+    pub depositor_total: u64,
+}
+
+/// Emitted when the owner adds to a vault's rent reserve.
+#[event]
+pub struct RentFunded {
+    /// The vault whose reserve grew.
+    pub vault: Pubkey,
+    /// The owner who funded the reserve.
+    pub owner: Pubkey,
+    /// The number of lamports added.
+    pub amount: u64,
+    /// The vault's rent reserve after this transaction.
+    pub rent_reserve: u64,
 }
 
 /// Emitted when a batch transfer is executed from a vault.
@@ -397,6 +1380,74 @@ pub struct BatchExecuted {
     pub recipient_count: u8,
     /// The total lamports transferred across all recipients.
     pub total_amount: u64,
+    /// Caller-supplied reference; empty when no memo was attached.
+    pub memo: String,
+}
+
+/// Emitted instead of [`BatchExecuted`] when a batch sent with
+/// `allow_partial` skipped recipients that could not be paid.
+#[event]
+pub struct BatchPartiallyExecuted {
+    /// The vault from which SOL was disbursed.
+    pub vault: Pubkey,
+    /// The owner who authorized the batch.
+    pub owner: Pubkey,
+    /// The recipients that were paid, in batch order.
+    pub succeeded: Vec<Pubkey>,
+    /// The recipients that were missing or not writable, in batch order.
+    pub skipped: Vec<Pubkey>,
+    /// The total lamports transferred to `succeeded`.
+    pub total_amount: u64,
+    /// Caller-supplied reference; empty when no memo was attached.
+    pub memo: String,
+}
+
+/// Emitted by [`tx_vault::preview_batch`] with the accounting a batch would
+/// have; no lamports moved.
+#[event]
+pub struct BatchPreview {
+    /// The vault the batch would be paid from.
+    pub vault: Pubkey,
+    /// The owner who would authorize the batch.
+    pub owner: Pubkey,
+    /// The recipients, in batch order.
+    pub recipients: Vec<Pubkey>,
+    /// The lamports each recipient would receive, matching `recipients`.
+    pub amounts: Vec<u64>,
+    /// The total lamports the batch would transfer.
+    pub total_amount: u64,
+}
+
+/// Emitted when a vault's spendable balance is swept to a single recipient.
+#[event]
+pub struct SweepExecuted {
+    /// The vault that was swept.
+    pub vault: Pubkey,
+    /// The account that received the swept lamports.
+    pub recipient: Pubkey,
+    /// The number of lamports transferred.
+    pub amount: u64,
+}
+
+/// Emitted when a vault is paused or resumed.
+#[event]
+pub struct PauseToggled {
+    /// The vault whose state changed.
+    pub vault: Pubkey,
+    /// The new paused state.
+    pub paused: bool,
+}
+
+/// Emitted when [`tx_vault::migrate_vault`] brings a vault up to
+/// [`Vault::SPACE`].
+#[event]
+pub struct VaultMigrated {
+    /// The migrated vault.
+    pub vault: Pubkey,
+    /// The owner who paid for the extra rent.
+    pub owner: Pubkey,
+    /// The vault's name.
+    pub name: String,
 }
 
 /// Emitted when a vault is closed and its lamports reclaimed.
@@ -406,6 +1457,14 @@ pub struct VaultClosed {
     pub vault: Pubkey,
     /// The owner who received the remaining lamports.
     pub owner: Pubkey,
+    /// The vault's cumulative deposit total at close time.
+    pub total_deposited: u64,
+    /// The vault's cumulative withdrawal total at close time.
+    pub total_withdrawn: u64,
+    /// The number of transactions the vault processed over its lifetime.
+    pub tx_count: u64,
+    /// The lamport balance returned to the owner, including rent.
+    pub reclaimed_lamports: u64,
 }
 
 // ---------------------------------------------------------------------------
@@ -435,16 +1494,322 @@ pub enum VaultError {
     #[msg("Batch must contain at least one recipient")]
     EmptyBatch,
 
-    /// The batch exceeds the maximum of 10 recipients.
-    #[msg("Batch must not exceed 10 recipients")]
+    /// The batch exceeds the recipient cap for the instruction used.
+    #[msg("Batch exceeds the maximum number of recipients (10, or 30 with an Address Lookup Table)")]
     BatchTooLarge,
 
-    /// The vault does not hold enough lamports (above rent-exempt minimum) to
-    /// cover the requested transfer.
-    #[msg("Insufficient funds in vault after reserving rent-exempt minimum")]
+    /// The batch is too large for a legacy transaction and must be sent through
+    /// `execute_batch_with_lut` in a transaction that uses an Address Lookup Table.
+    #[msg("Batches over 10 recipients require execute_batch_with_lut and an Address Lookup Table")]
+    BatchRequiresLookupTable,
+
+    /// The vault does not hold enough lamports (above the rent-exempt minimum
+    /// and rent reserve) to cover the requested transfer.
+    #[msg("Insufficient funds in vault after reserving rent-exempt minimum and rent reserve")]
     InsufficientFunds,
 
     /// A recipient account was not passed as writable.
     #[msg("Recipient account must be writable")]
     RecipientNotWritable,
+
+    /// The vault is paused and rejects deposits, withdrawals and closing.
+    #[msg("Vault is paused")]
+    VaultPaused,
+
+    /// The withdrawal would exceed the vault's per-window limit.
+    #[msg("Withdrawal exceeds the vault's daily withdrawal limit")]
+    WithdrawLimitExceeded,
+
+    /// The batch memo exceeds the 64-byte maximum.
+    #[msg("Memo must be 64 bytes or fewer")]
+    MemoTooLong,
+
+    /// The same recipient appears more than once in a batch.
+    #[msg("Batch contains a duplicate recipient")]
+    DuplicateRecipient,
+
+    /// The deposit is smaller than the vault's configured minimum.
+    #[msg("Deposit is below the vault's minimum deposit amount")]
+    BelowMinDeposit,
+
+    /// The vault still holds lamports beyond rent and `force` was not set.
+    #[msg("Vault holds funds beyond rent; sweep it first or close with force")]
+    VaultNotEmpty,
+
+    /// The vault name has leading or trailing whitespace or a control
+    /// character.
+    #[msg("Vault name must not have surrounding whitespace or control characters")]
+    InvalidName,
+
+    /// A batch with a memo has more recipients than a legacy transaction can
+    /// carry; memo batches have no lookup-table variant.
+    #[msg("Batches with a memo are limited to 10 recipients")]
+    MemoBatchTooLarge,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_vault() -> Vault {
+        Vault {
+            owner: Pubkey::new_unique(),
+            name: "test".to_string(),
+            total_deposited: 0,
+            total_withdrawn: 0,
+            tx_count: 0,
+            bump: 255,
+            created_at: 0,
+            paused: false,
+            daily_withdraw_limit: 0,
+            window_start: 0,
+            withdrawn_in_window: 0,
+            min_deposit: 0,
+            rent_reserve: 0,
+        }
+    }
+
+    #[test]
+    fn test_memo_batch_limits() {
+        assert!(validate_memo_batch("invoice-42", MAX_BATCH_SIZE).is_ok());
+        assert_eq!(
+            validate_memo_batch("invoice-42", MAX_BATCH_SIZE + 1).unwrap_err(),
+            VaultError::MemoBatchTooLarge.into()
+        );
+        assert_eq!(
+            validate_memo_batch(&"x".repeat(MAX_MEMO_LEN + 1), 1).unwrap_err(),
+            VaultError::MemoTooLong.into()
+        );
+    }
+
+    #[test]
+    fn test_unique_recipients_accepted() {
+        let recipients = vec![Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        assert!(ensure_unique_recipients(&recipients).is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_recipient_rejected() {
+        let dup = Pubkey::new_unique();
+        let recipients = vec![dup, Pubkey::new_unique(), dup];
+        assert_eq!(
+            ensure_unique_recipients(&recipients).unwrap_err(),
+            VaultError::DuplicateRecipient.into()
+        );
+    }
+
+    #[test]
+    fn test_close_requires_empty_vault_unless_forced() {
+        let vault = test_vault();
+        assert!(ensure_closable(&vault, 1_000, 1_000, false, 0).is_ok());
+        assert_eq!(
+            ensure_closable(&vault, 1_001, 1_000, false, 0).unwrap_err(),
+            VaultError::VaultNotEmpty.into()
+        );
+        assert!(ensure_closable(&vault, 1_001, 1_000, true, 0).is_ok());
+    }
+
+    #[test]
+    fn test_forced_close_respects_withdraw_limit() {
+        let mut vault = test_vault();
+        vault.daily_withdraw_limit = 1_000;
+        vault.window_start = 100;
+        vault.withdrawn_in_window = 600;
+
+        assert!(ensure_closable(&vault, 1_400, 1_000, true, 200).is_ok());
+        assert_eq!(
+            ensure_closable(&vault, 1_401, 1_000, true, 200).unwrap_err(),
+            VaultError::WithdrawLimitExceeded.into()
+        );
+        // Closing with nothing beyond the reserve is never limited.
+        vault.withdrawn_in_window = 1_000;
+        assert!(ensure_closable(&vault, 1_000, 1_000, false, 200).is_ok());
+    }
+
+    #[test]
+    fn test_paused_vault_cannot_be_closed() {
+        let mut vault = test_vault();
+        vault.paused = true;
+        for (lamports, force) in [(1_000, false), (1_000, true), (5_000, true)] {
+            assert_eq!(
+                ensure_closable(&vault, lamports, 1_000, force, 0).unwrap_err(),
+                VaultError::VaultPaused.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_rent_reserve_is_kept_back() {
+        let mut vault = test_vault();
+        assert_eq!(vault.reserved_lamports(890_880), 890_880);
+
+        vault.rent_reserve = 100_000;
+        assert_eq!(vault.reserved_lamports(890_880), 990_880);
+        let reserved = vault.reserved_lamports(890_880);
+        assert!(ensure_closable(&vault, 990_880, reserved, false, 0).is_ok());
+
+        vault.rent_reserve = u64::MAX;
+        assert_eq!(vault.reserved_lamports(890_880), u64::MAX);
+    }
+
+    #[test]
+    fn test_vault_summary_roundtrip() {
+        let mut vault = test_vault();
+        vault.total_deposited = 5_000;
+        vault.total_withdrawn = 1_000;
+        vault.tx_count = 3;
+        vault.created_at = 1_700_000_000;
+
+        let summary = VaultSummary::new(&vault, 4_000);
+        assert_eq!(summary.owner, vault.owner);
+        assert_eq!(summary.balance, 4_000);
+
+        let bytes = summary.try_to_vec().unwrap();
+        assert_eq!(VaultSummary::try_from_slice(&bytes).unwrap(), summary);
+    }
+
+    #[test]
+    fn test_check_withdrawal_does_not_record() {
+        let mut vault = test_vault();
+        vault.daily_withdraw_limit = 1_000;
+        vault.window_start = 100;
+        vault.withdrawn_in_window = 600;
+
+        assert_eq!(vault.check_withdrawal(200, 400).unwrap(), (100, 1_000));
+        assert_eq!(
+            vault.check_withdrawal(200, 401).unwrap_err(),
+            VaultError::WithdrawLimitExceeded.into()
+        );
+        // A new window starts from zero
+        let next_window = 100 + WITHDRAW_WINDOW_SECS;
+        assert_eq!(vault.check_withdrawal(next_window, 900).unwrap(), (next_window, 900));
+        assert_eq!(vault.withdrawn_in_window, 600);
+
+        vault.record_withdrawal(200, 400).unwrap();
+        assert_eq!((vault.window_start, vault.withdrawn_in_window), (100, 1_000));
+    }
+
+    #[test]
+    fn test_min_deposit_threshold() {
+        let mut vault = test_vault();
+        vault.min_deposit = 1_000;
+
+        assert_eq!(
+            vault.validate_deposit(999).unwrap_err(),
+            VaultError::BelowMinDeposit.into()
+        );
+        assert!(vault.validate_deposit(1_000).is_ok());
+    }
+
+    #[test]
+    fn test_zero_min_deposit_accepts_any_nonzero_amount() {
+        let vault = test_vault();
+        assert!(vault.validate_deposit(1).is_ok());
+        assert_eq!(vault.validate_deposit(0).unwrap_err(), VaultError::ZeroAmount.into());
+    }
+
+    #[test]
+    fn test_vault_name_normalization() {
+        assert!(validate_vault_name("Savings", true).is_ok());
+        assert!(validate_vault_name("Rainy day", true).is_ok());
+        for name in ["Savings ", " Savings", "Sav\u{7}ings", "Savings\n"] {
+            assert_eq!(
+                validate_vault_name(name, true).unwrap_err(),
+                VaultError::InvalidName.into()
+            );
+            assert!(validate_vault_name(name, false).is_ok());
+        }
+
+        // The length limit applies either way.
+        let long = "a".repeat(33);
+        assert_eq!(
+            validate_vault_name(&long, false).unwrap_err(),
+            VaultError::NameTooLong.into()
+        );
+        assert!(validate_vault_name(&long[..32], true).is_ok());
+    }
+
+    #[test]
+    fn test_missing_recipient_fails_before_transfer() {
+        let (paid, missing) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let owner = Pubkey::default();
+        let (mut lamports, mut data) = (5_000, []);
+        let accounts = [AccountInfo::new(
+            &paid,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        )];
+
+        // The missing recipient comes second, after one that resolves.
+        assert_eq!(
+            resolve_transfers(&accounts, &[paid, missing], &[100, 200], false).unwrap_err(),
+            ErrorCode::AccountNotEnoughKeys.into()
+        );
+        assert_eq!(accounts[0].lamports(), 5_000);
+
+        // Zero amounts need no account.
+        let resolved = resolve_transfers(&accounts, &[paid, missing], &[100, 0], false).unwrap();
+        assert_eq!(resolved.transfers.len(), 1);
+        assert_eq!(
+            (resolved.transfers[0].0.key, resolved.transfers[0].1),
+            (&paid, 100)
+        );
+        assert!(resolved.skipped.is_empty());
+    }
+
+    #[test]
+    fn test_partial_batch_skips_unpayable_recipients() {
+        let (paid, readonly, missing) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let owner = Pubkey::default();
+        let (mut paid_lamports, mut paid_data) = (0, []);
+        let (mut readonly_lamports, mut readonly_data) = (0, []);
+        let accounts = [
+            AccountInfo::new(
+                &paid,
+                false,
+                true,
+                &mut paid_lamports,
+                &mut paid_data,
+                &owner,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &readonly,
+                false,
+                false,
+                &mut readonly_lamports,
+                &mut readonly_data,
+                &owner,
+                false,
+                0,
+            ),
+        ];
+        let recipients = [readonly, paid, missing];
+        let amounts = [100, 200, 300];
+
+        // All-or-nothing by default.
+        assert_eq!(
+            resolve_transfers(&accounts, &recipients, &amounts, false).unwrap_err(),
+            VaultError::RecipientNotWritable.into()
+        );
+
+        // Only the paid recipient's amount would move.
+        let resolved = resolve_transfers(&accounts, &recipients, &amounts, true).unwrap();
+        assert_eq!(resolved.transfers.len(), 1);
+        assert_eq!(
+            (resolved.transfers[0].0.key, resolved.transfers[0].1),
+            (&paid, 200)
+        );
+        assert_eq!(resolved.skipped, [readonly, missing]);
+    }
 }